@@ -62,6 +62,15 @@ impl SupportedDevices {
         })
     }
 
+    /// `execution_provider`が利用可能かどうか。
+    pub(crate) fn supports(&self, execution_provider: ExecutionProvider) -> bool {
+        match execution_provider {
+            ExecutionProvider::Cpu => self.cpu,
+            ExecutionProvider::Cuda => self.cuda,
+            ExecutionProvider::DirectMl => self.dml,
+        }
+    }
+
     pub fn to_json(&self) -> serde_json::Value {
         serde_json::to_value(self).expect("should not fail")
     }
@@ -0,0 +1,213 @@
+use std::ops::Range;
+
+use crate::{Error, Result, UserDictWord, UserDictWordType};
+
+/// アクセント指定から作る単語の優先度。システム辞書やユーザー辞書の単語よりも優先させるため、
+/// 最も高い値とする。
+const HINT_PRIORITY: u32 = 10;
+
+/// `text`中のアクセント指定を取り除き、指定を除いたテキストと、指定された単語を返す。
+///
+/// アクセント指定は`会議{かいぎ:1}`のように、単語の直後に`{読み:アクセント核の位置}`を置いて行う。
+/// 読みはひらがなかカタカナで書き、アクセント核の位置は[`UserDictWord::accent_type`]と同じく
+/// 0から数える。指定の対象となる単語は`{`の直前にある漢字・カタカナ・英数字の並びであり、
+/// ひらがなや記号、空白で区切られる。
+///
+/// 単語の直後にあり、かつ直後が仮名である`{`はアクセント指定の始まりとみなす。それが上の形式で
+/// ない場合(`}`や`:`が無い、アクセント核の位置が数字でないなど)や、単語として登録できない指定
+/// (アクセント核の位置が読みのモーラ数を超えるなど)の場合は、その指定の範囲(バイト単位)を含む
+/// [`Error::InvalidAccentHint`]を返す。それ以外の`{`や`}`は、普通の文字としてそのまま残す。
+pub(crate) fn parse_accent_hints(text: &str) -> Result<(String, Vec<UserDictWord>)> {
+    let mut plain = String::with_capacity(text.len());
+    let mut words = vec![];
+    let mut rest_start = 0;
+    let mut search_start = 0;
+
+    while let Some(pos) = text[search_start..].find('{') {
+        let open = search_start + pos;
+        search_start = open + 1;
+
+        let Some((len, hint)) = parse_hint(&text[open..]) else {
+            continue;
+        };
+        let close = open + len;
+        let surface_start = text[..open]
+            .char_indices()
+            .rev()
+            .take_while(|&(i, c)| i >= rest_start && is_surface_char(c))
+            .last()
+            .map_or(open, |(i, _)| i);
+        let surface = &text[surface_start..open];
+        if surface.is_empty() {
+            continue;
+        }
+        let span = surface_start..close;
+
+        let hint = hint.map_err(|reason| invalid(span.clone(), reason))?;
+        let Ok(accent_type) = hint.accent_type.parse() else {
+            return Err(invalid(
+                span,
+                format!("アクセント核の位置が大きすぎます: {}", hint.accent_type),
+            ));
+        };
+        let word = UserDictWord::new(
+            surface,
+            to_katakana(hint.reading),
+            accent_type,
+            UserDictWordType::ProperNoun,
+            HINT_PRIORITY,
+        )
+        .map_err(|e| match e {
+            Error::InvalidWord(e) => invalid(span, e.to_string()),
+            e => e,
+        })?;
+
+        plain += &text[rest_start..open];
+        words.push(word);
+        rest_start = close;
+        search_start = close;
+    }
+    plain += &text[rest_start..];
+
+    Ok((plain, words))
+}
+
+/// `{読み:アクセント核の位置}`の形式の部分。
+struct Hint<'a> {
+    reading: &'a str,
+    accent_type: &'a str,
+}
+
+/// `text`の先頭の`{`から始まるアクセント指定を読み、指定とみなした部分のバイト数と共に返す。
+///
+/// `{`の直後が仮名でなければ、アクセント指定ではないものとして`None`を返す。指定とみなした部分は
+/// `}`まで、`}`が無ければ次の`{`の手前かテキストの末尾までとする。形式に当てはまらない場合は、
+/// その理由を`Err`として返す。
+fn parse_hint(text: &str) -> Option<(usize, std::result::Result<Hint<'_>, String>)> {
+    let content = text.strip_prefix('{')?;
+    if !content.starts_with(is_reading_char) {
+        return None;
+    }
+    let end = content.find(|c| c == '{' || c == '}');
+    let Some(end) = end.filter(|&end| content[end..].starts_with('}')) else {
+        let len = 1 + end.unwrap_or(content.len());
+        return Some((len, Err("`}`で閉じられていません".to_owned())));
+    };
+    let content = &content[..end];
+    Some((content.len() + 2, parse_hint_content(content)))
+}
+
+/// `{`と`}`の間の`読み:アクセント核の位置`を読む。
+fn parse_hint_content(content: &str) -> std::result::Result<Hint<'_>, String> {
+    let (reading, accent_type) = content
+        .split_once(':')
+        .ok_or_else(|| "アクセント核の位置がありません".to_owned())?;
+    if !reading.chars().all(is_reading_char) {
+        return Err(format!(
+            "読みはひらがなかカタカナで書く必要があります: {reading}"
+        ));
+    }
+    if accent_type.is_empty() || !accent_type.chars().all(|c| c.is_ascii_digit()) {
+        return Err(format!(
+            "アクセント核の位置が数字ではありません: {accent_type}"
+        ));
+    }
+    Ok(Hint {
+        reading,
+        accent_type,
+    })
+}
+
+fn invalid(span: Range<usize>, reason: impl Into<String>) -> Error {
+    Error::InvalidAccentHint {
+        span,
+        reason: reason.into(),
+    }
+}
+
+fn is_surface_char(c: char) -> bool {
+    matches!(
+        c,
+        '\u{3400}'..='\u{4DBF}'
+            | '\u{4E00}'..='\u{9FFF}'
+            | '々'
+            | '〆'
+            | 'ァ'..='ヺ'
+            | 'ー'
+            | '0'..='9'
+            | 'A'..='Z'
+            | 'a'..='z'
+            | '０'..='９'
+            | 'Ａ'..='Ｚ'
+            | 'ａ'..='ｚ'
+    )
+}
+
+fn is_reading_char(c: char) -> bool {
+    matches!(c, 'ぁ'..='ゖ' | 'ァ'..='ヺ' | 'ー')
+}
+
+fn to_katakana(reading: &str) -> String {
+    reading
+        .chars()
+        .map(|c| match c {
+            'ぁ'..='ゖ' => char::from_u32(c as u32 + ('ァ' as u32 - 'ぁ' as u32)).unwrap(),
+            c => c,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case("こんにちは", "こんにちは", &[])]
+    #[case("明日は会議{かいぎ:1}です", "明日は会議です", &[("会議", "カイギ", 1)])]
+    #[case("ボイボ{ボイボ:0}と東北{とーほく:3}", "ボイボと東北", &[("ボイボ", "ボイボ", 0), ("東北", "トーホク", 3)])]
+    #[case("VOICEVOX{ぼいすぼっくす:4}", "VOICEVOX", &[("ＶＯＩＣＥＶＯＸ", "ボイスボックス", 4)])]
+    fn parse_accent_hints_works(
+        #[case] text: &str,
+        #[case] expected_text: &str,
+        #[case] expected_words: &[(&str, &str, usize)],
+    ) {
+        let (plain, words) = parse_accent_hints(text).unwrap();
+        assert_eq!(expected_text, plain);
+        let words = words
+            .iter()
+            .map(|w| (&*w.surface, &*w.pronunciation, w.accent_type))
+            .collect::<Vec<_>>();
+        assert_eq!(expected_words, words);
+    }
+
+    #[rstest]
+    #[case("会議}です")]
+    #[case("関数{}")]
+    #[case("は{は:1}")]
+    #[case("会議{kaigi:1}")]
+    #[case("会議{{かいぎ:1}")]
+    fn parse_accent_hints_leaves_non_hints(#[case] text: &str) {
+        let (plain, words) = parse_accent_hints(text).unwrap();
+        assert_eq!(text, plain);
+        assert!(words.is_empty());
+    }
+
+    #[rstest]
+    #[case("明日は会議{かいぎ:4}", 9..28)]
+    #[case("会議{かいぎ:99999999999999999999999}", 0..41)]
+    #[case("{x} 会議{かいぎ:4}", 4..23)]
+    #[case("会議{かいぎ:a}です", 0..19)]
+    #[case("会議{かいぎ:1", 0..18)]
+    #[case("会議{かいぎ:1と東北{とーほく:3}", 0..27)]
+    #[case("会議{かいぎ}", 0..17)]
+    #[case("会議{かい ぎ:1}", 0..20)]
+    fn parse_accent_hints_reports_span(#[case] text: &str, #[case] expected_span: Range<usize>) {
+        let err = parse_accent_hints(text).unwrap_err();
+        assert!(
+            matches!(&err, Error::InvalidAccentHint { span, .. } if *span == expected_span),
+            "{err:?}",
+        );
+    }
+}
@@ -78,6 +78,11 @@ impl OjtPhoneme {
         "pau".into()
     }
 
+    /// `phoneme`が音素として有効かどうか。
+    pub fn is_valid_phoneme(phoneme: &str) -> bool {
+        PHONEME_MAP.contains_key(phoneme)
+    }
+
     pub fn phoneme_id(&self) -> i64 {
         if self.phoneme.is_empty() {
             -1
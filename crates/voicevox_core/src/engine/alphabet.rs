@@ -0,0 +1,121 @@
+use once_cell::sync::Lazy;
+use regex::{Captures, Regex};
+
+/// アルファベット一文字ずつの読み。
+const LETTER_READINGS: [&str; 26] = [
+    "エー",
+    "ビー",
+    "シー",
+    "ディー",
+    "イー",
+    "エフ",
+    "ジー",
+    "エイチ",
+    "アイ",
+    "ジェー",
+    "ケー",
+    "エル",
+    "エム",
+    "エヌ",
+    "オー",
+    "ピー",
+    "キュー",
+    "アール",
+    "エス",
+    "ティー",
+    "ユー",
+    "ブイ",
+    "ダブリュー",
+    "エックス",
+    "ワイ",
+    "ゼット",
+];
+
+/// 綴りで読まずに、単語として読むもの。大文字小文字は区別しない。
+const WORD_READINGS: &[(&str, &str)] = &[
+    ("voicevox", "ボイスボックス"),
+    ("hello", "ハロー"),
+    ("world", "ワールド"),
+    ("thank", "サンク"),
+    ("you", "ユー"),
+    ("yes", "イエス"),
+    ("no", "ノー"),
+    ("ok", "オーケー"),
+    ("web", "ウェブ"),
+    ("mail", "メール"),
+    ("phone", "フォン"),
+    ("app", "アプリ"),
+    ("open", "オープン"),
+    ("source", "ソース"),
+    ("free", "フリー"),
+    ("game", "ゲーム"),
+    ("music", "ミュージック"),
+    ("voice", "ボイス"),
+    ("box", "ボックス"),
+    ("talk", "トーク"),
+];
+
+static LATIN_WORD_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"[A-Za-zＡ-Ｚａ-ｚ]+").unwrap());
+
+/// テキスト中のアルファベットの単語をカタカナの読みに置き換える。
+///
+/// 組み込みの単語表にある単語は単語として読み、それ以外は一文字ずつ綴りで読む
+/// (`VOICEVOXで合成` → `ボイスボックスで合成`、`AIです` → `エーアイです`)。
+/// `is_user_word`が`true`を返す単語は、ユーザー辞書に任せるためにそのまま残す。
+///
+/// これは発音の推定を行うものではなく、あくまでベストエフォートの置き換えである。
+pub fn transliterate_alphabet(text: &str, is_user_word: impl Fn(&str) -> bool) -> String {
+    LATIN_WORD_REGEX
+        .replace_all(text, |caps: &Captures<'_>| {
+            let word = &caps[0];
+            if is_user_word(word) {
+                return word.to_owned();
+            }
+            let word = to_half_width_lowercase(word);
+            match WORD_READINGS.iter().find(|&&(w, _)| w == word) {
+                Some((_, reading)) => (*reading).to_owned(),
+                None => word
+                    .bytes()
+                    .map(|b| LETTER_READINGS[usize::from(b - b'a')])
+                    .collect(),
+            }
+        })
+        .into_owned()
+}
+
+fn to_half_width_lowercase(word: &str) -> String {
+    word.chars()
+        .map(|c| match c {
+            'Ａ'..='Ｚ' | 'ａ'..='ｚ' => char::from_u32(c as u32 - 0xfee0).unwrap(),
+            c => c,
+        })
+        .map(|c| c.to_ascii_lowercase())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case("こんにちは", "こんにちは")]
+    #[case("VOICEVOXで合成", "ボイスボックスで合成")]
+    #[case("ＶＯＩＣＥＶＯＸで合成", "ボイスボックスで合成")]
+    #[case("AIです", "エーアイです")]
+    #[case("Hello World", "ハロー ワールド")]
+    #[case("ok", "オーケー")]
+    #[case("NHK", "エヌエイチケー")]
+    fn transliterate_alphabet_works(#[case] text: &str, #[case] expected: &str) {
+        assert_eq!(expected, transliterate_alphabet(text, |_| false));
+    }
+
+    #[rstest]
+    fn transliterate_alphabet_skips_user_words() {
+        assert_eq!(
+            "ボイスボックスとNHK",
+            transliterate_alphabet("VOICEVOXとNHK", |word| word == "NHK")
+        );
+    }
+}
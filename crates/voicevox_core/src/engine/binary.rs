@@ -0,0 +1,187 @@
+//! [`AccentPhraseModel`]と[`AudioQueryModel`]のバイナリ形式のための、エンコーダとデコーダ。
+//!
+//! 先頭の2バイトは形式のバージョンと、何を表すデータであるかを示す種別である。以降の整数はLEB128の
+//! 可変長整数、浮動小数点数はリトルエンディアンの4バイト、文字列はバイト数とUTF-8のバイト列で表す。
+//!
+//! [`AccentPhraseModel`]: super::AccentPhraseModel
+//! [`AudioQueryModel`]: super::AudioQueryModel
+
+use crate::{Error, Result};
+
+/// 現在の形式のバージョン。形式を変えるときはこれを上げる。
+const FORMAT_VERSION: u8 = 1;
+
+pub(super) struct Encoder(Vec<u8>);
+
+impl Encoder {
+    pub(super) fn new(kind: u8) -> Self {
+        Self(vec![FORMAT_VERSION, kind])
+    }
+
+    pub(super) fn finish(self) -> Vec<u8> {
+        self.0
+    }
+
+    pub(super) fn u8(&mut self, value: u8) {
+        self.0.push(value);
+    }
+
+    pub(super) fn usize(&mut self, value: usize) {
+        let mut value = value as u64;
+        loop {
+            let byte = (value & 0x7f) as u8;
+            value >>= 7;
+            if value == 0 {
+                self.0.push(byte);
+                return;
+            }
+            self.0.push(byte | 0x80);
+        }
+    }
+
+    pub(super) fn f32(&mut self, value: f32) {
+        self.0.extend_from_slice(&value.to_le_bytes());
+    }
+
+    pub(super) fn str(&mut self, value: &str) {
+        self.bytes(value.as_bytes());
+    }
+
+    pub(super) fn bytes(&mut self, value: &[u8]) {
+        self.usize(value.len());
+        self.0.extend_from_slice(value);
+    }
+}
+
+pub(super) struct Decoder<'a> {
+    rest: &'a [u8],
+}
+
+impl<'a> Decoder<'a> {
+    /// 先頭のバージョンと種別を確かめる。
+    pub(super) fn new(bytes: &'a [u8], kind: u8) -> Result<Self> {
+        let mut decoder = Self { rest: bytes };
+        let version = decoder.u8()?;
+        if version != FORMAT_VERSION {
+            return Err(invalid(format!(
+                "対応していないバージョンです: {version} (対応しているバージョン: {FORMAT_VERSION})"
+            )));
+        }
+        let found = decoder.u8()?;
+        if found != kind {
+            return Err(invalid(format!(
+                "種別が異なります: {:?} (期待される種別: {:?})",
+                char::from(found),
+                char::from(kind),
+            )));
+        }
+        Ok(decoder)
+    }
+
+    /// 末尾に余分なバイトが無いことを確かめる。
+    pub(super) fn finish(self) -> Result<()> {
+        if !self.rest.is_empty() {
+            return Err(invalid(format!(
+                "末尾に余分な{}バイトがあります",
+                self.rest.len()
+            )));
+        }
+        Ok(())
+    }
+
+    pub(super) fn u8(&mut self) -> Result<u8> {
+        Ok(self.take(1)?[0])
+    }
+
+    pub(super) fn usize(&mut self) -> Result<usize> {
+        let mut value = 0_u64;
+        for shift in (0..64).step_by(7) {
+            let byte = self.u8()?;
+            value |= u64::from(byte & 0x7f) << shift;
+            if byte & 0x80 == 0 {
+                return value.try_into().map_err(|_| invalid("整数が大きすぎます"));
+            }
+        }
+        Err(invalid("整数が大きすぎます"))
+    }
+
+    pub(super) fn f32(&mut self) -> Result<f32> {
+        let bytes = self.take(4)?;
+        Ok(f32::from_le_bytes(bytes.try_into().unwrap()))
+    }
+
+    pub(super) fn string(&mut self) -> Result<String> {
+        let bytes = self.bytes()?;
+        String::from_utf8(bytes.to_owned()).map_err(|_| invalid("文字列がUTF-8ではありません"))
+    }
+
+    pub(super) fn bytes(&mut self) -> Result<&'a [u8]> {
+        let len = self.usize()?;
+        self.take(len)
+    }
+
+    /// 要素数を読み、`decode_element`で各要素を読む。
+    pub(super) fn vec<T>(
+        &mut self,
+        mut decode_element: impl FnMut(&mut Self) -> Result<T>,
+    ) -> Result<Vec<T>> {
+        let len = self.usize()?;
+        // 不正なデータで巨大な領域を確保しないよう、残りのバイト数で抑える
+        let mut elements = Vec::with_capacity(len.min(self.rest.len()));
+        for _ in 0..len {
+            elements.push(decode_element(self)?);
+        }
+        Ok(elements)
+    }
+
+    fn take(&mut self, len: usize) -> Result<&'a [u8]> {
+        if self.rest.len() < len {
+            return Err(invalid("データが途中で終わっています"));
+        }
+        let (taken, rest) = self.rest.split_at(len);
+        self.rest = rest;
+        Ok(taken)
+    }
+}
+
+fn invalid(reason: impl Into<String>) -> Error {
+    Error::InvalidBinaryData {
+        reason: reason.into(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::{Decoder, Encoder};
+    use crate::Error;
+
+    #[rstest]
+    #[case(0, &[0])]
+    #[case(127, &[0x7f])]
+    #[case(128, &[0x80, 0x01])]
+    #[case(300, &[0xac, 0x02])]
+    fn usize_works(#[case] value: usize, #[case] expected: &[u8]) {
+        let mut encoder = Encoder::new(b'T');
+        encoder.usize(value);
+        let bytes = encoder.finish();
+        assert_eq!(expected, &bytes[2..]);
+
+        let mut decoder = Decoder::new(&bytes, b'T').unwrap();
+        assert_eq!(value, decoder.usize().unwrap());
+        decoder.finish().unwrap();
+    }
+
+    #[rstest]
+    #[case(&[], "途中で終わって")]
+    #[case(&[2, b'T'], "バージョン")]
+    #[case(&[1, b'X'], "種別")]
+    fn decoder_new_rejects_invalid_header(#[case] bytes: &[u8], #[case] expected: &str) {
+        let err = Decoder::new(bytes, b'T').err().unwrap();
+        assert!(
+            matches!(&err, Error::InvalidBinaryData { reason } if reason.contains(expected)),
+            "{err:?}",
+        );
+    }
+}
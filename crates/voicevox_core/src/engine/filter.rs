@@ -0,0 +1,263 @@
+//! 出力する音声に掛ける、双2次(biquad)フィルタによるイコライザ。
+//!
+//! 係数は[Audio EQ Cookbook]に従って求める。
+//!
+//! [Audio EQ Cookbook]: https://www.w3.org/TR/audio-eq-cookbook/
+
+use std::f64::consts::PI;
+
+use serde::{Deserialize, Serialize};
+
+/// [`SynthesisOptions::eq`]の1つの帯域。
+///
+/// シリアライズ時は次のようになる。
+///
+/// ```json
+/// { "kind": "high_shelf", "frequency": 4000.0, "gain_db": 3.0, "q": 0.7071 }
+/// ```
+///
+/// [`SynthesisOptions::eq`]: crate::SynthesisOptions::eq
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct BiquadBand {
+    /// フィルタの種類。
+    pub kind: BiquadKind,
+    /// シェルフの場合は肩の周波数、ピーキングの場合は中心周波数(Hz)。
+    ///
+    /// 0からナイキスト周波数(12000Hz)の間に収まらない値は、その範囲に丸められる。
+    pub frequency: f32,
+    /// 増幅量(dB)。負の値の場合は減衰させる。
+    pub gain_db: f32,
+    /// Q値。
+    ///
+    /// ピーキングの場合は帯域の狭さを表す。シェルフの場合は肩の鋭さを表し、既定値の1/√2
+    /// (約0.7071)で行き過ぎの無い最も急な特性となる。正でない値は、十分小さい正の値に丸められる。
+    #[serde(default = "default_q")]
+    pub q: f32,
+}
+
+/// [`BiquadBand::kind`]の設定値。
+///
+/// シリアライズ時は`"low_shelf"`、`"high_shelf"`、`"peaking"`の文字列となる。
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BiquadKind {
+    /// `frequency`より低い帯域を`gain_db`だけ増幅する。
+    LowShelf,
+    /// `frequency`より高い帯域を`gain_db`だけ増幅する。
+    HighShelf,
+    /// `frequency`付近の帯域を`gain_db`だけ増幅する。
+    Peaking,
+}
+
+fn default_q() -> f32 {
+    std::f32::consts::FRAC_1_SQRT_2
+}
+
+/// `wave`に`bands`のフィルタを順に掛ける。
+///
+/// `bands`が空の場合は何もしない。
+pub(crate) fn equalize(bands: &[BiquadBand], sampling_rate: u32, wave: &mut [f32]) {
+    Equalizer::new(bands, sampling_rate).process(wave);
+}
+
+/// 連続する音声を分割して受け取り、[`equalize`]と同じフィルタを掛けるもの。
+///
+/// フィルタの状態を分割の境界を越えて保つため、分割した順に[`process`]に渡した結果は、全体を一度に
+/// [`equalize`]に渡した結果と一致する。
+///
+/// [`process`]: Self::process
+pub(crate) struct Equalizer(Vec<Biquad>);
+
+impl Equalizer {
+    pub(crate) fn new(bands: &[BiquadBand], sampling_rate: u32) -> Self {
+        Self(
+            bands
+                .iter()
+                .map(|band| Biquad::new(band, sampling_rate.into()))
+                .collect(),
+        )
+    }
+
+    /// `wave`に、続きとしてフィルタを掛ける。
+    pub(crate) fn process(&mut self, wave: &mut [f32]) {
+        for biquad in &mut self.0 {
+            for sample in &mut *wave {
+                *sample = biquad.process(*sample);
+            }
+        }
+    }
+}
+
+/// 転置直接形IIの双2次フィルタ。係数は`a0`で正規化してある。
+struct Biquad {
+    b: [f64; 3],
+    a: [f64; 2],
+    z: [f64; 2],
+}
+
+impl Biquad {
+    fn new(band: &BiquadBand, sampling_rate: f64) -> Self {
+        let nyquist = sampling_rate / 2.;
+        let frequency = f64::from(band.frequency).clamp(nyquist * 1e-4, nyquist * (1. - 1e-4));
+        let q = f64::from(band.q).max(1e-4);
+
+        let a = 10_f64.powf(f64::from(band.gain_db) / 40.);
+        let w0 = 2. * PI * frequency / sampling_rate;
+        let (sin, cos) = w0.sin_cos();
+        let alpha = sin / (2. * q);
+        let sqrt_a_alpha = 2. * a.sqrt() * alpha;
+
+        let ([b0, b1, b2], [a0, a1, a2]) = match band.kind {
+            BiquadKind::LowShelf => (
+                [
+                    a * ((a + 1.) - (a - 1.) * cos + sqrt_a_alpha),
+                    2. * a * ((a - 1.) - (a + 1.) * cos),
+                    a * ((a + 1.) - (a - 1.) * cos - sqrt_a_alpha),
+                ],
+                [
+                    (a + 1.) + (a - 1.) * cos + sqrt_a_alpha,
+                    -2. * ((a - 1.) + (a + 1.) * cos),
+                    (a + 1.) + (a - 1.) * cos - sqrt_a_alpha,
+                ],
+            ),
+            BiquadKind::HighShelf => (
+                [
+                    a * ((a + 1.) + (a - 1.) * cos + sqrt_a_alpha),
+                    -2. * a * ((a - 1.) + (a + 1.) * cos),
+                    a * ((a + 1.) + (a - 1.) * cos - sqrt_a_alpha),
+                ],
+                [
+                    (a + 1.) - (a - 1.) * cos + sqrt_a_alpha,
+                    2. * ((a - 1.) - (a + 1.) * cos),
+                    (a + 1.) - (a - 1.) * cos - sqrt_a_alpha,
+                ],
+            ),
+            BiquadKind::Peaking => (
+                [1. + alpha * a, -2. * cos, 1. - alpha * a],
+                [1. + alpha / a, -2. * cos, 1. - alpha / a],
+            ),
+        };
+
+        Self {
+            b: [b0 / a0, b1 / a0, b2 / a0],
+            a: [a1 / a0, a2 / a0],
+            z: [0.; 2],
+        }
+    }
+
+    fn process(&mut self, x: f32) -> f32 {
+        let x = f64::from(x);
+        let y = self.b[0] * x + self.z[0];
+        self.z[0] = self.b[1] * x - self.a[0] * y + self.z[1];
+        self.z[1] = self.b[2] * x - self.a[1] * y;
+        y as f32
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::{equalize, BiquadBand, BiquadKind, Equalizer};
+
+    const SAMPLING_RATE: u32 = 24000;
+
+    /// `frequency`の正弦波に`band`を掛けたときの、定常状態でのゲイン(dB)を求める。
+    fn measure_gain_db(band: BiquadBand, frequency: f32) -> f32 {
+        let input = (0..SAMPLING_RATE)
+            .map(|i| {
+                (2. * std::f32::consts::PI * frequency * i as f32 / SAMPLING_RATE as f32).sin()
+            })
+            .collect::<Vec<_>>();
+        let mut output = input.clone();
+        equalize(&[band], SAMPLING_RATE, &mut output);
+
+        let rms = |wave: &[f32]| {
+            let steady = &wave[wave.len() / 2..];
+            (steady.iter().map(|v| v * v).sum::<f32>() / steady.len() as f32).sqrt()
+        };
+        20. * (rms(&output) / rms(&input)).log10()
+    }
+
+    #[rstest]
+    #[case(BiquadKind::LowShelf, 200., 6., 0.7071, 50., 6.)]
+    #[case(BiquadKind::LowShelf, 200., 6., 0.7071, 8000., 0.)]
+    #[case(BiquadKind::HighShelf, 4000., 6., 0.7071, 10000., 6.)]
+    #[case(BiquadKind::HighShelf, 4000., 6., 0.7071, 50., 0.)]
+    #[case(BiquadKind::Peaking, 1000., -6., 1., 1000., -6.)]
+    #[case(BiquadKind::Peaking, 1000., -6., 1., 8000., 0.)]
+    #[case(BiquadKind::Peaking, 1000., 0., 1., 1000., 0.)]
+    fn equalize_works(
+        #[case] kind: BiquadKind,
+        #[case] frequency: f32,
+        #[case] gain_db: f32,
+        #[case] q: f32,
+        #[case] measured_at: f32,
+        #[case] expected_gain_db: f32,
+    ) {
+        let band = BiquadBand {
+            kind,
+            frequency,
+            gain_db,
+            q,
+        };
+        let actual = measure_gain_db(band, measured_at);
+        assert!((expected_gain_db - actual).abs() < 0.1, "{actual}");
+    }
+
+    #[rstest]
+    fn equalize_does_nothing_without_bands() {
+        let wave = [0.1, -0.2, 0.3, f32::MIN_POSITIVE];
+        let mut output = wave;
+        equalize(&[], SAMPLING_RATE, &mut output);
+        assert_eq!(wave, output);
+    }
+
+    #[rstest]
+    fn equalizer_continues_across_chunks() {
+        let bands = [
+            BiquadBand {
+                kind: BiquadKind::LowShelf,
+                frequency: 200.,
+                gain_db: 6.,
+                q: 0.7071,
+            },
+            BiquadBand {
+                kind: BiquadKind::Peaking,
+                frequency: 1000.,
+                gain_db: -3.,
+                q: 1.,
+            },
+        ];
+        let wave = (0..1000)
+            .map(|i| (i as f32 * 0.37).sin())
+            .collect::<Vec<_>>();
+
+        let mut expected = wave.clone();
+        equalize(&bands, SAMPLING_RATE, &mut expected);
+
+        let mut actual = wave;
+        let mut equalizer = Equalizer::new(&bands, SAMPLING_RATE);
+        for chunk in actual.chunks_mut(123) {
+            equalizer.process(chunk);
+        }
+        assert_eq!(expected, actual);
+    }
+
+    #[rstest]
+    fn biquad_band_serde_works() {
+        let band = serde_json::from_str::<BiquadBand>(
+            r#"{ "kind": "high_shelf", "frequency": 4000.0, "gain_db": 3.0 }"#,
+        )
+        .unwrap();
+        assert_eq!(
+            BiquadBand {
+                kind: BiquadKind::HighShelf,
+                frequency: 4000.,
+                gain_db: 3.,
+                q: std::f32::consts::FRAC_1_SQRT_2,
+            },
+            band,
+        );
+    }
+}
@@ -310,6 +310,10 @@ impl Utterance {
         text: impl AsRef<str>,
     ) -> Result<Self> {
         let labels = open_jtalk.extract_fullcontext(text)?;
+        Self::from_full_context_labels(labels)
+    }
+
+    pub fn from_full_context_labels(labels: Vec<String>) -> Result<Self> {
         Self::from_phonemes(
             labels
                 .into_iter()
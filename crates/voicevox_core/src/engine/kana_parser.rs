@@ -122,19 +122,58 @@ fn text_to_accent_phrase(phrase: &str) -> KanaParseResult<AccentPhraseModel> {
     ))
 }
 
+/// AquesTalk風記法のテキストを解析し、アクセント句の配列を生成する。
+///
+/// 受け付ける記法は次の通りである。
+///
+/// ```text
+/// テキスト     := 空白* アクセント句 (区切り アクセント句)* 空白*
+/// 区切り       := 空白* ("/" | "、") 空白* | 空白+
+/// アクセント句 := モーラ+ "？"?  (ただしモーラの間に"'"をちょうど1つ含む)
+/// モーラ       := "_"? カタカナのモーラ
+/// ```
+///
+/// - `'`はアクセント核の位置を表し、アクセント句の先頭には置けない。
+/// - `_`は直後のモーラを無声化する。
+/// - `？`はアクセント句を疑問系にする。アクセント句の末尾にのみ置ける。
+/// - `/`は無音を挟まずにアクセント句を区切り、`、`は無音を挟んでアクセント句を区切る。
+/// - 空白文字(半角・全角を問わない)は`/`と同じく無音を挟まない区切りとして扱う。ただし`/`や`、`とは
+///   異なり、連続していたり、他の区切りやテキストの先頭・末尾に隣接していたりしても空のアクセント句
+///   とはみなさない。`ア' 、イ'`のように`、`と組み合わせた場合は、`、`として扱う。
 pub fn parse_kana(text: &str) -> KanaParseResult<Vec<AccentPhraseModel>> {
     const TERMINATOR: char = '\0';
-    let mut parsed_result = Vec::new();
+    let mut parsed_result: Vec<AccentPhraseModel> = Vec::new();
     let chars_of_text = text.chars().chain([TERMINATOR]);
     let mut phrase = String::new();
+    // 直前のアクセント句が空白によって区切られ、その後にまだ`/`や`、`が来ていないかどうか
+    let mut after_soft_delimiter = false;
     for letter in chars_of_text {
-        if letter == TERMINATOR || letter == PAUSE_DELIMITER || letter == NOPAUSE_DELIMITER {
+        let is_soft_delimiter = letter.is_whitespace();
+        if letter == TERMINATOR
+            || letter == PAUSE_DELIMITER
+            || letter == NOPAUSE_DELIMITER
+            || is_soft_delimiter
+        {
             if phrase.is_empty() {
+                if is_soft_delimiter {
+                    continue;
+                }
+                if after_soft_delimiter {
+                    after_soft_delimiter = false;
+                    if letter == PAUSE_DELIMITER {
+                        let last = parsed_result
+                            .last_mut()
+                            .expect("should be preceded by a phrase");
+                        last.set_pause_mora(Some(pause_mora()));
+                    }
+                    continue;
+                }
                 return Err(KanaParseError(format!(
                     "accent phrase at position of {} is empty",
                     parsed_result.len()
                 )));
             }
+            after_soft_delimiter = is_soft_delimiter;
             let is_interrogative = phrase.contains(WIDE_INTERROGATION_MARK);
             if is_interrogative {
                 if phrase.find(WIDE_INTERROGATION_MARK).unwrap()
@@ -149,14 +188,7 @@ pub fn parse_kana(text: &str) -> KanaParseResult<Vec<AccentPhraseModel>> {
             let accent_phrase = {
                 let mut accent_phrase = text_to_accent_phrase(&phrase)?;
                 if letter == PAUSE_DELIMITER {
-                    accent_phrase.set_pause_mora(Some(MoraModel::new(
-                        PAUSE_DELIMITER.to_string(),
-                        None,
-                        None,
-                        "pau".to_string(),
-                        0.,
-                        0.,
-                    )));
+                    accent_phrase.set_pause_mora(Some(pause_mora()));
                 }
                 accent_phrase.set_is_interrogative(is_interrogative);
                 accent_phrase
@@ -170,6 +202,17 @@ pub fn parse_kana(text: &str) -> KanaParseResult<Vec<AccentPhraseModel>> {
     Ok(parsed_result)
 }
 
+fn pause_mora() -> MoraModel {
+    MoraModel::new(
+        PAUSE_DELIMITER.to_string(),
+        None,
+        None,
+        "pau".to_string(),
+        0.,
+        0.,
+    )
+}
+
 pub fn create_kana(accent_phrases: &[AccentPhraseModel]) -> String {
     let mut text = String::new();
     for phrase in accent_phrases {
@@ -249,6 +292,34 @@ mod tests {
         let result = parse_kana(text);
         assert_eq!(result.is_ok(), result_is_ok_expected, "{:?}", result);
     }
+
+    #[rstest]
+    #[case("ア'カ サ'タ", "ア'カ/サ'タ")]
+    #[case("ア'カ　サ'タ", "ア'カ/サ'タ")]
+    #[case("  ア'カ   サ'タ  ", "ア'カ/サ'タ")]
+    #[case("ア'カ / サ'タ", "ア'カ/サ'タ")]
+    #[case("ア'カ 、サ'タ", "ア'カ、サ'タ")]
+    #[case("ア'カ、 サ'タ", "ア'カ、サ'タ")]
+    #[case("ア'カ？ サ'タ、ナ'", "ア'カ？/サ'タ、ナ'")]
+    fn parse_kana_treats_whitespaces_as_soft_delimiters(
+        #[case] text: &str,
+        #[case] expected: &str,
+    ) {
+        assert_eq!(parse_kana(expected).unwrap(), parse_kana(text).unwrap());
+    }
+
+    #[rstest]
+    #[case("")]
+    #[case("   ")]
+    #[case("ア'カ /")]
+    #[case("ア'カ、 ")]
+    #[case("ア'カ / 、サ'タ")]
+    #[case("ア'カ ？")]
+    fn parse_kana_rejects_empty_phrases_around_whitespaces(#[case] text: &str) {
+        let result = parse_kana(text);
+        assert!(result.is_err(), "{result:?}");
+    }
+
     #[rstest]
     fn test_create_kana() {
         let text = "アンドロ'イドワ、デンキ'/ヒ'_ツジノ/ユメ'オ/ミ'ルカ？";
@@ -1,16 +1,28 @@
+mod accent_hint;
 mod acoustic_feature_extractor;
+mod alphabet;
+mod binary;
+mod filter;
 mod full_context_label;
 mod kana_parser;
 mod model;
 mod mora_list;
+mod normalize;
 mod open_jtalk;
+mod sentence;
 mod synthesis_engine;
 
 use super::*;
 
+pub(crate) use self::accent_hint::parse_accent_hints;
 pub use self::acoustic_feature_extractor::*;
+pub use self::alphabet::transliterate_alphabet;
+pub(crate) use self::filter::{equalize, Equalizer};
+pub use self::filter::{BiquadBand, BiquadKind};
 pub use self::full_context_label::*;
 pub use self::kana_parser::*;
 pub use self::model::*;
-pub use self::open_jtalk::OpenJtalk;
+pub use self::normalize::{normalize, normalize_width};
+pub use self::open_jtalk::{Analyzer, OpenJtalk, OpenJtalkError};
+pub use self::sentence::*;
 pub use self::synthesis_engine::*;
@@ -1,10 +1,20 @@
+use std::hash::{Hash, Hasher};
+
 use derive_getters::Getters;
 use derive_new::new;
 use serde::{Deserialize, Serialize};
 
+use super::binary::{Decoder, Encoder};
+use super::synthesis_engine::FRAME_RATE;
+use super::{create_kana, OjtPhoneme, SynthesisEngine};
+use crate::{Error, Result};
+
 /* 各フィールドのjsonフィールド名はsnake_caseとする*/
 
 /// モーラ（子音＋母音）ごとの情報。
+///
+/// 音長と音高はビット表現で比較される。そのため`NaN`同士はビット表現が同じであれば等しく、`0.0`と
+/// `-0.0`は等しくない。
 #[derive(Clone, Debug, new, Getters, Deserialize, Serialize)]
 pub struct MoraModel {
     /// 文字。
@@ -21,8 +31,83 @@ pub struct MoraModel {
     pitch: f32,
 }
 
+impl MoraModel {
+    fn bitwise_key(&self) -> impl Eq + Hash + '_ {
+        (
+            &self.text,
+            &self.consonant,
+            self.consonant_length.map(f32::to_bits),
+            &self.vowel,
+            self.vowel_length.to_bits(),
+            self.pitch.to_bits(),
+        )
+    }
+
+    fn encode(&self, encoder: &mut Encoder) {
+        encoder.str(&self.text);
+        match (&self.consonant, self.consonant_length) {
+            (Some(consonant), Some(consonant_length)) => {
+                encoder.u8(1);
+                encoder.str(consonant);
+                encoder.f32(consonant_length);
+            }
+            (Some(consonant), None) => {
+                encoder.u8(2);
+                encoder.str(consonant);
+            }
+            (None, Some(consonant_length)) => {
+                encoder.u8(3);
+                encoder.f32(consonant_length);
+            }
+            (None, None) => encoder.u8(0),
+        }
+        encoder.str(&self.vowel);
+        encoder.f32(self.vowel_length);
+        encoder.f32(self.pitch);
+    }
+
+    fn decode(decoder: &mut Decoder<'_>) -> Result<Self> {
+        let text = decoder.string()?;
+        let (consonant, consonant_length) = match decoder.u8()? {
+            0 => (None, None),
+            1 => (Some(decoder.string()?), Some(decoder.f32()?)),
+            2 => (Some(decoder.string()?), None),
+            3 => (None, Some(decoder.f32()?)),
+            tag => {
+                return Err(Error::InvalidBinaryData {
+                    reason: format!("子音の種別が不正です: {tag}"),
+                })
+            }
+        };
+        Ok(Self {
+            text,
+            consonant,
+            consonant_length,
+            vowel: decoder.string()?,
+            vowel_length: decoder.f32()?,
+            pitch: decoder.f32()?,
+        })
+    }
+}
+
+impl PartialEq for MoraModel {
+    fn eq(&self, other: &Self) -> bool {
+        self.bitwise_key() == other.bitwise_key()
+    }
+}
+
+impl Eq for MoraModel {}
+
+impl Hash for MoraModel {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.bitwise_key().hash(state);
+    }
+}
+
 /// AccentPhrase (アクセント句ごとの情報)。
-#[derive(Clone, Debug, new, Getters, Deserialize, Serialize)]
+///
+/// モーラの音長と音高は[`MoraModel`]と同じくビット表現で比較される。
+#[derive(Clone, Debug, PartialEq, Eq, Hash, new, Getters, Deserialize, Serialize)]
 pub struct AccentPhraseModel {
     /// モーラの配列。
     moras: Vec<MoraModel>,
@@ -31,21 +116,286 @@ pub struct AccentPhraseModel {
     /// 後ろに無音を付けるかどうか。
     pause_mora: Option<MoraModel>,
     /// 疑問系かどうか。
+    ///
+    /// テキストの解析では、アクセント句が「？」で終わる場合に`true`となる。
+    /// [`SynthesisOptions::enable_interrogative_upspeak`]が有効な場合、`true`のアクセント句の語尾の
+    /// 音高が上げられる。このフィールドを持たないJSONからデシリアライズした場合は`false`となる。
+    ///
+    /// [`SynthesisOptions::enable_interrogative_upspeak`]: crate::SynthesisOptions::enable_interrogative_upspeak
     #[serde(default)]
     is_interrogative: bool,
 }
 
 impl AccentPhraseModel {
+    /// モーラの配列から、疑問系でないアクセント句を作る。
+    ///
+    /// [`new`]とは異なり、音声合成に使えるかどうかを[`AudioQueryModel::from_accent_phrases`]と同じ
+    /// 規則で検証する。テキストを解析せずに、プログラムで組み立てたモーラから音声合成を行うためのもの
+    /// である。
+    ///
+    /// # Errors
+    ///
+    /// `moras`が空の場合、`accent`が`1`以上モーラ数以下でない場合、もしくはモーラの音素・音長・音高が
+    /// 不正な場合、`index`を`0`とする[`Error::InconsistentAccentPhrase`]を返す。
+    ///
+    /// [`new`]: Self::new
+    pub fn from_moras(
+        moras: Vec<MoraModel>,
+        accent: usize,
+        pause_mora: Option<MoraModel>,
+    ) -> Result<Self> {
+        let accent_phrase = Self::new(moras, accent, pause_mora, false);
+        accent_phrase
+            .validate()
+            .map_err(|reason| Error::InconsistentAccentPhrase { index: 0, reason })?;
+        Ok(accent_phrase)
+    }
+
+    /// モーラとアクセント位置が音声合成に使える状態かどうかを検証する。
+    ///
+    /// 以下のいずれかに当てはまる場合、その理由を返す。
+    ///
+    /// - モーラが空である。
+    /// - アクセント位置が`1`以上モーラ数以下でない。
+    /// - 無音を含むいずれかのモーラについて、
+    ///     - 子音もしくは母音が音素として無効である。
+    ///     - 子音とその音長の一方のみが存在する。
+    ///     - 音長もしくは音高が有限の非負の値でない。
+    fn validate(&self) -> std::result::Result<(), String> {
+        if self.moras.is_empty() {
+            return Err("モーラがありません".to_owned());
+        }
+        if !(1..=self.moras.len()).contains(&self.accent) {
+            return Err(format!(
+                "アクセント位置{}がモーラ数{}の範囲外です",
+                self.accent,
+                self.moras.len(),
+            ));
+        }
+
+        let is_valid_value = |value: f32| value.is_finite() && value >= 0.;
+        for mora in self.moras.iter().chain(&self.pause_mora) {
+            let invalid_phoneme = mora
+                .consonant
+                .iter()
+                .chain([&mora.vowel])
+                .find(|phoneme| !OjtPhoneme::is_valid_phoneme(phoneme));
+            if let Some(phoneme) = invalid_phoneme {
+                return Err(format!("`{}`の音素`{phoneme}`は無効です", mora.text));
+            }
+            if mora.consonant.is_some() != mora.consonant_length.is_some() {
+                return Err(format!(
+                    "`{}`の子音とその音長の一方のみが指定されています",
+                    mora.text,
+                ));
+            }
+            let values = mora
+                .consonant_length
+                .iter()
+                .chain([&mora.vowel_length, &mora.pitch]);
+            if !values.copied().all(is_valid_value) {
+                return Err(format!("`{}`の音長もしくは音高が不正です", mora.text));
+            }
+        }
+        Ok(())
+    }
+
     pub(super) fn set_pause_mora(&mut self, pause_mora: Option<MoraModel>) {
         self.pause_mora = pause_mora;
     }
 
-    pub(super) fn set_is_interrogative(&mut self, is_interrogative: bool) {
+    /// [`is_interrogative`]を設定する。
+    ///
+    /// [`is_interrogative`]: Self::is_interrogative
+    pub fn set_is_interrogative(&mut self, is_interrogative: bool) {
         self.is_interrogative = is_interrogative;
     }
+
+    /// `mora_offset`番目のモーラの前で分割し、後ろ側を返す。`mora_offset`は`1`以上モーラ数未満で
+    /// なければならない。
+    ///
+    /// 規則は[`AudioQueryModel::split_accent_phrase`]と同じである。
+    fn split_off(&mut self, mora_offset: usize) -> Self {
+        let accent = self.accent;
+        let latter = Self::new(
+            self.moras.split_off(mora_offset),
+            accent.saturating_sub(mora_offset).max(1),
+            self.pause_mora.take(),
+            self.is_interrogative,
+        );
+        self.accent = accent.min(mora_offset);
+        self.is_interrogative = false;
+        latter
+    }
+
+    /// 後ろに`latter`を結合する。
+    ///
+    /// 規則は[`AudioQueryModel::merge_accent_phrases`]と同じである。
+    fn append(&mut self, latter: Self) {
+        self.moras.extend(latter.moras);
+        self.pause_mora = latter.pause_mora;
+        self.is_interrogative = latter.is_interrogative;
+    }
+
+    /// JSONよりも小さく、速く読み書きできる独自のバイナリ形式に変換する。
+    ///
+    /// テキストの解析結果をディスクにキャッシュするような用途のためのものである。形式は
+    /// [`AudioQueryModel::to_bytes`]と同様であり、先頭にバージョンを表す1バイトを含む。
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut encoder = Encoder::new(Self::BINARY_KIND);
+        self.encode(&mut encoder);
+        encoder.finish()
+    }
+
+    /// [`to_bytes`]で変換したバイナリから復元する。
+    ///
+    /// # Errors
+    ///
+    /// 形式が不正な場合や、バージョンが対応していないものである場合、
+    /// [`Error::InvalidBinaryData`]を返す。
+    ///
+    /// [`to_bytes`]: Self::to_bytes
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        let mut decoder = Decoder::new(bytes, Self::BINARY_KIND)?;
+        let accent_phrase = Self::decode(&mut decoder)?;
+        decoder.finish()?;
+        Ok(accent_phrase)
+    }
+    const BINARY_KIND: u8 = b'A';
+
+    fn encode(&self, encoder: &mut Encoder) {
+        encoder.usize(self.moras.len());
+        for mora in &self.moras {
+            mora.encode(encoder);
+        }
+        encoder.usize(self.accent);
+        encoder.u8(u8::from(self.pause_mora.is_some()) | u8::from(self.is_interrogative) << 1);
+        if let Some(pause_mora) = &self.pause_mora {
+            pause_mora.encode(encoder);
+        }
+    }
+
+    fn decode(decoder: &mut Decoder<'_>) -> Result<Self> {
+        let moras = decoder.vec(MoraModel::decode)?;
+        let accent = decoder.usize()?;
+        let flags = decoder.u8()?;
+        let pause_mora = if flags & 1 != 0 {
+            Some(MoraModel::decode(decoder)?)
+        } else {
+            None
+        };
+        Ok(Self::new(moras, accent, pause_mora, flags & 2 != 0))
+    }
+}
+
+/// 全てのアクセント句を、1モーラずつのアクセント句に分割する。
+///
+/// 各アクセント句のアクセント核は`1`となる。無音と疑問系の指定は、元のアクセント句の最後のモーラ
+/// から成るアクセント句に引き継がれる。
+pub(crate) fn split_into_moras(accent_phrases: Vec<AccentPhraseModel>) -> Vec<AccentPhraseModel> {
+    let mut split = vec![];
+    for mut accent_phrase in accent_phrases {
+        let mut pieces = vec![];
+        while accent_phrase.moras.len() > 1 {
+            let last = accent_phrase.moras.len() - 1;
+            pieces.push(accent_phrase.split_off(last));
+        }
+        pieces.push(accent_phrase);
+        split.extend(pieces.into_iter().rev());
+    }
+    split
+}
+
+/// モーラ数が`min_moras`未満のアクセント句を、後ろのアクセント句に結合する。
+///
+/// 末尾のアクセント句は前のアクセント句に結合する。無音が続くアクセント句は、その前後で読み方が
+/// 変わらないよう、後ろのアクセント句と結合しない。
+pub(crate) fn merge_short_accent_phrases(
+    accent_phrases: Vec<AccentPhraseModel>,
+    min_moras: usize,
+) -> Vec<AccentPhraseModel> {
+    let is_short_and_open =
+        |ap: &AccentPhraseModel| ap.moras.len() < min_moras && ap.pause_mora.is_none();
+
+    let mut merged = Vec::<AccentPhraseModel>::with_capacity(accent_phrases.len());
+    for accent_phrase in accent_phrases {
+        match merged.last_mut() {
+            Some(last) if is_short_and_open(last) => last.append(accent_phrase),
+            _ => merged.push(accent_phrase),
+        }
+    }
+    if let [.., former, last] = &*merged {
+        if last.moras.len() < min_moras && former.pause_mora.is_none() {
+            let last = merged.pop().unwrap();
+            merged.last_mut().unwrap().append(last);
+        }
+    }
+    merged
+}
+
+/// 2つの[`AudioQueryModel`]の間でのアクセント句の差分。[`AudioQueryModel::diff`]が返す。
+///
+/// `index`は比較元(`self`)での、`other_index`は比較先(`other`)でのアクセント句の位置を表す。
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AccentPhraseDiff {
+    /// `self`の`index`番目のアクセント句が、`other`の`other_index`番目のアクセント句に変更された。
+    Changed { index: usize, other_index: usize },
+    /// `other`の`other_index`番目のアクセント句が、`self`の`index`番目のアクセント句の前(`index`が
+    /// アクセント句の数と等しい場合は末尾)に挿入された。
+    Inserted { index: usize, other_index: usize },
+    /// `self`の`index`番目のアクセント句が削除された。`other_index`は削除された位置に対応する
+    /// `other`での位置を表す。
+    Removed { index: usize, other_index: usize },
+}
+
+/// AudioQueryのうち、アクセント句以外の全体に関わるパラメータ。
+///
+/// [`AudioQueryModel::from_accent_phrases`]でアクセント句と組み合わせて使う。既定値は
+/// [`Synthesizer::audio_query`]がスタイルの既定値を持たない場合に返すものと同じである。
+///
+/// [`Synthesizer::audio_query`]: crate::Synthesizer::audio_query
+#[derive(Clone, Copy, Debug, PartialEq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct ProsodyParams {
+    /// 全体の話速。
+    pub speed_scale: f32,
+    /// 全体の音高。
+    pub pitch_scale: f32,
+    /// 全体の抑揚。
+    pub intonation_scale: f32,
+    /// 全体の音量。
+    pub volume_scale: f32,
+    /// 音声の前の無音時間。
+    pub pre_phoneme_length: f32,
+    /// 音声の後の無音時間。
+    pub post_phoneme_length: f32,
+    /// 音声データの出力サンプリングレート。
+    pub output_sampling_rate: u32,
+    /// 音声データをステレオ出力するか否か。
+    pub output_stereo: bool,
+}
+
+impl Default for ProsodyParams {
+    fn default() -> Self {
+        Self {
+            speed_scale: 1.,
+            pitch_scale: 0.,
+            intonation_scale: 1.,
+            volume_scale: 1.,
+            pre_phoneme_length: 0.1,
+            post_phoneme_length: 0.1,
+            output_sampling_rate: 24000,
+            output_stereo: false,
+        }
+    }
 }
 
 /// AudioQuery (音声合成用のクエリ)。
+///
+/// 浮動小数点数のフィールドは[`MoraModel`]と同じくビット表現で比較される。同じ音声が合成される
+/// AudioQueryであっても、[`kana`]が異なれば等しくない。
+///
+/// [`kana`]: Self::kana
 #[allow(clippy::too_many_arguments)]
 #[derive(Clone, new, Getters, Deserialize, Serialize)]
 pub struct AudioQueryModel {
@@ -76,12 +426,537 @@ pub struct AudioQueryModel {
     kana: Option<String>,
 }
 
+impl AudioQueryModel {
+    /// アクセント句の配列と[`ProsodyParams`]からAudioQueryを組み立てる。
+    ///
+    /// [`kana`]は`None`となる。
+    ///
+    /// # Errors
+    ///
+    /// 音声合成に使えないアクセント句が含まれる場合、[`Error::InconsistentAccentPhrase`]を返す。
+    ///
+    /// [`kana`]: Self::kana
+    pub fn from_accent_phrases(
+        accent_phrases: Vec<AccentPhraseModel>,
+        prosody: &ProsodyParams,
+    ) -> Result<Self> {
+        validate_accent_phrases(&accent_phrases)?;
+
+        let ProsodyParams {
+            speed_scale,
+            pitch_scale,
+            intonation_scale,
+            volume_scale,
+            pre_phoneme_length,
+            post_phoneme_length,
+            output_sampling_rate,
+            output_stereo,
+        } = *prosody;
+        Ok(Self::new(
+            accent_phrases,
+            speed_scale,
+            pitch_scale,
+            intonation_scale,
+            volume_scale,
+            pre_phoneme_length,
+            post_phoneme_length,
+            output_sampling_rate,
+            output_stereo,
+            None,
+        ))
+    }
+
+    /// アクセント句が全て音声合成に使える状態かどうかを検証する。
+    ///
+    /// # Errors
+    ///
+    /// 音声合成に使えないアクセント句が含まれる場合、[`Error::InconsistentAccentPhrase`]を返す。
+    pub fn validate(&self) -> Result<()> {
+        validate_accent_phrases(&self.accent_phrases)
+    }
+
+    /// 音声合成したときに、実質的に無音となるかどうかを返す。
+    ///
+    /// 推論は行わず、以下のいずれかに当てはまるかどうかを見る。記号のみのテキストのように、読み
+    /// 上げられるものが無い場合に`true`となる。
+    ///
+    /// - アクセント句が無い。
+    /// - 全体の音量がほぼ`0`である。
+    /// - 無音を除く全てのモーラについて、音高もしくは音長がほぼ`0`である。
+    pub fn is_effectively_silent(&self) -> bool {
+        self.silence_reason().is_some()
+    }
+
+    /// 実質的に無音となる場合、その理由を返す。
+    pub(crate) fn silence_reason(&self) -> Option<&'static str> {
+        const NEAR_ZERO: f32 = 1e-3;
+
+        if self.accent_phrases.is_empty() {
+            return Some("アクセント句がありません");
+        }
+        if self.volume_scale.abs() < NEAR_ZERO {
+            return Some("全体の音量がほぼ0です");
+        }
+        let is_audible = |mora: &MoraModel| {
+            mora.pitch > NEAR_ZERO
+                && mora.consonant_length.unwrap_or(0.) + mora.vowel_length > NEAR_ZERO
+        };
+        if !self
+            .accent_phrases
+            .iter()
+            .flat_map(|accent_phrase| &accent_phrase.moras)
+            .any(is_audible)
+        {
+            return Some("全てのモーラの音高もしくは音長がほぼ0です");
+        }
+        None
+    }
+
+    /// 全てのモーラの音高を`0`にしたAudioQueryを返す。
+    ///
+    /// 音高が`0`のモーラは無声音として推論されるため、声帯の振動による成分が無い、ささやき声の
+    /// ような音声となる。
+    pub(crate) fn whispered(&self) -> Self {
+        let mut query = self.clone();
+        for accent_phrase in &mut query.accent_phrases {
+            for mora in accent_phrase
+                .moras
+                .iter_mut()
+                .chain(&mut accent_phrase.pause_mora)
+            {
+                mora.pitch = 0.;
+            }
+        }
+        query
+    }
+
+    /// `index`番目のアクセント句を、`mora_offset`番目のモーラの前で2つに分割する。
+    ///
+    /// アクセント核は、それを含む側のアクセント句に引き継がれる。アクセント核を含まない側の
+    /// アクセント句は、前側なら末尾のモーラ、後ろ側なら先頭のモーラをアクセント核とする。無音と
+    /// 疑問系の指定は後ろ側のアクセント句に引き継がれる。
+    ///
+    /// [`kana`]が`Some`の場合、分割後のアクセント句から作り直される。
+    ///
+    /// # Errors
+    ///
+    /// `index`がアクセント句の範囲外の場合は[`Error::InvalidAccentPhraseIndex`]を、`mora_offset`が
+    /// `0`もしくはモーラ数以上の場合は[`Error::InvalidMoraOffset`]を返す。
+    ///
+    /// [`kana`]: Self::kana
+    pub fn split_accent_phrase(&mut self, index: usize, mora_offset: usize) -> Result<()> {
+        let accent_phrase = self
+            .accent_phrases
+            .get_mut(index)
+            .ok_or(Error::InvalidAccentPhraseIndex { index })?;
+        if !(1..accent_phrase.moras.len()).contains(&mora_offset) {
+            return Err(Error::InvalidMoraOffset { mora_offset });
+        }
+
+        let latter = accent_phrase.split_off(mora_offset);
+        self.accent_phrases.insert(index + 1, latter);
+        self.update_kana();
+        Ok(())
+    }
+
+    /// `index`番目と`index + 1`番目のアクセント句を1つに結合する。
+    ///
+    /// アクセント核は前側のアクセント句のものを引き継ぐ。無音と疑問系の指定は後ろ側のアクセント句の
+    /// ものを引き継ぐ。
+    ///
+    /// [`kana`]が`Some`の場合、結合後のアクセント句から作り直される。
+    ///
+    /// # Errors
+    ///
+    /// `index + 1`がアクセント句の範囲外の場合は[`Error::InvalidAccentPhraseIndex`]を返す。
+    ///
+    /// [`kana`]: Self::kana
+    pub fn merge_accent_phrases(&mut self, index: usize) -> Result<()> {
+        let latter_index = index
+            .checked_add(1)
+            .filter(|&i| i < self.accent_phrases.len())
+            .ok_or(Error::InvalidAccentPhraseIndex { index })?;
+
+        let latter = self.accent_phrases.remove(latter_index);
+        self.accent_phrases[index].append(latter);
+
+        self.update_kana();
+        Ok(())
+    }
+
+    /// 音声合成したときの`start_sec`秒から`end_sec`秒までの範囲を含む、アクセント句のみを残した
+    /// AudioQueryを返す。
+    ///
+    /// 範囲はアクセント句の境界まで広げられる。アクセント句は分割しないため、範囲の境界でもアクセント
+    /// 核や推論済みの音高・音素長はそのまま保たれる。各アクセント句の時間は[`Synthesizer::synthesis`]
+    /// と同じ計算で、疑問文の調整を行わないものとして求める。最初のアクセント句は音声の前の無音を、
+    /// 最後のアクセント句は音声の後の無音を含むものとして扱う。
+    ///
+    /// 範囲外の時刻は音声の先頭もしくは末尾に丸められ、`start_sec`が`end_sec`より大きい場合は
+    /// `start_sec`を含むアクセント句のみを残す。音声の前後の無音の長さなど、アクセント句以外の値は
+    /// 元のAudioQueryのものを引き継ぐ。そのため、合成される音声は元の音声の該当部分の前後に無音を
+    /// 付けたものとなる。[`kana`]が`Some`の場合、残したアクセント句から作り直される。
+    ///
+    /// アクセント句が無い場合は、そのままのAudioQueryを返す。
+    ///
+    /// [`Synthesizer::synthesis`]: crate::Synthesizer::synthesis
+    /// [`kana`]: Self::kana
+    pub fn sub_range(&self, start_sec: f32, end_sec: f32) -> Self {
+        let mut query = self.clone();
+        if self.accent_phrases.is_empty() {
+            return query;
+        }
+
+        let ends = SynthesisEngine::accent_phrase_frame_ends(self, false);
+        // `sec`秒の時点を含むアクセント句の位置。末尾以降は最後のアクセント句とする
+        let index_at = |sec: f32| {
+            let frame = (sec * FRAME_RATE) as usize;
+            ends.partition_point(|&end| end <= frame)
+                .min(ends.len() - 1)
+        };
+        let first = index_at(start_sec);
+        let last = index_at(end_sec).max(first);
+
+        query.accent_phrases.truncate(last + 1);
+        query.accent_phrases.drain(..first);
+        query.update_kana();
+        query
+    }
+
+    /// 全てのモーラの子音長と母音長を、それぞれ`consonant_scale`倍と`vowel_scale`倍にする。
+    ///
+    /// 全体の話速である[`speed_scale`]とは独立に作用する。アクセント句の後ろの無音
+    /// ([`AccentPhraseModel::pause_mora`])は変更しない。
+    ///
+    /// 長さが0の音素があると音声合成に失敗するため、変更後の長さは[`MIN_PHONEME_LENGTH`]以上に
+    /// 切り上げられる。
+    ///
+    /// [`speed_scale`]: Self::speed_scale
+    /// [`MIN_PHONEME_LENGTH`]: Self::MIN_PHONEME_LENGTH
+    pub fn scale_phoneme_lengths(&mut self, consonant_scale: f32, vowel_scale: f32) {
+        let scale = |length: f32, scale: f32| (length * scale).max(Self::MIN_PHONEME_LENGTH);
+
+        for mora in self
+            .accent_phrases
+            .iter_mut()
+            .flat_map(|accent_phrase| &mut accent_phrase.moras)
+        {
+            if let Some(consonant_length) = &mut mora.consonant_length {
+                *consonant_length = scale(*consonant_length, consonant_scale);
+            }
+            mora.vowel_length = scale(mora.vowel_length, vowel_scale);
+        }
+    }
+
+    /// [`scale_phoneme_lengths`]で変更した音素の長さの下限(秒)。
+    ///
+    /// [`scale_phoneme_lengths`]: Self::scale_phoneme_lengths
+    pub const MIN_PHONEME_LENGTH: f32 = 0.01;
+
+    /// 現在のアクセント句からAquesTalk風記法を生成する。
+    ///
+    /// [`kana`]と異なり、アクセント句を編集した後でも常にその内容を反映する。生成された文字列を
+    /// AquesTalk風記法として解析すると、同じアクセント句の構造(モーラ、アクセント位置、無音、疑問系)
+    /// が得られる。
+    ///
+    /// [`kana`]: Self::kana
+    pub fn to_kana(&self) -> String {
+        create_kana(&self.accent_phrases)
+    }
+
+    /// JSONよりも小さく、速く読み書きできる独自のバイナリ形式に変換する。
+    ///
+    /// テキストの解析結果をディスクにキャッシュするような用途のためのものである。C APIなどで使われる
+    /// JSONとは異なり、形式は外部に公開するものではない。先頭にバージョンを表す1バイトを含み、形式を
+    /// 変更した場合はバージョンを上げる。[`kana`]も含まれる。
+    ///
+    /// [`kana`]: Self::kana
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut encoder = Encoder::new(Self::BINARY_KIND);
+        encoder.usize(self.accent_phrases.len());
+        for accent_phrase in &self.accent_phrases {
+            accent_phrase.encode(&mut encoder);
+        }
+        for value in [
+            self.speed_scale,
+            self.pitch_scale,
+            self.intonation_scale,
+            self.volume_scale,
+            self.pre_phoneme_length,
+            self.post_phoneme_length,
+        ] {
+            encoder.f32(value);
+        }
+        encoder.usize(self.output_sampling_rate as usize);
+        encoder.u8(u8::from(self.output_stereo) | u8::from(self.kana.is_some()) << 1);
+        if let Some(kana) = &self.kana {
+            encoder.str(kana);
+        }
+        encoder.finish()
+    }
+
+    /// [`to_bytes`]で変換したバイナリから復元する。
+    ///
+    /// # Errors
+    ///
+    /// 形式が不正な場合や、バージョンが対応していないものである場合、
+    /// [`Error::InvalidBinaryData`]を返す。
+    ///
+    /// [`to_bytes`]: Self::to_bytes
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        let mut decoder = Decoder::new(bytes, Self::BINARY_KIND)?;
+        let accent_phrases = decoder.vec(AccentPhraseModel::decode)?;
+        let speed_scale = decoder.f32()?;
+        let pitch_scale = decoder.f32()?;
+        let intonation_scale = decoder.f32()?;
+        let volume_scale = decoder.f32()?;
+        let pre_phoneme_length = decoder.f32()?;
+        let post_phoneme_length = decoder.f32()?;
+        let output_sampling_rate =
+            decoder
+                .usize()?
+                .try_into()
+                .map_err(|_| Error::InvalidBinaryData {
+                    reason: "サンプリングレートが大きすぎます".to_owned(),
+                })?;
+        let flags = decoder.u8()?;
+        let kana = if flags & 2 != 0 {
+            Some(decoder.string()?)
+        } else {
+            None
+        };
+        decoder.finish()?;
+        Ok(Self::new(
+            accent_phrases,
+            speed_scale,
+            pitch_scale,
+            intonation_scale,
+            volume_scale,
+            pre_phoneme_length,
+            post_phoneme_length,
+            output_sampling_rate,
+            flags & 1 != 0,
+            kana,
+        ))
+    }
+    const BINARY_KIND: u8 = b'Q';
+
+    /// `self`から`other`へのアクセント句の差分を、`self`での位置の順に返す。
+    ///
+    /// 変更されていないアクセント句は含まれない。差分のあるアクセント句のみを音声合成し直すことで、
+    /// 編集された文章を効率的に音声合成し直すことができる。
+    ///
+    /// アクセント句はモーラ、アクセント位置、無音、疑問系が全て等しい場合に変更されていないと
+    /// みなす。アクセント句全体の対応は最長共通部分列によって求め、対応が取れなかった区間では前から
+    /// 順に[`AccentPhraseDiff::Changed`]として対にし、余ったものを[`AccentPhraseDiff::Removed`]
+    /// もしくは[`AccentPhraseDiff::Inserted`]とする。
+    pub fn diff(&self, other: &Self) -> Vec<AccentPhraseDiff> {
+        let (old, new) = (&self.accent_phrases, &other.accent_phrases);
+
+        // 前後の共通部分を先に取り除き、編集箇所が少ない場合の計算量を抑える
+        let prefix = old.iter().zip(new).take_while(|(a, b)| a == b).count();
+        let suffix = old[prefix..]
+            .iter()
+            .rev()
+            .zip(new[prefix..].iter().rev())
+            .take_while(|(a, b)| a == b)
+            .count();
+        let old = &old[prefix..old.len() - suffix];
+        let new = &new[prefix..new.len() - suffix];
+
+        // lcs[i][j]: old[i..]とnew[j..]の最長共通部分列の長さ
+        let mut lcs = vec![vec![0_usize; new.len() + 1]; old.len() + 1];
+        for i in (0..old.len()).rev() {
+            for j in (0..new.len()).rev() {
+                lcs[i][j] = if old[i] == new[j] {
+                    lcs[i + 1][j + 1] + 1
+                } else {
+                    lcs[i + 1][j].max(lcs[i][j + 1])
+                };
+            }
+        }
+
+        let is_matched = |i: usize, j: usize| i < old.len() && j < new.len() && old[i] == new[j];
+
+        let mut diffs = vec![];
+        let (mut i, mut j) = (0, 0);
+        while i < old.len() || j < new.len() {
+            if is_matched(i, j) {
+                i += 1;
+                j += 1;
+                continue;
+            }
+
+            let (start_i, start_j) = (i, j);
+            while (i < old.len() || j < new.len()) && !is_matched(i, j) {
+                if j == new.len() || (i < old.len() && lcs[i + 1][j] >= lcs[i][j + 1]) {
+                    i += 1;
+                } else {
+                    j += 1;
+                }
+            }
+
+            let (removed, inserted) = (i - start_i, j - start_j);
+            let changed = removed.min(inserted);
+            diffs.extend((0..changed).map(|k| AccentPhraseDiff::Changed {
+                index: prefix + start_i + k,
+                other_index: prefix + start_j + k,
+            }));
+            diffs.extend((changed..removed).map(|k| AccentPhraseDiff::Removed {
+                index: prefix + start_i + k,
+                other_index: prefix + j,
+            }));
+            diffs.extend((changed..inserted).map(|k| AccentPhraseDiff::Inserted {
+                index: prefix + i,
+                other_index: prefix + start_j + k,
+            }));
+        }
+        diffs
+    }
+
+    fn update_kana(&mut self) {
+        if self.kana.is_some() {
+            self.kana = Some(self.to_kana());
+        }
+    }
+}
+
+impl AudioQueryModel {
+    fn bitwise_key(&self) -> impl Eq + Hash + '_ {
+        (
+            &self.accent_phrases,
+            [
+                self.speed_scale,
+                self.pitch_scale,
+                self.intonation_scale,
+                self.volume_scale,
+                self.pre_phoneme_length,
+                self.post_phoneme_length,
+            ]
+            .map(f32::to_bits),
+            self.output_sampling_rate,
+            self.output_stereo,
+            &self.kana,
+        )
+    }
+}
+
+impl PartialEq for AudioQueryModel {
+    fn eq(&self, other: &Self) -> bool {
+        self.bitwise_key() == other.bitwise_key()
+    }
+}
+
+impl Eq for AudioQueryModel {}
+
+impl Hash for AudioQueryModel {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.bitwise_key().hash(state);
+    }
+}
+
+fn validate_accent_phrases(accent_phrases: &[AccentPhraseModel]) -> Result<()> {
+    for (index, accent_phrase) in accent_phrases.iter().enumerate() {
+        accent_phrase
+            .validate()
+            .map_err(|reason| Error::InconsistentAccentPhrase { index, reason })?;
+    }
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
+    use std::collections::hash_map::DefaultHasher;
+
     use super::*;
+    use crate::engine::parse_kana;
     use crate::*;
     use pretty_assertions::assert_eq;
 
+    #[rstest]
+    #[case("ア'カ/サ'タ", 1., 5.5, false)]
+    #[case("ア'カ/サ'タ", 0., 5.5, true)]
+    #[case("ア'カ/サ'タ", 1., 0., true)]
+    #[case("", 1., 5.5, true)]
+    fn is_effectively_silent_works(
+        #[case] kana: &str,
+        #[case] volume_scale: f32,
+        #[case] pitch: f32,
+        #[case] expected: bool,
+    ) {
+        let mut accent_phrases = if kana.is_empty() {
+            vec![]
+        } else {
+            parse_kana(kana).unwrap()
+        };
+        for mora in accent_phrases.iter_mut().flat_map(|ap| &mut ap.moras) {
+            mora.vowel_length = 0.1;
+            mora.pitch = pitch;
+        }
+        let mut query = audio_query(accent_phrases);
+        query.volume_scale = volume_scale;
+        assert_eq!(expected, query.is_effectively_silent());
+    }
+
+    #[rstest]
+    fn whispered_works() {
+        let mut accent_phrases = parse_kana("ア'カ/サ'タ、ナ'").unwrap();
+        for mora in accent_phrases.iter_mut().flat_map(|ap| &mut ap.moras) {
+            mora.vowel_length = 0.1;
+            mora.pitch = 5.5;
+        }
+        let query = audio_query(accent_phrases);
+
+        let whispered = query.whispered();
+        let moras = |query: &AudioQueryModel| {
+            query
+                .accent_phrases
+                .iter()
+                .flat_map(|ap| ap.moras.iter().chain(&ap.pause_mora))
+                .cloned()
+                .collect::<Vec<_>>()
+        };
+        assert!(moras(&whispered).iter().all(|mora| mora.pitch == 0.));
+        assert_eq!(
+            moras(&query)
+                .iter()
+                .map(|mora| (&mora.text, mora.vowel_length))
+                .collect::<Vec<_>>(),
+            moras(&whispered)
+                .iter()
+                .map(|mora| (&mora.text, mora.vowel_length))
+                .collect::<Vec<_>>(),
+        );
+        assert_eq!(query.kana, whispered.kana);
+    }
+
+    #[rstest]
+    #[case(0.5, 0.5, true)]
+    #[case(f32::NAN, f32::NAN, true)]
+    #[case(0., -0., false)]
+    #[case(0.5, 0.25, false)]
+    fn audio_query_model_eq_and_hash_use_bit_patterns(
+        #[case] pitch: f32,
+        #[case] other_pitch: f32,
+        #[case] expected: bool,
+    ) {
+        let query_with_pitch = |pitch| {
+            let mut accent_phrases = parse_kana("ア'カ/サ'タ").unwrap();
+            accent_phrases[1].moras[0].pitch = pitch;
+            audio_query(accent_phrases)
+        };
+        let hash = |query: &AudioQueryModel| {
+            let mut hasher = DefaultHasher::new();
+            query.hash(&mut hasher);
+            hasher.finish()
+        };
+
+        let query = query_with_pitch(pitch);
+        let other = query_with_pitch(other_pitch);
+        assert_eq!(expected, query == other);
+        assert_eq!(expected, hash(&query) == hash(&other));
+    }
+
     #[rstest]
     fn check_audio_query_model_json_field_snake_case() {
         let audio_query_model =
@@ -90,6 +965,383 @@ mod tests {
         check_json_field_snake_case(&val);
     }
 
+    #[rstest]
+    #[case(false)]
+    #[case(true)]
+    fn accent_phrase_is_interrogative_roundtrips(#[case] is_interrogative: bool) {
+        let accent_phrase = AccentPhraseModel::new(vec![mora("ア")], 1, None, is_interrogative);
+        let json = serde_json::to_value(&accent_phrase).unwrap();
+        assert_eq!(json["is_interrogative"], is_interrogative);
+        assert_eq!(
+            accent_phrase,
+            serde_json::from_value::<AccentPhraseModel>(json).unwrap(),
+        );
+    }
+
+    #[rstest]
+    fn accent_phrase_is_interrogative_defaults_to_false() {
+        let json = serde_json::json!({
+            "moras": [mora("ア")],
+            "accent": 1,
+            "pause_mora": null,
+        });
+        let accent_phrase = serde_json::from_value::<AccentPhraseModel>(json).unwrap();
+        assert!(!accent_phrase.is_interrogative());
+    }
+
+    fn mora(text: &str) -> MoraModel {
+        MoraModel::new(text.into(), None, None, "a".into(), 0.1, 5.)
+    }
+
+    fn audio_query(accent_phrases: Vec<AccentPhraseModel>) -> AudioQueryModel {
+        let kana = create_kana(&accent_phrases);
+        AudioQueryModel::new(
+            accent_phrases,
+            1.,
+            0.,
+            1.,
+            1.,
+            0.1,
+            0.1,
+            24000,
+            false,
+            Some(kana),
+        )
+    }
+
+    #[rstest]
+    #[case(1, "ア'カ/サ'タ")]
+    #[case(2, "アカ'/サ'タ")]
+    #[case(4, "アカ'/サタ'")]
+    fn split_accent_phrase_works(#[case] accent: usize, #[case] expected_kana: &str) {
+        let mut query = audio_query(vec![AccentPhraseModel::new(
+            ["ア", "カ", "サ", "タ"].map(mora).into(),
+            accent,
+            None,
+            false,
+        )]);
+
+        query.split_accent_phrase(0, 2).unwrap();
+
+        assert_eq!(2, query.accent_phrases().len());
+        assert_eq!(Some(expected_kana), query.kana().as_deref());
+    }
+
+    #[rstest]
+    #[case(0., 0.1, "ア'カ")]
+    #[case(0.35, 0.45, "サ'タ")]
+    #[case(0.25, 0.55, "ア'カ/サ'タ/ナ'ハ")]
+    #[case(0.35, 100., "サ'タ/ナ'ハ")]
+    #[case(-1., 0.3, "ア'カ/サ'タ")]
+    #[case(0.6, 0.1, "ナ'ハ")]
+    fn sub_range_works(#[case] start_sec: f32, #[case] end_sec: f32, #[case] expected_kana: &str) {
+        // 各モーラは0.1秒、音声の前後の無音も0.1秒のため、アクセント句は0〜0.3秒、0.3〜0.5秒、
+        // 0.5〜0.8秒となる
+        let query = audio_query(
+            [["ア", "カ"], ["サ", "タ"], ["ナ", "ハ"]]
+                .map(|moras| AccentPhraseModel::new(moras.map(mora).into(), 1, None, false))
+                .into(),
+        );
+
+        let sub_query = query.sub_range(start_sec, end_sec);
+
+        assert_eq!(Some(expected_kana), sub_query.kana().as_deref());
+        assert_eq!(query.pre_phoneme_length(), sub_query.pre_phoneme_length());
+        assert_eq!(query.post_phoneme_length(), sub_query.post_phoneme_length());
+    }
+
+    #[rstest]
+    #[case("ア'カ/サ_タ'")]
+    #[case("コンニチワ'、ゲ'ンキデスカ？")]
+    #[case("ハ'ジメマシテ、_シ'/ヨロシク'？")]
+    fn to_kana_round_trips(#[case] kana: &str) {
+        let mut query = audio_query(parse_kana(kana).unwrap());
+        query.split_accent_phrase(0, 1).unwrap();
+
+        let structure = |accent_phrases: &[AccentPhraseModel]| {
+            accent_phrases
+                .iter()
+                .map(|phrase| {
+                    (
+                        phrase
+                            .moras()
+                            .iter()
+                            .map(|mora| mora.text().clone())
+                            .collect::<Vec<_>>(),
+                        *phrase.accent(),
+                        phrase.pause_mora().is_some(),
+                        *phrase.is_interrogative(),
+                    )
+                })
+                .collect::<Vec<_>>()
+        };
+        let reparsed = parse_kana(&query.to_kana()).unwrap();
+        assert_eq!(structure(query.accent_phrases()), structure(&reparsed));
+    }
+
+    #[rstest]
+    fn merge_accent_phrases_works() {
+        let mut query = audio_query(vec![
+            AccentPhraseModel::new(["ア", "カ"].map(mora).into(), 1, None, false),
+            AccentPhraseModel::new(["サ", "タ"].map(mora).into(), 2, None, true),
+        ]);
+
+        query.merge_accent_phrases(0).unwrap();
+
+        assert_eq!(1, query.accent_phrases().len());
+        assert_eq!(Some("ア'カサタ？"), query.kana().as_deref());
+    }
+
+    #[rstest]
+    #[case("ア'カ/サ'タ、ナ'？", "ア'/カ'/サ'/タ'、ナ'？")]
+    #[case("コンニチワ'", "コ'/ン'/ニ'/チ'/ワ'")]
+    fn split_into_moras_works(#[case] kana: &str, #[case] expected: &str) {
+        let accent_phrases = split_into_moras(parse_kana(kana).unwrap());
+        assert_eq!(expected, create_kana(&accent_phrases));
+    }
+
+    #[rstest]
+    #[case("ア'/カ'/サ'タ/ナ'ニヌ", 2, "ア'カ/サ'タ/ナ'ニヌ")]
+    #[case("ア'/カ'/サ'タ/ナ'ニヌ", 3, "ア'カサタ/ナ'ニヌ")]
+    #[case("ア'、カ'/サ'タ", 2, "ア'、カ'サタ")]
+    #[case("ア'カサ/タ'", 2, "ア'カサタ")]
+    #[case("ア'", 2, "ア'")]
+    #[case("ア'/カ'", 0, "ア'/カ'")]
+    fn merge_short_accent_phrases_works(
+        #[case] kana: &str,
+        #[case] min_moras: usize,
+        #[case] expected: &str,
+    ) {
+        let accent_phrases = merge_short_accent_phrases(parse_kana(kana).unwrap(), min_moras);
+        assert_eq!(expected, create_kana(&accent_phrases));
+    }
+
+    #[rstest]
+    #[case(0.5, 1.)]
+    #[case(2., 1.)]
+    #[case(0., 1.)]
+    #[case(1., 0.5)]
+    fn scale_phoneme_lengths_works(#[case] consonant_scale: f32, #[case] vowel_scale: f32) {
+        let mora = |text: &str, consonant_length| {
+            MoraModel::new(
+                text.into(),
+                Some("k".into()),
+                Some(consonant_length),
+                "a".into(),
+                0.1,
+                5.,
+            )
+        };
+        let mut query = audio_query(vec![AccentPhraseModel::new(
+            vec![mora("カ", 0.04), mora("カ", 0.06)],
+            1,
+            None,
+            false,
+        )]);
+
+        query.scale_phoneme_lengths(consonant_scale, vowel_scale);
+
+        let (consonant_total, vowel_total) = query.accent_phrases()[0].moras().iter().fold(
+            (0., 0.),
+            |(consonant_total, vowel_total), mora| {
+                (
+                    consonant_total + mora.consonant_length().unwrap(),
+                    vowel_total + mora.vowel_length(),
+                )
+            },
+        );
+        let expected_consonant_total = (0.04 * consonant_scale)
+            .max(AudioQueryModel::MIN_PHONEME_LENGTH)
+            + (0.06 * consonant_scale).max(AudioQueryModel::MIN_PHONEME_LENGTH);
+        assert!((expected_consonant_total - consonant_total).abs() < 1e-6);
+        assert!((0.2 * vowel_scale - vowel_total).abs() < 1e-6);
+    }
+
+    #[rstest]
+    fn accent_phrase_edit_rejects_invalid_positions() {
+        let mut query = audio_query(vec![AccentPhraseModel::new(
+            ["ア", "カ"].map(mora).into(),
+            1,
+            None,
+            false,
+        )]);
+
+        assert!(matches!(
+            query.split_accent_phrase(1, 1),
+            Err(Error::InvalidAccentPhraseIndex { index: 1 })
+        ));
+        assert!(matches!(
+            query.split_accent_phrase(0, 2),
+            Err(Error::InvalidMoraOffset { mora_offset: 2 })
+        ));
+        assert!(matches!(
+            query.merge_accent_phrases(0),
+            Err(Error::InvalidAccentPhraseIndex { index: 0 })
+        ));
+        assert!(matches!(
+            query.merge_accent_phrases(usize::MAX),
+            Err(Error::InvalidAccentPhraseIndex { index: usize::MAX })
+        ));
+    }
+
+    #[rstest]
+    #[case("ア'カ/サ'タ/ナ'ハ", "ア'カ/サ'タ/ナ'ハ", &[])]
+    #[case(
+        "ア'カ/サ'タ/ナ'ハ",
+        "ア'カ/サタ'/ナ'ハ",
+        &[AccentPhraseDiff::Changed { index: 1, other_index: 1 }]
+    )]
+    #[case(
+        "ア'カ/ナ'ハ",
+        "ア'カ/サ'タ/ナ'ハ",
+        &[AccentPhraseDiff::Inserted { index: 1, other_index: 1 }]
+    )]
+    #[case(
+        "ア'カ/サ'タ/ナ'ハ",
+        "ア'カ/ナ'ハ",
+        &[AccentPhraseDiff::Removed { index: 1, other_index: 1 }]
+    )]
+    #[case(
+        "ア'カ/サ'タ",
+        "ア'カ/サ'タ/ナ'ハ/マ'ヤ",
+        &[
+            AccentPhraseDiff::Inserted { index: 2, other_index: 2 },
+            AccentPhraseDiff::Inserted { index: 2, other_index: 3 },
+        ]
+    )]
+    #[case(
+        "ア'カ/サ'タ/ナ'ハ",
+        "マ'ヤ/サ'タ/ラ'ワ/ガ'ザ",
+        &[
+            AccentPhraseDiff::Changed { index: 0, other_index: 0 },
+            AccentPhraseDiff::Changed { index: 2, other_index: 2 },
+            AccentPhraseDiff::Inserted { index: 3, other_index: 3 },
+        ]
+    )]
+    fn diff_works(
+        #[case] kana: &str,
+        #[case] other_kana: &str,
+        #[case] expected: &[AccentPhraseDiff],
+    ) {
+        let query = audio_query(parse_kana(kana).unwrap());
+        let other = audio_query(parse_kana(other_kana).unwrap());
+        assert_eq!(expected, query.diff(&other));
+    }
+
+    #[rstest]
+    #[case(AccentPhraseModel::new(vec![], 1, None, false))]
+    #[case(AccentPhraseModel::new(["ア", "カ"].map(mora).into(), 0, None, false))]
+    #[case(AccentPhraseModel::new(["ア", "カ"].map(mora).into(), 3, None, false))]
+    #[case(AccentPhraseModel::new(
+        vec![MoraModel::new("ア".into(), None, None, "x".into(), 0.1, 5.)],
+        1,
+        None,
+        false,
+    ))]
+    #[case(AccentPhraseModel::new(
+        vec![MoraModel::new("カ".into(), Some("k".into()), None, "a".into(), 0.1, 5.)],
+        1,
+        None,
+        false,
+    ))]
+    #[case(AccentPhraseModel::new(
+        vec![MoraModel::new("ア".into(), None, None, "a".into(), f32::NAN, 5.)],
+        1,
+        None,
+        false,
+    ))]
+    #[case(AccentPhraseModel::new(
+        vec![mora("ア")],
+        1,
+        Some(MoraModel::new("、".into(), None, None, "pau".into(), -0.1, 0.)),
+        false,
+    ))]
+    fn from_accent_phrases_rejects_inconsistent_accent_phrase(
+        #[case] accent_phrase: AccentPhraseModel,
+    ) {
+        let accent_phrases = vec![
+            AccentPhraseModel::new(["ア", "カ"].map(mora).into(), 1, None, false),
+            accent_phrase,
+        ];
+        assert!(matches!(
+            AudioQueryModel::from_accent_phrases(accent_phrases, &ProsodyParams::default()),
+            Err(Error::InconsistentAccentPhrase { index: 1, .. }),
+        ));
+    }
+
+    #[rstest]
+    #[case(1, true)]
+    #[case(2, true)]
+    #[case(0, false)]
+    #[case(3, false)]
+    fn from_moras_validates_accent(#[case] accent: usize, #[case] expected_ok: bool) {
+        let moras = ["ア", "カ"].map(mora).to_vec();
+        let pause_mora = Some(MoraModel::new(
+            "、".into(),
+            None,
+            None,
+            "pau".into(),
+            0.1,
+            0.,
+        ));
+        let result = AccentPhraseModel::from_moras(moras.clone(), accent, pause_mora.clone());
+        if expected_ok {
+            assert_eq!(
+                AccentPhraseModel::new(moras, accent, pause_mora, false),
+                result.unwrap(),
+            );
+        } else {
+            assert!(
+                matches!(
+                    result,
+                    Err(Error::InconsistentAccentPhrase { index: 0, .. })
+                ),
+                "{result:?}",
+            );
+        }
+    }
+
+    #[rstest]
+    #[case("ア'カ/サ'タ")]
+    #[case("コンニチワ'、ゲ'ンキデスカ？")]
+    #[case("ハ'ジメマシテ、_シ'/ヨロシク'？")]
+    fn binary_round_trip_works(#[case] kana: &str) {
+        let query = audio_query(parse_kana(kana).unwrap());
+
+        let bytes = query.to_bytes();
+        assert!(bytes.len() < serde_json::to_vec(&query).unwrap().len());
+        assert_eq!(query, AudioQueryModel::from_bytes(&bytes).unwrap());
+
+        for accent_phrase in query.accent_phrases() {
+            let bytes = accent_phrase.to_bytes();
+            assert_eq!(
+                *accent_phrase,
+                AccentPhraseModel::from_bytes(&bytes).unwrap()
+            );
+        }
+    }
+
+    #[rstest]
+    fn from_bytes_rejects_invalid_data() {
+        let query = audio_query(parse_kana("ア'カ/サ'タ").unwrap());
+        let bytes = query.to_bytes();
+
+        let mut trailing = bytes.clone();
+        trailing.push(0);
+        assert!(matches!(
+            AudioQueryModel::from_bytes(&trailing),
+            Err(Error::InvalidBinaryData { .. }),
+        ));
+        assert!(matches!(
+            AudioQueryModel::from_bytes(&bytes[..bytes.len() - 1]),
+            Err(Error::InvalidBinaryData { .. }),
+        ));
+        assert!(matches!(
+            AccentPhraseModel::from_bytes(&bytes),
+            Err(Error::InvalidBinaryData { .. }),
+        ));
+    }
+
     fn check_json_field_snake_case(val: &serde_json::Value) {
         use serde_json::Value::*;
 
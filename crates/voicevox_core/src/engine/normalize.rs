@@ -0,0 +1,284 @@
+use once_cell::sync::Lazy;
+use regex::{Captures, Regex};
+
+const DIGIT_READINGS: [&str; 10] = [
+    "ゼロ",
+    "イチ",
+    "ニ",
+    "サン",
+    "ヨン",
+    "ゴ",
+    "ロク",
+    "ナナ",
+    "ハチ",
+    "キュウ",
+];
+
+/// 「日」の前に来る日付の読み。ここに無いものは漢数字に「ニチ」を付けて読む。
+const DAY_READINGS: &[(u64, &str)] = &[
+    (1, "ツイタチ"),
+    (2, "フツカ"),
+    (3, "ミッカ"),
+    (4, "ヨッカ"),
+    (5, "イツカ"),
+    (6, "ムイカ"),
+    (7, "ナノカ"),
+    (8, "ヨウカ"),
+    (9, "ココノカ"),
+    (10, "トオカ"),
+    (14, "ジュウヨッカ"),
+    (20, "ハツカ"),
+    (24, "ニジュウヨッカ"),
+];
+
+const SYMBOL_READINGS: &[(char, &str)] = &[
+    ('%', "パーセント"),
+    ('&', "アンド"),
+    ('+', "プラス"),
+    ('=', "イコール"),
+    ('@', "アット"),
+    ('#', "シャープ"),
+];
+
+static SEPARATED_NUMBER_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"\d{1,3}(?:,\d{3})+").unwrap());
+static PHONE_NUMBER_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"\d{2,4}-\d{2,4}-\d{3,4}").unwrap());
+static DATE_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"(\d{1,2})月(\d{1,2})日").unwrap());
+static YEN_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"[¥￥](\d+)").unwrap());
+static DOLLAR_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"\$(\d+(?:\.\d+)?)").unwrap());
+static DECIMAL_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"(\d+)\.(\d+)").unwrap());
+static INTEGER_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"\d+").unwrap());
+
+/// OpenJTalkに渡す前に、数字や日付、通貨、一部の記号を読み上げやすい形に展開する。
+///
+/// - 整数は漢数字に変換する (`2024年` → `二千二十四年`)。
+/// - 小数は「点」で区切り、小数部を一桁ずつ読む (`3.14` → `三点イチヨン`)。
+/// - `090-1234-5678`のような電話番号らしい数字や、`0`から始まる数字は一桁ずつ読む。
+/// - `M月D日`の「日」は慣用的な読みにする (`4月1日` → `四月ツイタチ`)。
+/// - `¥`と`$`は後ろの数字に「円」「ドル」を付ける。
+pub fn normalize(text: &str) -> String {
+    let text = to_half_width_digits(text);
+    let text =
+        SEPARATED_NUMBER_REGEX.replace_all(&text, |caps: &Captures<'_>| caps[0].replace(',', ""));
+    let text = PHONE_NUMBER_REGEX.replace_all(&text, |caps: &Captures<'_>| {
+        caps[0]
+            .split('-')
+            .map(read_digits)
+            .collect::<Vec<_>>()
+            .join("ノ")
+    });
+    let text = DATE_REGEX.replace_all(&text, |caps: &Captures<'_>| {
+        format!("{}月{}", read_number(&caps[1]), read_day(&caps[2]))
+    });
+    let text = YEN_REGEX.replace_all(&text, |caps: &Captures<'_>| {
+        format!("{}円", read_number(&caps[1]))
+    });
+    let text = DOLLAR_REGEX.replace_all(&text, |caps: &Captures<'_>| {
+        format!("{}ドル", read_decimal(&caps[1]))
+    });
+    let text = DECIMAL_REGEX.replace_all(&text, |caps: &Captures<'_>| read_decimal(&caps[0]));
+    let text = INTEGER_REGEX.replace_all(&text, |caps: &Captures<'_>| read_number(&caps[0]));
+
+    text.chars()
+        .map(
+            |c| match SYMBOL_READINGS.iter().find(|&&(symbol, _)| symbol == c) {
+                Some((_, reading)) => (*reading).to_owned(),
+                None => c.to_string(),
+            },
+        )
+        .collect()
+}
+
+/// 半角カタカナ(と半角の句読点・括弧)。[`FULL_WIDTH_KATAKANA`]と一文字ずつ対応する。
+const HALF_WIDTH_KATAKANA: &str = "｡｢｣､･ｦｧｨｩｪｫｬｭｮｯｰｱｲｳｴｵｶｷｸｹｺｻｼｽｾｿﾀﾁﾂﾃﾄﾅﾆﾇﾈﾉﾊﾋﾌﾍﾎﾏﾐﾑﾒﾓﾔﾕﾖﾗﾘﾙﾚﾛﾜﾝﾞﾟ";
+const FULL_WIDTH_KATAKANA: &str =
+    "。「」、・ヲァィゥェォャュョッーアイウエオカキクケコサシスセソタチツテトナニヌネノハヒフヘホマミムメモヤユヨラリルレロワン゛゜";
+
+/// OpenJTalkに渡す前に、文字の幅の揺れを揃える。
+///
+/// - 半角カタカナは全角カタカナにする。後ろに続く半角の濁点・半濁点は合成する (`ﾎﾞｲｽ` →
+///   `ボイス`)。
+/// - 全角の英数字は半角にする (`１２３` → `123`)。
+///
+/// NFKCとは異なり、これ以外の文字は変えない。全角の記号(`！`、`？`、`（`など)は文の区切りや
+/// 読みに影響するためそのまま残し、`①`や`²`、`㌔`のように展開すると意味が変わる文字も残す。
+pub fn normalize_width(text: &str) -> String {
+    let mut normalized = String::with_capacity(text.len());
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        let c = match HALF_WIDTH_KATAKANA.chars().position(|h| h == c) {
+            Some(i) => FULL_WIDTH_KATAKANA.chars().nth(i).unwrap(),
+            None => match c {
+                '０'..='９' | 'Ａ'..='Ｚ' | 'ａ'..='ｚ' => {
+                    char::from_u32(c as u32 - ('Ａ' as u32 - 'A' as u32)).unwrap()
+                }
+                c => {
+                    normalized.push(c);
+                    continue;
+                }
+            },
+        };
+        let composed = match chars.peek() {
+            Some('ﾞ') => with_dakuten(c),
+            Some('ﾟ') => with_handakuten(c),
+            _ => None,
+        };
+        if let Some(composed) = composed {
+            chars.next();
+            normalized.push(composed);
+        } else {
+            normalized.push(c);
+        }
+    }
+    normalized
+}
+
+fn with_dakuten(c: char) -> Option<char> {
+    match c {
+        'カ' | 'キ' | 'ク' | 'ケ' | 'コ' | 'サ' | 'シ' | 'ス' | 'セ' | 'ソ' | 'タ' | 'チ'
+        | 'ツ' | 'テ' | 'ト' | 'ハ' | 'ヒ' | 'フ' | 'ヘ' | 'ホ' => {
+            char::from_u32(c as u32 + 1)
+        }
+        'ウ' => Some('ヴ'),
+        'ワ' => Some('ヷ'),
+        'ヲ' => Some('ヺ'),
+        _ => None,
+    }
+}
+
+fn with_handakuten(c: char) -> Option<char> {
+    match c {
+        'ハ' | 'ヒ' | 'フ' | 'ヘ' | 'ホ' => char::from_u32(c as u32 + 2),
+        _ => None,
+    }
+}
+
+fn to_half_width_digits(text: &str) -> String {
+    text.chars()
+        .map(|c| match c {
+            '０'..='９' => char::from_u32(c as u32 - '０' as u32 + '0' as u32).unwrap(),
+            '．' => '.',
+            '，' => ',',
+            '％' => '%',
+            '＄' => '$',
+            c => c,
+        })
+        .collect()
+}
+
+/// 数字列を読む。`0`から始まるものや桁数が多すぎるものは一桁ずつ読む。
+fn read_number(digits: &str) -> String {
+    match digits.parse::<u64>() {
+        Ok(n) if !(digits.len() > 1 && digits.starts_with('0')) => to_kanji_numeral(n),
+        _ => read_digits(digits),
+    }
+}
+
+fn read_decimal(decimal: &str) -> String {
+    match decimal.split_once('.') {
+        Some((integer, fraction)) => {
+            format!("{}点{}", read_number(integer), read_digits(fraction))
+        }
+        None => read_number(decimal),
+    }
+}
+
+fn read_digits(digits: &str) -> String {
+    digits
+        .chars()
+        .filter_map(|c| c.to_digit(10))
+        .map(|d| DIGIT_READINGS[d as usize])
+        .collect()
+}
+
+fn read_day(digits: &str) -> String {
+    let day = digits
+        .parse::<u64>()
+        .expect("should be matched with `\\d{1,2}`");
+    match DAY_READINGS.iter().find(|&&(n, _)| n == day) {
+        Some((_, reading)) => (*reading).to_owned(),
+        None => format!("{}ニチ", to_kanji_numeral(day)),
+    }
+}
+
+fn to_kanji_numeral(n: u64) -> String {
+    const DIGITS: [&str; 10] = ["", "一", "二", "三", "四", "五", "六", "七", "八", "九"];
+    const SMALL_UNITS: [&str; 4] = ["千", "百", "十", ""];
+    const LARGE_UNITS: [&str; 5] = ["", "万", "億", "兆", "京"];
+
+    if n == 0 {
+        return "ゼロ".to_owned();
+    }
+
+    let mut groups = vec![];
+    let mut rest = n;
+    while rest > 0 {
+        groups.push(rest % 10000);
+        rest /= 10000;
+    }
+
+    let mut numeral = String::new();
+    for (i, &group) in groups.iter().enumerate().rev() {
+        if group == 0 {
+            continue;
+        }
+        let digits = [group / 1000, group / 100 % 10, group / 10 % 10, group % 10];
+        for (&digit, unit) in digits.iter().zip(SMALL_UNITS) {
+            if digit == 0 {
+                continue;
+            }
+            if digit != 1 || unit.is_empty() {
+                numeral += DIGITS[digit as usize];
+            }
+            numeral += unit;
+        }
+        numeral += LARGE_UNITS[i];
+    }
+    numeral
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case("こんにちは", "こんにちは")]
+    #[case("2024年", "二千二十四年")]
+    #[case("２０２４年", "二千二十四年")]
+    #[case("1000人", "千人")]
+    #[case("10000人", "一万人")]
+    #[case("1,234,567円", "百二十三万四千五百六十七円")]
+    #[case("100000001", "一億一")]
+    #[case("0人", "ゼロ人")]
+    #[case("3.14", "三点イチヨン")]
+    #[case("0.5秒", "ゼロ点ゴ秒")]
+    #[case("4月1日", "四月ツイタチ")]
+    #[case("12月20日", "十二月ハツカ")]
+    #[case("12月25日", "十二月二十五ニチ")]
+    #[case("¥500", "五百円")]
+    #[case("$9.99", "九点キュウキュウドル")]
+    #[case("090-1234-5678", "ゼロキュウゼロノイチニサンヨンノゴロクナナハチ")]
+    #[case("0120", "ゼロイチニゼロ")]
+    #[case("50%", "五十パーセント")]
+    #[case("A&B", "AアンドB")]
+    #[case("1+1=2", "一プラス一イコール二")]
+    #[case("123456789012345678901234", "イチニサンヨンゴロクナナハチキュウゼロイチニサンヨンゴロクナナハチキュウゼロイチニサンヨン")]
+    fn normalize_works(#[case] text: &str, #[case] expected: &str) {
+        assert_eq!(expected, normalize(text));
+    }
+
+    #[rstest]
+    #[case("ﾎﾞｲｽ", "ボイス")]
+    #[case("１２３", "123")]
+    #[case("ＶＯＩＣＥＶＯＸ　ｖｏｘ", "VOICEVOX　vox")]
+    #[case("ｶﾞｷﾞﾊﾟﾋﾟｳﾞｧ", "ガギパピヴァ")]
+    #[case("ｱﾞﾟｰ｡", "ア゛゜ー。")]
+    #[case("ボイス！？（１）", "ボイス！？（1）")]
+    #[case("①㌔²", "①㌔²")]
+    fn normalize_width_works(#[case] text: &str, #[case] expected: &str) {
+        assert_eq!(expected, normalize_width(text));
+    }
+}
@@ -1,13 +1,23 @@
+use once_cell::sync::Lazy;
 use std::io::Write;
 use std::{
+    collections::HashMap,
     path::{Path, PathBuf},
-    sync::Mutex,
+    slice,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, Mutex, Weak,
+    },
 };
-use tempfile::NamedTempFile;
+use tempfile::{NamedTempFile, TempPath};
 
 use ::open_jtalk::*;
 
-use crate::{Error, UserDict};
+use super::{
+    binary::{Decoder, Encoder},
+    FullContextLabelError,
+};
+use crate::{user_dict::to_zenkaku, Error, UserDict, UserDictWord};
 
 #[derive(thiserror::Error, Debug)]
 pub enum OpenJtalkError {
@@ -24,11 +34,45 @@ pub enum OpenJtalkError {
 pub type Result<T> = std::result::Result<T, OpenJtalkError>;
 
 /// テキスト解析器としてのOpen JTalk。
+///
+/// システム辞書は`OpenJtalk`ごとに読み込まれる。複数の[`Synthesizer`]で辞書を共有するには、1つの
+/// `OpenJtalk`を[`Arc`]で共有する。別々の箇所で作る場合でも[`shared`]を使えば、同じ辞書ディレクトリに
+/// 対しては同じ`OpenJtalk`が返されるため、辞書が重複して読み込まれることは無い。
+///
+/// [`Synthesizer`]: crate::Synthesizer
+/// [`shared`]: Self::shared
 pub struct OpenJtalk {
     resources: Mutex<Resources>,
     dict_dir: Option<PathBuf>,
+    /// 設定されているユーザー辞書。
+    user_dict: Mutex<UserDict>,
+    /// コンパイルされたユーザー辞書のサイズ(バイト)。
+    user_dict_size: AtomicU64,
 }
 
+/// [`OpenJtalk::analyzer`]で作る、複数のテキストを続けて解析するための解析器。
+///
+/// 結果は[`OpenJtalk::extract_fullcontext`]と同じである。
+pub struct Analyzer {
+    resources: Resources,
+}
+
+impl Analyzer {
+    /// `text`のフルコンテキストラベルを抽出する。
+    pub fn extract_fullcontext(&mut self, text: impl AsRef<str>) -> Result<Vec<String>> {
+        extract_fullcontext(&mut self.resources, text)
+    }
+}
+
+/// [`OpenJtalk::shared`]が作った`OpenJtalk`。キーは正規化した辞書ディレクトリ。
+static SHARED: Lazy<Mutex<HashMap<PathBuf, Weak<OpenJtalk>>>> = Lazy::new(Mutex::default);
+
+/// MeCabがシステム辞書として読み込むファイル。
+const SYSTEM_DICT_FILES: &[&str] = &["sys.dic", "unk.dic", "matrix.bin", "char.bin"];
+
+/// [`UserDict::to_openjtalk_dict_bytes`]が出力するバイナリの種別。
+const USER_DICT_BINARY_KIND: u8 = b'U';
+
 struct Resources {
     mecab: ManagedResource<Mecab>,
     njd: ManagedResource<Njd>,
@@ -38,15 +82,23 @@ struct Resources {
 #[allow(unsafe_code)]
 unsafe impl Send for Resources {}
 
+impl Resources {
+    fn new() -> Self {
+        Self {
+            mecab: ManagedResource::initialize(),
+            njd: ManagedResource::initialize(),
+            jpcommon: ManagedResource::initialize(),
+        }
+    }
+}
+
 impl OpenJtalk {
     pub fn new_without_dic() -> Self {
         Self {
-            resources: Mutex::new(Resources {
-                mecab: ManagedResource::initialize(),
-                njd: ManagedResource::initialize(),
-                jpcommon: ManagedResource::initialize(),
-            }),
+            resources: Mutex::new(Resources::new()),
             dict_dir: None,
+            user_dict: Mutex::default(),
+            user_dict_size: AtomicU64::new(0),
         }
     }
     pub fn new_with_initialize(
@@ -58,102 +110,233 @@ impl OpenJtalk {
         Ok(s)
     }
 
+    /// `open_jtalk_dict_dir`の辞書を読み込んだ`OpenJtalk`を、プロセス内で共有する形で得る。
+    ///
+    /// 同じ辞書ディレクトリに対して既に`shared`で作られた`OpenJtalk`が生きていれば、それを返す。
+    /// そうでなければ[`new_with_initialize`]で新たに作る。
+    ///
+    /// 返された`OpenJtalk`は共有されるため、[`use_user_dict`]で設定したユーザー辞書は同じ辞書
+    /// ディレクトリの`shared`の利用者全てに影響する。
+    ///
+    /// [`new_with_initialize`]: Self::new_with_initialize
+    /// [`use_user_dict`]: Self::use_user_dict
+    pub fn shared(open_jtalk_dict_dir: impl AsRef<Path>) -> crate::result::Result<Arc<Self>> {
+        let dict_dir = open_jtalk_dict_dir.as_ref();
+        let key = dict_dir
+            .canonicalize()
+            .unwrap_or_else(|_| dict_dir.to_owned());
+
+        let mut shared = SHARED.lock().unwrap();
+        if let Some(open_jtalk) = shared.get(&key).and_then(Weak::upgrade) {
+            return Ok(open_jtalk);
+        }
+        let open_jtalk = Arc::new(Self::new_with_initialize(dict_dir)?);
+        shared.retain(|_, open_jtalk| open_jtalk.strong_count() > 0);
+        shared.insert(key, Arc::downgrade(&open_jtalk));
+        Ok(open_jtalk)
+    }
+
+    /// 読み込んでいる辞書のサイズ(バイト)を返す。
+    ///
+    /// システム辞書のファイルと、[`use_user_dict`]でコンパイルされたユーザー辞書のサイズの合計である。
+    /// 辞書が読み込まれていない場合は`0`を返す。
+    ///
+    /// これはファイルのサイズであり、実際のメモリ使用量ではない。MeCabは辞書をメモリマップして使うため、
+    /// 同じ辞書を読み込んだ`OpenJtalk`同士ではページが共有されうるが、この値からは共有されているか
+    /// 重複しているかを区別できない。辞書を共有するには、[`shared`]で`OpenJtalk`自体を共有すること。
+    ///
+    /// [`use_user_dict`]: Self::use_user_dict
+    /// [`shared`]: Self::shared
+    pub fn memory_usage(&self) -> u64 {
+        let Some(dict_dir) = &self.dict_dir else {
+            return 0;
+        };
+        let system_dict_size = SYSTEM_DICT_FILES
+            .iter()
+            .filter_map(|filename| dict_dir.join(filename).metadata().ok())
+            .map(|metadata| metadata.len())
+            .sum::<u64>();
+        system_dict_size + self.user_dict_size.load(Ordering::SeqCst)
+    }
+
     // 先に`load`を呼ぶ必要がある。
     /// ユーザー辞書を設定する。
     ///
     /// この関数を呼び出した後にユーザー辞書を変更した場合は、再度この関数を呼ぶ必要がある。
     pub fn use_user_dict(&self, user_dict: &UserDict) -> crate::result::Result<()> {
-        let dict_dir = self
-            .dict_dir
-            .as_ref()
-            .and_then(|dict_dir| dict_dir.to_str())
-            .ok_or(Error::NotLoadedOpenjtalkDict)?;
+        let dict_dir = self.dict_dir()?;
+        let temp_dict_path = compile_user_dict(dict_dir, user_dict)?;
+        self.load_user_dict(
+            dict_dir,
+            &temp_dict_path,
+            user_dict.clone(),
+            "辞書のコンパイルに失敗しました",
+        )
+    }
+
+    /// [`UserDict::to_openjtalk_dict_bytes`]でコンパイル済みのユーザー辞書を設定する。
+    ///
+    /// [`use_user_dict`]と同じだが、辞書のコンパイルを行わない。`bytes`は同じバージョンのこの
+    /// ライブラリで、同じシステム辞書を使って作られたものである必要がある。
+    ///
+    /// # Errors
+    ///
+    /// `bytes`の形式が不正な場合は[`Error::InvalidBinaryData`]を、MeCabが辞書として読み込めなかった
+    /// 場合は[`Error::UseUserDict`]を返す。
+    ///
+    /// [`use_user_dict`]: Self::use_user_dict
+    pub fn use_user_dict_bytes(&self, bytes: &[u8]) -> crate::result::Result<()> {
+        let dict_dir = self.dict_dir()?;
+
+        let mut decoder = Decoder::new(bytes, USER_DICT_BINARY_KIND)?;
+        let words =
+            serde_json::from_str(&decoder.string()?).map_err(|e| Error::InvalidBinaryData {
+                reason: e.to_string(),
+            })?;
+        let contextual_words =
+            serde_json::from_str(&decoder.string()?).map_err(|e| Error::InvalidBinaryData {
+                reason: e.to_string(),
+            })?;
+        let compiled = decoder.bytes()?;
+        decoder.finish()?;
 
-        // ユーザー辞書用のcsvを作成
-        let mut temp_csv = NamedTempFile::new().map_err(|e| Error::UseUserDict(e.to_string()))?;
-        temp_csv
-            .write_all(user_dict.to_mecab_format().as_bytes())
+        let mut temp_dict = NamedTempFile::new().map_err(|e| Error::UseUserDict(e.to_string()))?;
+        temp_dict
+            .write_all(compiled)
             .map_err(|e| Error::UseUserDict(e.to_string()))?;
-        let temp_csv_path = temp_csv.into_temp_path();
-        let temp_dict = NamedTempFile::new().map_err(|e| Error::UseUserDict(e.to_string()))?;
         let temp_dict_path = temp_dict.into_temp_path();
 
-        // Mecabでユーザー辞書をコンパイル
-        // TODO: エラー（SEGV）が出るパターンを把握し、それをRust側で防ぐ。
-        mecab_dict_index(&[
-            "mecab-dict-index",
-            "-d",
+        self.load_user_dict(
             dict_dir,
-            "-u",
-            temp_dict_path.to_str().unwrap(),
-            "-f",
-            "utf-8",
-            "-t",
-            "utf-8",
-            temp_csv_path.to_str().unwrap(),
-            "-q",
-        ]);
+            &temp_dict_path,
+            UserDict::from_parts(words, contextual_words),
+            "辞書の読み込みに失敗しました",
+        )
+    }
+
+    /// `user_dict`をコンパイルし、単語の一覧と合わせて[`use_user_dict_bytes`]で読める形にする。
+    ///
+    /// [`use_user_dict_bytes`]: Self::use_user_dict_bytes
+    pub(crate) fn compile_user_dict_to_bytes(
+        &self,
+        user_dict: &UserDict,
+    ) -> crate::result::Result<Vec<u8>> {
+        let dict_dir = self.dict_dir()?;
+        let temp_dict_path = compile_user_dict(dict_dir, user_dict)?;
+        let compiled =
+            fs_err::read(&temp_dict_path).map_err(|e| Error::UseUserDict(e.to_string()))?;
 
+        let mut encoder = Encoder::new(USER_DICT_BINARY_KIND);
+        encoder.str(&serde_json::to_string(user_dict.words()).expect("should be always valid"));
+        encoder.str(
+            &serde_json::to_string(user_dict.contextual_words()).expect("should be always valid"),
+        );
+        encoder.bytes(&compiled);
+        Ok(encoder.finish())
+    }
+
+    /// コンパイル済みのユーザー辞書`dict_path`をシステム辞書と共に読み込み、`user_dict`を
+    /// 設定されているユーザー辞書とする。
+    fn load_user_dict(
+        &self,
+        dict_dir: &str,
+        dict_path: &Path,
+        user_dict: UserDict,
+        error_message: &str,
+    ) -> crate::result::Result<()> {
         let Resources { mecab, .. } = &mut *self.resources.lock().unwrap();
 
-        let result = mecab.load_with_userdic(Path::new(dict_dir), Some(Path::new(&temp_dict_path)));
+        let result = mecab.load_with_userdic(Path::new(dict_dir), Some(dict_path));
 
+        if !result {
+            return Err(Error::UseUserDict(error_message.to_string()));
+        }
+
+        let user_dict_size = dict_path.metadata().map_or(0, |metadata| metadata.len());
+        *self.user_dict.lock().unwrap() = user_dict;
+        self.user_dict_size.store(user_dict_size, Ordering::SeqCst);
+
+        Ok(())
+    }
+
+    /// 設定されているユーザー辞書に`temp_word`を一時的に加えた上で、`text`のフルコンテキストラベル
+    /// を抽出する。
+    ///
+    /// ユーザー辞書に登録する前の単語の読みやアクセントを確認するためのものである。解析はこの
+    /// `OpenJtalk`とは別のリソースで行われるため、設定されているユーザー辞書は変更されず、並行して
+    /// 行われる他の解析も妨げない。ただし呼ぶたびに、設定されているユーザー辞書と`temp_word`から
+    /// なる辞書のコンパイルとシステム辞書の読み込みが行われる。
+    pub fn analyze_with_temp_dict(
+        &self,
+        text: &str,
+        temp_word: &UserDictWord,
+    ) -> crate::result::Result<Vec<String>> {
+        self.analyze_with_temp_words(text, slice::from_ref(temp_word))
+    }
+
+    /// 設定されているユーザー辞書に`temp_words`を一時的に加えた上で、`text`のフルコンテキスト
+    /// ラベルを抽出する。
+    ///
+    /// [`analyze_with_temp_dict`]の複数の単語を加える版。
+    ///
+    /// [`analyze_with_temp_dict`]: Self::analyze_with_temp_dict
+    pub(crate) fn analyze_with_temp_words(
+        &self,
+        text: &str,
+        temp_words: &[UserDictWord],
+    ) -> crate::result::Result<Vec<String>> {
+        let mut user_dict = self.user_dict.lock().unwrap().clone();
+        for temp_word in temp_words {
+            user_dict.add_word(temp_word.clone())?;
+        }
+        let mut resources = self.new_resources(&user_dict)?;
+
+        extract_fullcontext(&mut resources, text).map_err(|e| FullContextLabelError::from(e).into())
+    }
+
+    /// 複数のテキストを続けて解析するための[`Analyzer`]を作る。
+    ///
+    /// `Analyzer`はこの`OpenJtalk`とは別のリソースを持つため、`Analyzer`での解析は他の解析と
+    /// ロックを取り合わない。作るときにシステム辞書の読み込みと、ユーザー辞書が設定されていれば
+    /// そのコンパイルが行われるが、以降の解析ではそれらは行われない。
+    ///
+    /// ユーザー辞書は作った時点で設定されているものが使われる。その後に[`use_user_dict`]を呼んでも
+    /// `Analyzer`には反映されない。
+    ///
+    /// [`use_user_dict`]: Self::use_user_dict
+    pub fn analyzer(&self) -> crate::result::Result<Analyzer> {
+        let user_dict = self.user_dict.lock().unwrap().clone();
+        Ok(Analyzer {
+            resources: self.new_resources(&user_dict)?,
+        })
+    }
+
+    /// この`OpenJtalk`とは別に、システム辞書と`user_dict`を読み込んだリソースを作る。
+    fn new_resources(&self, user_dict: &UserDict) -> crate::result::Result<Resources> {
+        let dict_dir = self.dict_dir()?;
+
+        let mut resources = Resources::new();
+        if user_dict.is_empty() {
+            if !resources.mecab.load(Path::new(dict_dir)) {
+                return Err(Error::NotLoadedOpenjtalkDict);
+            }
+            return Ok(resources);
+        }
+
+        let temp_dict_path = compile_user_dict(dict_dir, user_dict)?;
+        let result = resources
+            .mecab
+            .load_with_userdic(Path::new(dict_dir), Some(Path::new(&temp_dict_path)));
         if !result {
             return Err(Error::UseUserDict(
                 "辞書のコンパイルに失敗しました".to_string(),
             ));
         }
-
-        Ok(())
+        Ok(resources)
     }
 
     pub fn extract_fullcontext(&self, text: impl AsRef<str>) -> Result<Vec<String>> {
-        let Resources {
-            mecab,
-            njd,
-            jpcommon,
-        } = &mut *self.resources.lock().unwrap();
-
-        jpcommon.refresh();
-        njd.refresh();
-        mecab.refresh();
-
-        let mecab_text =
-            text2mecab(text.as_ref()).map_err(|e| OpenJtalkError::ExtractFullContext {
-                text: text.as_ref().into(),
-                source: Some(e.into()),
-            })?;
-        if mecab.analysis(mecab_text) {
-            njd.mecab2njd(
-                mecab
-                    .get_feature()
-                    .ok_or(OpenJtalkError::ExtractFullContext {
-                        text: text.as_ref().into(),
-                        source: None,
-                    })?,
-                mecab.get_size(),
-            );
-            njd.set_pronunciation();
-            njd.set_digit();
-            njd.set_accent_phrase();
-            njd.set_accent_type();
-            njd.set_unvoiced_vowel();
-            njd.set_long_vowel();
-            jpcommon.njd2jpcommon(njd);
-            jpcommon.make_label();
-            jpcommon
-                .get_label_feature_to_iter()
-                .ok_or_else(|| OpenJtalkError::ExtractFullContext {
-                    text: text.as_ref().into(),
-                    source: None,
-                })
-                .map(|iter| iter.map(|s| s.to_string()).collect())
-        } else {
-            Err(OpenJtalkError::ExtractFullContext {
-                text: text.as_ref().into(),
-                source: None,
-            })
-        }
+        extract_fullcontext(&mut self.resources.lock().unwrap(), text)
     }
 
     fn load(&mut self, open_jtalk_dict_dir: impl AsRef<Path>) -> Result<()> {
@@ -177,6 +360,115 @@ impl OpenJtalk {
     pub fn dict_loaded(&self) -> bool {
         self.dict_dir.is_some()
     }
+
+    /// 設定されているユーザー辞書の[`ContextualWord`]を`text`に適用する。
+    ///
+    /// [`ContextualWord`]: crate::ContextualWord
+    pub(crate) fn apply_contextual_words(
+        &self,
+        text: &str,
+        excluded_surfaces: &[&str],
+    ) -> crate::result::Result<(String, Vec<UserDictWord>)> {
+        self.user_dict
+            .lock()
+            .unwrap()
+            .apply_contextual_words(text, excluded_surfaces)
+    }
+
+    /// `surface`が、設定されているユーザー辞書に登録されている単語かどうか。
+    pub(crate) fn is_user_dict_word(&self, surface: &str) -> bool {
+        let surface = to_zenkaku(surface);
+        self.user_dict
+            .lock()
+            .unwrap()
+            .surfaces()
+            .any(|s| *s == surface)
+    }
+
+    fn dict_dir(&self) -> crate::result::Result<&str> {
+        self.dict_dir
+            .as_ref()
+            .and_then(|dict_dir| dict_dir.to_str())
+            .ok_or(Error::NotLoadedOpenjtalkDict)
+    }
+}
+
+/// ユーザー辞書をMeCabの辞書としてコンパイルし、一時ファイルに書き出す。
+fn compile_user_dict(dict_dir: &str, user_dict: &UserDict) -> crate::result::Result<TempPath> {
+    // ユーザー辞書用のcsvを作成
+    let mut temp_csv = NamedTempFile::new().map_err(|e| Error::UseUserDict(e.to_string()))?;
+    temp_csv
+        .write_all(user_dict.to_mecab_format().as_bytes())
+        .map_err(|e| Error::UseUserDict(e.to_string()))?;
+    let temp_csv_path = temp_csv.into_temp_path();
+    let temp_dict = NamedTempFile::new().map_err(|e| Error::UseUserDict(e.to_string()))?;
+    let temp_dict_path = temp_dict.into_temp_path();
+
+    // Mecabでユーザー辞書をコンパイル
+    // TODO: エラー（SEGV）が出るパターンを把握し、それをRust側で防ぐ。
+    mecab_dict_index(&[
+        "mecab-dict-index",
+        "-d",
+        dict_dir,
+        "-u",
+        temp_dict_path.to_str().unwrap(),
+        "-f",
+        "utf-8",
+        "-t",
+        "utf-8",
+        temp_csv_path.to_str().unwrap(),
+        "-q",
+    ]);
+
+    Ok(temp_dict_path)
+}
+
+fn extract_fullcontext(resources: &mut Resources, text: impl AsRef<str>) -> Result<Vec<String>> {
+    let Resources {
+        mecab,
+        njd,
+        jpcommon,
+    } = resources;
+
+    jpcommon.refresh();
+    njd.refresh();
+    mecab.refresh();
+
+    let mecab_text = text2mecab(text.as_ref()).map_err(|e| OpenJtalkError::ExtractFullContext {
+        text: text.as_ref().into(),
+        source: Some(e.into()),
+    })?;
+    if mecab.analysis(mecab_text) {
+        njd.mecab2njd(
+            mecab
+                .get_feature()
+                .ok_or(OpenJtalkError::ExtractFullContext {
+                    text: text.as_ref().into(),
+                    source: None,
+                })?,
+            mecab.get_size(),
+        );
+        njd.set_pronunciation();
+        njd.set_digit();
+        njd.set_accent_phrase();
+        njd.set_accent_type();
+        njd.set_unvoiced_vowel();
+        njd.set_long_vowel();
+        jpcommon.njd2jpcommon(njd);
+        jpcommon.make_label();
+        jpcommon
+            .get_label_feature_to_iter()
+            .ok_or_else(|| OpenJtalkError::ExtractFullContext {
+                text: text.as_ref().into(),
+                source: None,
+            })
+            .map(|iter| iter.map(|s| s.to_string()).collect())
+    } else {
+        Err(OpenJtalkError::ExtractFullContext {
+            text: text.as_ref().into(),
+            source: None,
+        })
+    }
 }
 
 #[cfg(test)]
@@ -294,4 +586,140 @@ mod tests {
             assert_debug_fmt_eq!(expected, result);
         }
     }
+
+    #[rstest]
+    fn analyze_with_temp_dict_does_not_mutate_user_dict() {
+        const TEXT: &str = "this_word_should_not_exist_in_default_dictionary";
+
+        let open_jtalk = OpenJtalk::new_with_initialize(OPEN_JTALK_DIC_DIR).unwrap();
+        let temp_word = UserDictWord::new(
+            TEXT,
+            "アイウエオ".to_owned(),
+            0,
+            UserDictWordType::ProperNoun,
+            5,
+        )
+        .unwrap();
+
+        let before = open_jtalk.extract_fullcontext(TEXT).unwrap();
+        let preview = open_jtalk.analyze_with_temp_dict(TEXT, &temp_word).unwrap();
+        let after = open_jtalk.extract_fullcontext(TEXT).unwrap();
+
+        let phonemes = |labels: &[String]| {
+            labels
+                .iter()
+                .map(|label| label.split(['-', '+']).nth(1).unwrap().to_owned())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            ["sil", "a", "i", "u", "e", "o", "sil"],
+            &*phonemes(&preview),
+        );
+        assert_eq!(before, after);
+        assert!(!open_jtalk.is_user_dict_word(TEXT));
+    }
+
+    #[rstest]
+    fn analyzer_works() {
+        const WORD: &str = "this_word_should_not_exist_in_default_dictionary";
+        const TEXTS: &[&str] = &["こんにちは、ヒホです。", "同じ、文章、です。", WORD];
+
+        let open_jtalk = OpenJtalk::new_with_initialize(OPEN_JTALK_DIC_DIR).unwrap();
+        let mut analyzer = open_jtalk.analyzer().unwrap();
+        for text in TEXTS {
+            assert_eq!(
+                open_jtalk.extract_fullcontext(text).unwrap(),
+                analyzer.extract_fullcontext(text).unwrap(),
+            );
+        }
+
+        let mut user_dict = UserDict::new();
+        user_dict
+            .add_word(
+                UserDictWord::new(
+                    WORD,
+                    "アイウエオ".to_owned(),
+                    0,
+                    UserDictWordType::ProperNoun,
+                    5,
+                )
+                .unwrap(),
+            )
+            .unwrap();
+        open_jtalk.use_user_dict(&user_dict).unwrap();
+        let mut new_analyzer = open_jtalk.analyzer().unwrap();
+        assert_eq!(
+            open_jtalk.extract_fullcontext(WORD).unwrap(),
+            new_analyzer.extract_fullcontext(WORD).unwrap(),
+        );
+        assert_ne!(
+            open_jtalk.extract_fullcontext(WORD).unwrap(),
+            analyzer.extract_fullcontext(WORD).unwrap(),
+            "作った後のユーザー辞書は反映されない",
+        );
+    }
+
+    #[rstest]
+    fn shared_shares_dictionary() {
+        let open_jtalk = OpenJtalk::shared(OPEN_JTALK_DIC_DIR).unwrap();
+        let other = OpenJtalk::shared(OPEN_JTALK_DIC_DIR).unwrap();
+        assert!(Arc::ptr_eq(&open_jtalk, &other));
+
+        let system_dict_size = open_jtalk.memory_usage();
+        assert!(system_dict_size > 0);
+        assert_eq!(0, OpenJtalk::new_without_dic().memory_usage());
+
+        let mut user_dict = UserDict::new();
+        user_dict
+            .add_word(
+                UserDictWord::new(
+                    "this_word_should_not_exist_in_default_dictionary",
+                    "アイウエオ".to_owned(),
+                    0,
+                    UserDictWordType::ProperNoun,
+                    5,
+                )
+                .unwrap(),
+            )
+            .unwrap();
+        other.use_user_dict(&user_dict).unwrap();
+        assert!(open_jtalk.memory_usage() > system_dict_size);
+    }
+
+    #[rstest]
+    fn use_user_dict_bytes_works() {
+        const WORD: &str = "this_word_should_not_exist_in_default_dictionary";
+
+        let mut user_dict = UserDict::new();
+        user_dict
+            .add_word(
+                UserDictWord::new(
+                    WORD,
+                    "アイウエオ".to_owned(),
+                    0,
+                    UserDictWordType::ProperNoun,
+                    5,
+                )
+                .unwrap(),
+            )
+            .unwrap();
+        let compiled = OpenJtalk::new_with_initialize(OPEN_JTALK_DIC_DIR).unwrap();
+        compiled.use_user_dict(&user_dict).unwrap();
+        let bytes = user_dict.to_openjtalk_dict_bytes(&compiled).unwrap();
+
+        let open_jtalk = OpenJtalk::new_with_initialize(OPEN_JTALK_DIC_DIR).unwrap();
+        open_jtalk.use_user_dict_bytes(&bytes).unwrap();
+        assert_eq!(
+            compiled.extract_fullcontext(WORD).unwrap(),
+            open_jtalk.extract_fullcontext(WORD).unwrap(),
+        );
+        assert!(open_jtalk.is_user_dict_word(WORD));
+        assert_eq!(compiled.memory_usage(), open_jtalk.memory_usage());
+
+        let result = open_jtalk.use_user_dict_bytes(&bytes[..bytes.len() - 1]);
+        assert!(
+            matches!(result, Err(Error::InvalidBinaryData { .. })),
+            "{result:?}",
+        );
+    }
 }
@@ -0,0 +1,99 @@
+/// 文末として扱う文字。
+const SENTENCE_TERMINATORS: &[char] = &['。', '！', '？'];
+
+/// 開き括弧と閉じ括弧の組。括弧の中では文を区切らない。
+const QUOTE_PAIRS: &[(char, char)] = &[('「', '」'), ('『', '』'), ('（', '）'), ('“', '”')];
+
+/// テキストを文ごとに分割する。
+///
+/// 「。」「！」「？」を文末とみなすが、以下の場合は区切らない。
+///
+/// - 括弧(「」、『』、（）、“”)の内側。
+/// - 文末記号の直後に続く文末記号、閉じ括弧、三点リーダー(「…」「‥」)。これらは直前の文に含める。
+///
+/// 前後の空白は取り除かれ、空白のみの文は返さない。
+pub fn split_sentences(text: &str) -> Vec<&str> {
+    let mut sentences = Vec::new();
+    let mut depth = 0_usize;
+    let mut start = 0;
+    let mut chars = text.char_indices().peekable();
+
+    while let Some((i, c)) = chars.next() {
+        if QUOTE_PAIRS.iter().any(|&(open, _)| open == c) {
+            depth += 1;
+        } else if QUOTE_PAIRS.iter().any(|&(_, close)| close == c) {
+            depth = depth.saturating_sub(1);
+        }
+
+        if depth > 0 || !SENTENCE_TERMINATORS.contains(&c) {
+            continue;
+        }
+
+        let mut end = i + c.len_utf8();
+        while let Some(&(j, next)) = chars.peek() {
+            let is_trailing = SENTENCE_TERMINATORS.contains(&next)
+                || ['…', '‥'].contains(&next)
+                || QUOTE_PAIRS.iter().any(|&(_, close)| close == next);
+            if !is_trailing {
+                break;
+            }
+            end = j + next.len_utf8();
+            chars.next();
+        }
+        sentences.push(&text[start..end]);
+        start = end;
+    }
+    sentences.push(&text[start..]);
+
+    sentences
+        .into_iter()
+        .map(str::trim)
+        .filter(|sentence| !sentence.is_empty())
+        .collect()
+}
+
+/// 文の文末記号を返す。文末記号の後に続く閉じ括弧や三点リーダーは読み飛ばす。
+///
+/// 文末記号で終わらない文に対しては`None`を返す。
+pub fn sentence_terminator(sentence: &str) -> Option<char> {
+    sentence
+        .trim_end()
+        .chars()
+        .rev()
+        .find(|&c| !['…', '‥'].contains(&c) && !QUOTE_PAIRS.iter().any(|&(_, close)| close == c))
+        .filter(|c| SENTENCE_TERMINATORS.contains(c))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case("", &[])]
+    #[case("　", &[])]
+    #[case("こんにちは", &["こんにちは"])]
+    #[case("おはよう。こんにちは！元気？", &["おはよう。", "こんにちは！", "元気？"])]
+    #[case("本当！？うそ。", &["本当！？", "うそ。"])]
+    #[case("えっ…そうなの？", &["えっ…そうなの？"])]
+    #[case("えっ。…そうなの？", &["えっ。…", "そうなの？"])]
+    #[case("彼は「はい。そうです。」と言った。次へ。", &["彼は「はい。そうです。」と言った。", "次へ。"])]
+    #[case("（笑。）それで。", &["（笑。）それで。"])]
+    #[case("終わり。 始まり", &["終わり。", "始まり"])]
+    fn split_sentences_works(#[case] text: &str, #[case] expected: &[&str]) {
+        assert_eq!(expected, split_sentences(text));
+    }
+
+    #[rstest]
+    #[case("おはよう。", Some('。'))]
+    #[case("元気？！", Some('！'))]
+    #[case("「はい。」", Some('。'))]
+    #[case("そうか……", None)]
+    #[case("それで？…", Some('？'))]
+    #[case("こんにちは", None)]
+    #[case("", None)]
+    fn sentence_terminator_works(#[case] sentence: &str, #[case] expected: Option<char>) {
+        assert_eq!(expected, sentence_terminator(sentence));
+    }
+}
@@ -1,5 +1,6 @@
 use derive_new::new;
 use std::io::{Cursor, Write};
+use std::ops::Range;
 use std::sync::Arc;
 
 use super::full_context_label::Utterance;
@@ -25,21 +26,54 @@ unsafe impl Send for SynthesisEngine {}
 
 impl SynthesisEngine {
     pub const DEFAULT_SAMPLING_RATE: u32 = 24000;
+    /// 出力するWAVデータの量子化ビット数。
+    pub const BIT_DEPTH: u16 = 16;
 
     pub fn inference_core(&self) -> &InferenceCore {
         &self.inference_core
     }
 
+    /// `text`からアクセント句を作る。
+    ///
+    /// 空白と句読点のみからなるテキスト(空文字列を含む)の場合は、OpenJTalkを通さずに空のアクセント句
+    /// の配列を返す。ただしOpenJTalkの辞書が読み込まれていない場合は、テキストの内容によらず
+    /// [`Error::NotLoadedOpenjtalkDict`]を返す。
+    ///
+    /// `text`には`会議{かいぎ:1}`のようなアクセント指定を含めることができる。指定された単語の読みと
+    /// アクセントは、この呼び出しの間だけユーザー辞書に加えられる。記法の詳細は
+    /// `parse_accent_hints`を参照。その場合はユーザー辞書のコンパイルとシステム辞書の読み込みを
+    /// 呼び出しごとに行う。
+    ///
+    /// ユーザー辞書に[`ContextualWord`]がある場合は、その文脈に当てはまる箇所も同様に扱う。ただし
+    /// アクセント指定のある単語と同じ表記の`ContextualWord`は使わない。
+    ///
+    /// [`ContextualWord`]: crate::ContextualWord
     pub async fn create_accent_phrases(
         &self,
         text: &str,
         style_id: StyleId,
     ) -> Result<Vec<AccentPhraseModel>> {
-        if text.is_empty() {
+        if !self.is_openjtalk_dict_loaded() {
+            return Err(Error::NotLoadedOpenjtalkDict);
+        }
+        let (text, mut hint_words) = parse_accent_hints(text)?;
+        let (text, contextual_words) = self.open_jtalk.apply_contextual_words(
+            &text,
+            &hint_words.iter().map(|w| &*w.surface).collect::<Vec<_>>(),
+        )?;
+        hint_words.extend(contextual_words);
+        if is_silent_text(&text) {
             return Ok(Vec::new());
         }
 
-        let utterance = Utterance::extract_full_context_label(&self.open_jtalk, text)?;
+        let utterance = if hint_words.is_empty() {
+            Utterance::extract_full_context_label(&self.open_jtalk, &text)?
+        } else {
+            let labels = self
+                .open_jtalk
+                .analyze_with_temp_words(&text, &hint_words)?;
+            Utterance::from_full_context_labels(labels)?
+        };
 
         let accent_phrases: Vec<AccentPhraseModel> = utterance
             .breath_groups()
@@ -310,12 +344,181 @@ impl SynthesisEngine {
         Ok(new_accent_phrases)
     }
 
+    /// `query`を音声合成する。
+    ///
+    /// アクセント句が空の場合は`decode`を行わず、前後の無音の長さの無音を返す。
     pub async fn synthesis(
         &self,
         query: &AudioQueryModel,
         style_id: StyleId,
         enable_interrogative_upspeak: bool,
     ) -> Result<Vec<f32>> {
+        let (f0, flatten_phoneme) = Self::decode_inputs(query, enable_interrogative_upspeak);
+        if query.accent_phrases().is_empty() {
+            return Ok(vec![0.; f0.len() * SAMPLES_PER_FRAME]);
+        }
+        self.inference_core()
+            .decode(
+                f0.len(),
+                OjtPhoneme::num_phoneme(),
+                &f0,
+                &flatten_phoneme,
+                style_id,
+            )
+            .await
+    }
+
+    /// [`decode_inputs`]のフレームのうち、先頭のアクセント句(音声の前の無音を含む)が終わる位置を
+    /// 返す。
+    ///
+    /// アクセント句が1つ以下の場合や、後続のフレームが[`HEAD_LOOKAHEAD_FRAMES`]に満たない場合は、
+    /// 先頭のアクセント句を分けて`decode`する意味が無いものとして`None`を返す。
+    ///
+    /// [`decode_inputs`]: Self::decode_inputs
+    pub fn head_end(query: &AudioQueryModel, enable_interrogative_upspeak: bool) -> Option<usize> {
+        match *Self::accent_phrase_frame_ends(query, enable_interrogative_upspeak) {
+            [head_end, .., total] if head_end + HEAD_LOOKAHEAD_FRAMES <= total => Some(head_end),
+            _ => None,
+        }
+    }
+
+    /// 先頭のアクセント句の部分だけを`decode`する。
+    ///
+    /// 後続の[`HEAD_LOOKAHEAD_FRAMES`]フレームも文脈として`decode`に渡し、その部分の波形は捨てる。
+    /// 長さは[`synthesis`]の結果の先頭`head_end`フレーム分と一致するが、それより後ろの文脈を欠くため、
+    /// 波形はわずかに異なりうる。
+    ///
+    /// [`synthesis`]: Self::synthesis
+    pub async fn decode_head(
+        &self,
+        f0: &[f32],
+        flatten_phoneme: &[f32],
+        head_end: usize,
+        style_id: StyleId,
+    ) -> Result<Vec<f32>> {
+        let mut wave = self
+            .decode_frames(
+                f0,
+                flatten_phoneme,
+                0..head_end + HEAD_LOOKAHEAD_FRAMES,
+                style_id,
+            )
+            .await?;
+        wave.truncate(head_end * SAMPLES_PER_FRAME);
+        Ok(wave)
+    }
+
+    /// フレームを[`PREVIEW_DECIMATION`]個に1つに間引いて`decode`を行う、低品質だが高速な音声合成。
+    ///
+    /// `decode`の処理時間はフレーム数にほぼ比例するため、[`synthesis`]の約1/[`PREVIEW_DECIMATION`]の
+    /// 時間で済む。間引いたフレームから生成された波形は、音高を保ったまま元の長さに引き伸ばされる
+    /// ため、長さは[`synthesis`]の結果と一致する。一方で引き伸ばしによる音質の劣化が大きく、抑揚や
+    /// タイミングの確認以外には向かない。
+    ///
+    /// [`synthesis`]: Self::synthesis
+    pub async fn synthesis_preview(
+        &self,
+        query: &AudioQueryModel,
+        style_id: StyleId,
+        enable_interrogative_upspeak: bool,
+    ) -> Result<Vec<f32>> {
+        let (f0, flatten_phoneme) = Self::decode_inputs(query, enable_interrogative_upspeak);
+        if query.accent_phrases().is_empty() {
+            return Ok(vec![0.; f0.len() * SAMPLES_PER_FRAME]);
+        }
+        let phoneme_size = OjtPhoneme::num_phoneme();
+
+        let decimated_f0 = f0
+            .iter()
+            .step_by(PREVIEW_DECIMATION)
+            .copied()
+            .collect::<Vec<_>>();
+        let decimated_phoneme = flatten_phoneme
+            .chunks(phoneme_size)
+            .step_by(PREVIEW_DECIMATION)
+            .flatten()
+            .copied()
+            .collect::<Vec<_>>();
+
+        let wave = self
+            .inference_core()
+            .decode(
+                decimated_f0.len(),
+                phoneme_size,
+                &decimated_f0,
+                &decimated_phoneme,
+                style_id,
+            )
+            .await?;
+        Ok(time_stretch(
+            &wave,
+            PREVIEW_DECIMATION,
+            f0.len() * SAMPLES_PER_FRAME,
+        ))
+    }
+
+    /// [`decode_inputs`]のフレームのうち、各アクセント句が終わる位置を返す。
+    ///
+    /// 最初のアクセント句には音声の前の無音を、最後のアクセント句には音声の後の無音を含める。
+    /// アクセント句が無い場合は、全体を1つの区間とする。
+    ///
+    /// [`decode_inputs`]: Self::decode_inputs
+    pub fn accent_phrase_frame_ends(
+        query: &AudioQueryModel,
+        enable_interrogative_upspeak: bool,
+    ) -> Vec<usize> {
+        let speed_scale = *query.speed_scale();
+        let accent_phrases = if enable_interrogative_upspeak {
+            adjust_interrogative_accent_phrases(query.accent_phrases().as_slice())
+        } else {
+            query.accent_phrases().clone()
+        };
+
+        let mut end = to_frame_length(*query.pre_phoneme_length(), speed_scale);
+        let mut ends = accent_phrases
+            .iter()
+            .map(|accent_phrase| {
+                end += phoneme_lengths(std::slice::from_ref(accent_phrase))
+                    .map(|phoneme_length| to_frame_length(phoneme_length, speed_scale))
+                    .sum::<usize>();
+                end
+            })
+            .collect::<Vec<_>>();
+        let total = end + to_frame_length(*query.post_phoneme_length(), speed_scale);
+        match ends.last_mut() {
+            Some(last) => *last = total,
+            None => ends.push(total),
+        }
+        ends
+    }
+
+    /// [`decode_inputs`]で作った入力のうち、`frames`の範囲のみを`decode`する。
+    ///
+    /// [`decode_inputs`]: Self::decode_inputs
+    pub async fn decode_frames(
+        &self,
+        f0: &[f32],
+        flatten_phoneme: &[f32],
+        frames: Range<usize>,
+        style_id: StyleId,
+    ) -> Result<Vec<f32>> {
+        let phoneme_size = OjtPhoneme::num_phoneme();
+        self.inference_core()
+            .decode(
+                frames.len(),
+                phoneme_size,
+                &f0[frames.clone()],
+                &flatten_phoneme[frames.start * phoneme_size..frames.end * phoneme_size],
+                style_id,
+            )
+            .await
+    }
+
+    /// `decode`に渡すフレームごとのf0と、平坦化した音素のone-hotベクトルを作る。
+    pub fn decode_inputs(
+        query: &AudioQueryModel,
+        enable_interrogative_upspeak: bool,
+    ) -> (Vec<f32>, Vec<f32>) {
         let speed_scale = *query.speed_scale();
         let pitch_scale = *query.pitch_scale();
         let intonation_scale = *query.intonation_scale();
@@ -377,17 +580,12 @@ impl SynthesisEngine {
         let mut phoneme: Vec<Vec<f32>> = Vec::new();
         let mut f0: Vec<f32> = Vec::new();
         {
-            const RATE: f32 = 24000. / 256.;
             let mut sum_of_phoneme_length = 0;
             let mut count_of_f0 = 0;
             let mut vowel_indexes_index = 0;
 
             for (i, phoneme_length) in phoneme_length_list.iter().enumerate() {
-                // VOICEVOX ENGINEと挙動を合わせるため、四捨五入ではなく偶数丸めをする
-                //
-                // https://github.com/VOICEVOX/voicevox_engine/issues/552
-                let phoneme_length = ((*phoneme_length * RATE).round_ties_even_() / speed_scale)
-                    .round_ties_even_() as usize;
+                let phoneme_length = to_frame_length(*phoneme_length, speed_scale);
                 let phoneme_id = phoneme_data_list[i].phoneme_id();
 
                 for _ in 0..phoneme_length {
@@ -411,40 +609,122 @@ impl SynthesisEngine {
         // 2次元のvectorを1次元に変換し、アドレスを連続させる
         let flatten_phoneme = phoneme.into_iter().flatten().collect::<Vec<_>>();
 
-        self.inference_core()
-            .decode(
-                f0.len(),
-                OjtPhoneme::num_phoneme(),
-                &f0,
-                &flatten_phoneme,
-                style_id,
-            )
-            .await
+        (f0, flatten_phoneme)
     }
 
-    pub async fn synthesis_wave_format(
-        &self,
+    /// `query`を音声合成したときの長さを、`decode`を行わずに秒単位で求める。
+    pub fn estimate_duration(query: &AudioQueryModel, enable_interrogative_upspeak: bool) -> f32 {
+        let speed_scale = *query.speed_scale();
+
+        let accent_phrases = if enable_interrogative_upspeak {
+            adjust_interrogative_accent_phrases(query.accent_phrases().as_slice())
+        } else {
+            query.accent_phrases().clone()
+        };
+
+        let num_frames = [*query.pre_phoneme_length()]
+            .into_iter()
+            .chain(phoneme_lengths(&accent_phrases))
+            .chain([*query.post_phoneme_length()])
+            .map(|phoneme_length| to_frame_length(phoneme_length, speed_scale))
+            .sum::<usize>();
+
+        num_frames as f32 / FRAME_RATE
+    }
+
+    /// `query`を音声合成したときに、疑問文の調整によって音声が変わるかどうかを求める。
+    ///
+    /// `enable_interrogative_upspeak`が`true`であっても、疑問文のアクセント句が無い場合や、その最後の
+    /// モーラが無声化している場合は`false`となる。
+    pub fn applies_interrogative_upspeak(
         query: &AudioQueryModel,
-        style_id: StyleId,
         enable_interrogative_upspeak: bool,
-    ) -> Result<Vec<u8>> {
-        let wave = self
-            .synthesis(query, style_id, enable_interrogative_upspeak)
-            .await?;
-        let volume_scale = *query.volume_scale();
-        let output_stereo = *query.output_stereo();
+    ) -> bool {
+        enable_interrogative_upspeak && query.accent_phrases().iter().any(is_upspeak_target)
+    }
+
+    /// 各アクセント句が始まる位置を、出力のサンプリングレートでのサンプル数(全チャンネルで1つと
+    /// 数える)で返す。
+    ///
+    /// 位置は[`accent_phrase_frame_ends`]と同じ計算で求める。最初のアクセント句は、音声の前の無音の
+    /// 直後から始まる。
+    ///
+    /// [`accent_phrase_frame_ends`]: Self::accent_phrase_frame_ends
+    pub fn accent_phrase_cue_points(
+        query: &AudioQueryModel,
+        enable_interrogative_upspeak: bool,
+    ) -> Vec<u32> {
+        if query.accent_phrases().is_empty() {
+            return vec![];
+        }
+        let samples_per_frame = SAMPLES_PER_FRAME as u32
+            * (*query.output_sampling_rate() / Self::DEFAULT_SAMPLING_RATE);
+        let first_start = to_frame_length(*query.pre_phoneme_length(), *query.speed_scale());
+        let ends = Self::accent_phrase_frame_ends(query, enable_interrogative_upspeak);
+
+        [first_start]
+            .into_iter()
+            .chain(ends[..ends.len() - 1].iter().copied())
+            .map(|frame| frame as u32 * samples_per_frame)
+            .collect()
+    }
+
+    /// `synthesis`の結果を、`query`の音量・サンプリングレート・チャンネル数に従ってWAVに変換し、
+    /// 量子化前の音声の[`SynthesisMetrics`]と共に返す。
+    ///
+    /// `cue_points`が空でなければ、それらの位置(サンプル数)を`cue `チャンクとして書き込む。
+    /// `output_gain_db`と計測については[`to_pcm_with_metrics`]を参照。
+    ///
+    /// [`to_pcm_with_metrics`]: Self::to_pcm_with_metrics
+    pub fn to_wav_with_metrics(
+        query: &AudioQueryModel,
+        wave: &[f32],
+        output_gain_db: f32,
+        cue_points: &[u32],
+    ) -> (Vec<u8>, SynthesisMetrics) {
+        let (pcm, metrics) = Self::to_pcm_with_metrics(query, wave, output_gain_db);
+        (Self::pcm_to_wav(query, pcm, cue_points), metrics)
+    }
+
+    /// `cue `チャンクは`data`チャンクの後に置く。`cue `チャンクを解釈しないプレイヤーは、未知の
+    /// チャンクとして読み飛ばす。
+    fn pcm_to_wav(query: &AudioQueryModel, pcm: Vec<u8>, cue_points: &[u32]) -> Vec<u8> {
+        let mut wav = Self::wav_header(query, pcm.len() as u32, cue_points.len());
+        wav.extend_from_slice(&pcm);
+
+        if !cue_points.is_empty() {
+            let cue_chunk_size = 4 + 24 * cue_points.len() as u32;
+            wav.write_all("cue ".as_bytes()).unwrap();
+            wav.write_all(&cue_chunk_size.to_le_bytes()).unwrap();
+            wav.write_all(&(cue_points.len() as u32).to_le_bytes())
+                .unwrap();
+            for (id, &position) in (1_u32..).zip(cue_points) {
+                wav.write_all(&id.to_le_bytes()).unwrap();
+                wav.write_all(&position.to_le_bytes()).unwrap();
+                wav.write_all("data".as_bytes()).unwrap();
+                wav.write_all(&0_u32.to_le_bytes()).unwrap(); // chunk start
+                wav.write_all(&0_u32.to_le_bytes()).unwrap(); // block start
+                wav.write_all(&position.to_le_bytes()).unwrap(); // sample offset
+            }
+        }
+
+        wav
+    }
+
+    /// `query`のサンプリングレート・チャンネル数で、`bytes_size`バイトのPCMと`num_cue_points`個の
+    /// キューポイントを持つWAVの、PCMより前の部分を返す。
+    ///
+    /// 返り値の容量はWAV全体の大きさとしてある。
+    pub fn wav_header(query: &AudioQueryModel, bytes_size: u32, num_cue_points: usize) -> Vec<u8> {
+        let num_channels: u16 = if *query.output_stereo() { 2 } else { 1 };
         let output_sampling_rate = *query.output_sampling_rate();
 
         // TODO: 44.1kHzなどの対応
 
-        let num_channels: u16 = if output_stereo { 2 } else { 1 };
-        let bit_depth: u16 = 16;
-        let repeat_count: u32 =
-            (output_sampling_rate / Self::DEFAULT_SAMPLING_RATE) * num_channels as u32;
+        let bit_depth = Self::BIT_DEPTH;
         let block_size: u16 = bit_depth * num_channels / 8;
 
-        let bytes_size = wave.len() as u32 * repeat_count * 2;
-        let wave_size = bytes_size + 44;
+        let wave_size = Self::wav_len(bytes_size as usize, num_cue_points) as u32;
 
         let buf: Vec<u8> = Vec::with_capacity(wave_size as usize);
         let mut cur = Cursor::new(buf);
@@ -465,21 +745,88 @@ impl SynthesisEngine {
         cur.write_all("data".as_bytes()).unwrap();
         cur.write_all(&bytes_size.to_le_bytes()).unwrap();
 
+        cur.into_inner()
+    }
+
+    /// `pcm_len`バイトのPCMと`num_cue_points`個のキューポイントを持つWAV全体のバイト数。
+    pub fn wav_len(pcm_len: usize, num_cue_points: usize) -> usize {
+        let cue_chunk_len = if num_cue_points == 0 {
+            0
+        } else {
+            8 + 4 + 24 * num_cue_points
+        };
+        44 + pcm_len + cue_chunk_len
+    }
+
+    /// `query`を`synthesis`したときの音声の長さ(サンプル数)を、推論を行わずに求める。
+    pub fn wave_len(query: &AudioQueryModel, enable_interrogative_upspeak: bool) -> usize {
+        let (f0, _) = Self::decode_inputs(query, enable_interrogative_upspeak);
+        f0.len() * SAMPLES_PER_FRAME
+    }
+
+    /// `wave_len`サンプルの音声を[`to_pcm`]で変換したときの、PCMのバイト数。
+    ///
+    /// [`to_pcm`]: Self::to_pcm
+    pub fn pcm_len(query: &AudioQueryModel, wave_len: usize) -> usize {
+        let num_channels = if *query.output_stereo() { 2 } else { 1 };
+        let repeat_count =
+            (*query.output_sampling_rate() / Self::DEFAULT_SAMPLING_RATE) as usize * num_channels;
+        wave_len * repeat_count * 2
+    }
+
+    /// `synthesis`の結果を、`query`の音量・サンプリングレート・チャンネル数に従って16bitのリニアPCMに
+    /// 変換する。
+    ///
+    /// 音量を掛けた後、量子化の前に[`output_gain`]で`output_gain_db`のゲインを掛ける。
+    pub fn to_pcm(query: &AudioQueryModel, wave: &[f32], output_gain_db: f32) -> Vec<u8> {
+        Self::to_pcm_with_metrics(query, wave, output_gain_db).0
+    }
+
+    /// [`to_pcm`]に加えて、量子化前の音声の[`SynthesisMetrics`]を返す。
+    ///
+    /// 計測はサンプリングレートの変換とチャンネルの複製の前に、`wave`の1サンプルごとに行う。
+    ///
+    /// [`to_pcm`]: Self::to_pcm
+    pub fn to_pcm_with_metrics(
+        query: &AudioQueryModel,
+        wave: &[f32],
+        output_gain_db: f32,
+    ) -> (Vec<u8>, SynthesisMetrics) {
+        let volume_scale = *query.volume_scale();
+        let output_gain = output_gain(output_gain_db);
+        let num_channels: u32 = if *query.output_stereo() { 2 } else { 1 };
+        let repeat_count: u32 =
+            (*query.output_sampling_rate() / Self::DEFAULT_SAMPLING_RATE) * num_channels;
+
+        let mut pcm = Vec::with_capacity(wave.len() * repeat_count as usize * 2);
+        let mut clipped_sample_count = 0;
+        let mut peak = 0_f32;
         for value in wave {
-            let v = (value * volume_scale).clamp(-1., 1.);
-            let data = (v * 0x7fff as f32) as i16;
+            let v = output_gain(value * volume_scale);
+            if v.abs() > 1. {
+                clipped_sample_count += 1;
+            }
+            peak = peak.max(v.abs());
+            let data = (v.clamp(-1., 1.) * 0x7fff as f32) as i16;
             for _ in 0..repeat_count {
-                cur.write_all(&data.to_le_bytes()).unwrap();
+                pcm.extend_from_slice(&data.to_le_bytes());
             }
         }
-
-        Ok(cur.into_inner())
+        let metrics = SynthesisMetrics {
+            clipped_sample_count,
+            peak_dbfs: 20. * peak.log10(),
+        };
+        (pcm, metrics)
     }
 
     pub fn is_openjtalk_dict_loaded(&self) -> bool {
         self.open_jtalk.dict_loaded()
     }
 
+    pub fn is_user_dict_word(&self, surface: &str) -> bool {
+        self.open_jtalk.is_user_dict_word(surface)
+    }
+
     fn initial_process(accent_phrases: &[AccentPhraseModel]) -> (Vec<MoraModel>, Vec<OjtPhoneme>) {
         let flatten_moras = to_flatten_moras(accent_phrases);
 
@@ -520,6 +867,90 @@ impl SynthesisEngine {
     }
 }
 
+/// `decode`が1フレームあたりに生成するサンプル数。
+const SAMPLES_PER_FRAME: usize = 256;
+
+/// `decode`が1フレームあたりに生成するサンプル数から求めた、1秒あたりのフレーム数。
+pub(super) const FRAME_RATE: f32 = 24000. / SAMPLES_PER_FRAME as f32;
+
+/// [`SynthesisEngine::decode_head`]が、先頭のアクセント句の後ろから文脈として含めるフレーム数。
+const HEAD_LOOKAHEAD_FRAMES: usize = 8;
+
+/// [`SynthesisEngine::synthesis_preview`]で、何フレームに1つを`decode`に渡すか。
+pub const PREVIEW_DECIMATION: usize = 2;
+
+/// 波形を、音高を保ったまま`factor`倍の長さに引き伸ばし、`len`サンプルにする。
+///
+/// 窓をずらしながら切り出した波形を、`factor`倍の間隔で重ね合わせる(OLA法)。
+fn time_stretch(wave: &[f32], factor: usize, len: usize) -> Vec<f32> {
+    const WINDOW_SIZE: usize = 512;
+    const SYNTHESIS_HOP: usize = WINDOW_SIZE / 2;
+
+    let analysis_hop = SYNTHESIS_HOP / factor;
+    // 50%ずつ重ねたときに和が1になるよう、周期的なHann窓を使う
+    let window = (0..WINDOW_SIZE)
+        .map(|i| 0.5 - 0.5 * (2. * std::f32::consts::PI * i as f32 / WINDOW_SIZE as f32).cos())
+        .collect::<Vec<_>>();
+
+    let mut stretched = vec![0.; len];
+    for k in 0..=len / SYNTHESIS_HOP + 1 {
+        // 窓の中心が、出力では`k * SYNTHESIS_HOP`、入力では`k * analysis_hop`に来るようにする
+        let dst_start = (k * SYNTHESIS_HOP) as isize - (WINDOW_SIZE / 2) as isize;
+        let src_start = (k * analysis_hop) as isize - (WINDOW_SIZE / 2) as isize;
+        for (i, w) in window.iter().enumerate() {
+            let (Ok(dst), Ok(src)) = (
+                usize::try_from(dst_start + i as isize),
+                usize::try_from(src_start + i as isize),
+            ) else {
+                continue;
+            };
+            if let (Some(y), Some(x)) = (stretched.get_mut(dst), wave.get(src)) {
+                *y += w * x;
+            }
+        }
+    }
+    stretched
+}
+
+/// 出力の最終段で`output_gain_db`のゲインを掛ける関数を返す。
+///
+/// ゲインを掛けた後、[`OUTPUT_GAIN_KNEE`]を超える振幅は、±1に漸近するように滑らかに圧縮する。
+/// `output_gain_db`が0の場合は何もしない。
+pub(crate) fn output_gain(output_gain_db: f32) -> impl Fn(f32) -> f32 {
+    let gain = 10_f32.powf(output_gain_db / 20.);
+    move |value| {
+        if output_gain_db == 0. {
+            return value;
+        }
+        let value = value * gain;
+        if value.abs() <= OUTPUT_GAIN_KNEE {
+            return value;
+        }
+        let headroom = 1. - OUTPUT_GAIN_KNEE;
+        let excess = (value.abs() - OUTPUT_GAIN_KNEE) / headroom;
+        value.signum() * (OUTPUT_GAIN_KNEE + headroom * excess.tanh())
+    }
+}
+
+/// [`output_gain`]で、振幅の圧縮を始める大きさ。
+const OUTPUT_GAIN_KNEE: f32 = 0.8;
+
+/// アクセント句に含まれる音素の長さを、音素の順に列挙する。
+fn phoneme_lengths(accent_phrases: &[AccentPhraseModel]) -> impl Iterator<Item = f32> {
+    to_flatten_moras(accent_phrases)
+        .into_iter()
+        .flat_map(|mora| [*mora.consonant_length(), Some(*mora.vowel_length())])
+        .flatten()
+}
+
+/// 音素の長さ(秒)を、`speed_scale`を反映したフレーム数に変換する。
+fn to_frame_length(phoneme_length: f32, speed_scale: f32) -> usize {
+    // VOICEVOX ENGINEと挙動を合わせるため、四捨五入ではなく偶数丸めをする
+    //
+    // https://github.com/VOICEVOX/voicevox_engine/issues/552
+    ((phoneme_length * FRAME_RATE).round_ties_even_() / speed_scale).round_ties_even_() as usize
+}
+
 pub fn to_flatten_moras(accent_phrases: &[AccentPhraseModel]) -> Vec<MoraModel> {
     let mut flatten_moras = Vec::new();
 
@@ -577,6 +1008,18 @@ pub fn split_mora(phoneme_list: &[OjtPhoneme]) -> (Vec<OjtPhoneme>, Vec<OjtPhone
     (consonant_phoneme_list, vowel_phoneme_list, vowel_indexes)
 }
 
+/// 読み上げる内容が無いものとして扱う句読点。
+const SILENT_PUNCTUATIONS: &[char] = &[
+    '、', '。', '，', '．', '！', '？', '・', '…', '‥', '「', '」', '『', '』', '（', '）', ',',
+    '.', '!', '?', '(', ')',
+];
+
+/// `text`が空白と句読点のみからなるかどうか。
+fn is_silent_text(text: &str) -> bool {
+    text.chars()
+        .all(|c| c.is_whitespace() || SILENT_PUNCTUATIONS.contains(&c))
+}
+
 fn mora_to_text(mora: impl AsRef<str>) -> String {
     let last_char = mora.as_ref().chars().last().unwrap();
     let mora = if ['A', 'I', 'U', 'E', 'O'].contains(&last_char) {
@@ -610,20 +1053,28 @@ fn adjust_interrogative_accent_phrases(
 
 fn adjust_interrogative_moras(accent_phrase: &AccentPhraseModel) -> Vec<MoraModel> {
     let moras = accent_phrase.moras();
-    if *accent_phrase.is_interrogative() && !moras.is_empty() {
+    if is_upspeak_target(accent_phrase) {
         let last_mora = moras.last().unwrap();
-        let last_mora_pitch = *last_mora.pitch();
-        if last_mora_pitch != 0.0 {
-            let mut new_moras: Vec<MoraModel> = Vec::with_capacity(moras.len() + 1);
-            new_moras.extend_from_slice(moras.as_slice());
-            let interrogative_mora = make_interrogative_mora(last_mora);
-            new_moras.push(interrogative_mora);
-            return new_moras;
-        }
+        let mut new_moras: Vec<MoraModel> = Vec::with_capacity(moras.len() + 1);
+        new_moras.extend_from_slice(moras.as_slice());
+        let interrogative_mora = make_interrogative_mora(last_mora);
+        new_moras.push(interrogative_mora);
+        return new_moras;
     }
     moras.clone()
 }
 
+/// 疑問文の調整でモーラが追加されるアクセント句かどうか。
+///
+/// 疑問文であっても、最後のモーラが無声化している(音高が0の)場合は調整しない。
+fn is_upspeak_target(accent_phrase: &AccentPhraseModel) -> bool {
+    *accent_phrase.is_interrogative()
+        && accent_phrase
+            .moras()
+            .last()
+            .map_or(false, |last_mora| *last_mora.pitch() != 0.0)
+}
+
 fn make_interrogative_mora(last_mora: &MoraModel) -> MoraModel {
     const FIX_VOWEL_LENGTH: f32 = 0.15;
     const ADJUST_PITCH: f32 = 0.3;
@@ -649,12 +1100,181 @@ mod tests {
 
     use crate::*;
 
+    #[rstest]
+    fn time_stretch_keeps_pitch() {
+        // 周期が解析側のホップ幅を割り切る正弦波は、引き伸ばしても同じ正弦波になる
+        let sine = |i: usize| (2. * std::f32::consts::PI * (i % 64) as f32 / 64.).sin();
+        let wave = (0..4800).map(sine).collect::<Vec<_>>();
+
+        let stretched = time_stretch(&wave, 2, 9600);
+
+        assert_eq!(9600, stretched.len());
+        // 窓が入力の範囲内に収まる区間で比べる
+        for (i, &actual) in stretched.iter().enumerate().take(8000).skip(256) {
+            assert!((sine(i) - actual).abs() < 1e-4, "{i}: {actual}");
+        }
+    }
+
+    #[rstest]
+    #[case(0., &[0.5, -0.5, 1.5], &[0.5, -0.5, 1.5])]
+    #[case(-6., &[0.5, -0.5], &[0.2506, -0.2506])]
+    #[case(6., &[0.2, 0.5, -0.5, 100.], &[0.3991, 0.9513, -0.9513, 1.])]
+    fn output_gain_works(
+        #[case] output_gain_db: f32,
+        #[case] input: &[f32],
+        #[case] expected: &[f32],
+    ) {
+        let output_gain = output_gain(output_gain_db);
+        for (&input, &expected) in input.iter().zip(expected) {
+            let actual = output_gain(input);
+            assert!((expected - actual).abs() < 1e-4, "{input}: {actual}");
+            assert!(output_gain_db == 0. || actual.abs() <= 1.);
+        }
+    }
+
+    #[rstest]
+    #[case(1., 0., &[0.5, -0.25], 0, -6.0206)]
+    #[case(2., 0., &[0.5, -0.75, 0.1], 1, 3.5218)]
+    #[case(1., 0., &[0., 0.], 0, f32::NEG_INFINITY)]
+    fn to_pcm_with_metrics_works(
+        #[case] volume_scale: f32,
+        #[case] output_gain_db: f32,
+        #[case] wave: &[f32],
+        #[case] expected_clipped_sample_count: usize,
+        #[case] expected_peak_dbfs: f32,
+    ) {
+        let query = AudioQueryModel::new(
+            vec![],
+            1.,
+            0.,
+            1.,
+            volume_scale,
+            0.,
+            0.,
+            SynthesisEngine::DEFAULT_SAMPLING_RATE,
+            true,
+            None,
+        );
+
+        let (pcm, metrics) = SynthesisEngine::to_pcm_with_metrics(&query, wave, output_gain_db);
+
+        assert_eq!(SynthesisEngine::to_pcm(&query, wave, output_gain_db), pcm);
+        assert_eq!(expected_clipped_sample_count, metrics.clipped_sample_count,);
+        assert!(
+            expected_peak_dbfs == metrics.peak_dbfs
+                || (expected_peak_dbfs - metrics.peak_dbfs).abs() < 1e-4,
+            "{}",
+            metrics.peak_dbfs,
+        );
+    }
+
+    #[rstest]
+    #[case(SynthesisEngine::DEFAULT_SAMPLING_RATE, &[9 * 256, 27 * 256])]
+    #[case(2 * SynthesisEngine::DEFAULT_SAMPLING_RATE, &[9 * 512, 27 * 512])]
+    fn accent_phrase_cue_points_works(#[case] output_sampling_rate: u32, #[case] expected: &[u32]) {
+        let mora = MoraModel::new("ア".into(), None, None, "a".into(), 0.1, 5.);
+        let accent_phrases = vec![
+            AccentPhraseModel::new(vec![mora.clone(), mora.clone()], 1, None, false),
+            AccentPhraseModel::new(vec![mora], 1, None, false),
+        ];
+        let query = AudioQueryModel::new(
+            accent_phrases,
+            1.,
+            0.,
+            1.,
+            1.,
+            0.1,
+            0.1,
+            output_sampling_rate,
+            false,
+            None,
+        );
+
+        assert_eq!(
+            expected,
+            SynthesisEngine::accent_phrase_cue_points(&query, false),
+        );
+    }
+
+    #[rstest]
+    fn to_wav_with_metrics_writes_cue_chunk() {
+        let query = AudioQueryModel::new(
+            vec![],
+            1.,
+            0.,
+            1.,
+            1.,
+            0.,
+            0.,
+            SynthesisEngine::DEFAULT_SAMPLING_RATE,
+            false,
+            None,
+        );
+        let wave = [0.; 4];
+
+        let (plain, _) = SynthesisEngine::to_wav_with_metrics(&query, &wave, 0., &[]);
+        let (wav, _) = SynthesisEngine::to_wav_with_metrics(&query, &wave, 0., &[1, 3]);
+
+        assert_eq!(52, plain.len());
+        assert_eq!(plain[8..40], wav[8..40]);
+        assert_eq!(
+            wav.len() as u32 - 8,
+            u32::from_le_bytes(wav[4..8].try_into().unwrap())
+        );
+        let cue = &wav[plain.len()..];
+        assert_eq!(b"cue ", &cue[..4]);
+        assert_eq!(52, u32::from_le_bytes(cue[4..8].try_into().unwrap()));
+        assert_eq!(2, u32::from_le_bytes(cue[8..12].try_into().unwrap()));
+        let positions = cue[12..]
+            .chunks(24)
+            .map(|point| {
+                assert_eq!(b"data", &point[8..12]);
+                u32::from_le_bytes(point[20..24].try_into().unwrap())
+            })
+            .collect::<Vec<_>>();
+        assert_eq!([1, 3], *positions);
+    }
+
+    #[rstest]
+    #[case(true, true, 5., true)]
+    #[case(false, true, 5., false)]
+    #[case(true, false, 5., false)]
+    #[case(true, true, 0., false)]
+    fn applies_interrogative_upspeak_works(
+        #[case] enable_interrogative_upspeak: bool,
+        #[case] is_interrogative: bool,
+        #[case] last_pitch: f32,
+        #[case] expected: bool,
+    ) {
+        let moras = vec![
+            MoraModel::new("ア".into(), None, None, "a".into(), 0.1, 5.),
+            MoraModel::new("ア".into(), None, None, "a".into(), 0.1, last_pitch),
+        ];
+        let query = AudioQueryModel::from_accent_phrases(
+            vec![AccentPhraseModel::new(moras, 1, None, is_interrogative)],
+            &Default::default(),
+        )
+        .unwrap();
+
+        assert_eq!(
+            expected,
+            SynthesisEngine::applies_interrogative_upspeak(&query, enable_interrogative_upspeak),
+        );
+    }
+
     #[rstest]
     #[tokio::test]
     async fn is_openjtalk_dict_loaded_works() {
-        let core = InferenceCore::new_with_initialize(false, 0, false)
-            .await
-            .unwrap();
+        let core = InferenceCore::new_with_initialize(
+            ExecutionProvider::Cpu,
+            &InitializeOptions {
+                acceleration_mode: AccelerationMode::Cpu,
+                load_all_models: false,
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
         let synthesis_engine = SynthesisEngine::new(
             core,
             OpenJtalk::new_with_initialize(OPEN_JTALK_DIC_DIR)
@@ -668,9 +1288,16 @@ mod tests {
     #[rstest]
     #[tokio::test]
     async fn create_accent_phrases_works() {
-        let core = InferenceCore::new_with_initialize(false, 0, true)
-            .await
-            .unwrap();
+        let core = InferenceCore::new_with_initialize(
+            ExecutionProvider::Cpu,
+            &InitializeOptions {
+                acceleration_mode: AccelerationMode::Cpu,
+                load_all_models: true,
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
         let synthesis_engine = SynthesisEngine::new(
             core,
             OpenJtalk::new_with_initialize(OPEN_JTALK_DIC_DIR)
@@ -721,4 +1348,95 @@ mod tests {
             );
         }
     }
+
+    #[rstest]
+    #[tokio::test]
+    async fn create_accent_phrases_applies_accent_hints() {
+        let core = InferenceCore::new_with_initialize(
+            ExecutionProvider::Cpu,
+            &InitializeOptions {
+                acceleration_mode: AccelerationMode::Cpu,
+                load_all_models: true,
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+        let synthesis_engine = SynthesisEngine::new(
+            core,
+            OpenJtalk::new_with_initialize(OPEN_JTALK_DIC_DIR)
+                .unwrap()
+                .into(),
+        );
+
+        let accent_phrases = synthesis_engine
+            .create_accent_phrases("会議{かいぎ:3}", StyleId::new(1))
+            .await
+            .unwrap();
+        assert_eq!(1, accent_phrases.len());
+        assert_eq!(3, *accent_phrases[0].accent());
+        let moras = accent_phrases[0]
+            .moras()
+            .iter()
+            .map(|mora| mora.text().clone())
+            .collect::<Vec<_>>();
+        assert_eq!(["カ", "イ", "ギ"], &*moras);
+
+        let result = synthesis_engine
+            .create_accent_phrases("明日は会議{かいぎ:4}", StyleId::new(1))
+            .await;
+        assert!(
+            matches!(result, Err(Error::InvalidAccentHint { ref span, .. }) if *span == (9..28)),
+            "{result:?}",
+        );
+
+        synthesis_engine
+            .create_accent_phrases("会議}です。関数{}", StyleId::new(1))
+            .await
+            .unwrap();
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn create_accent_phrases_applies_contextual_words() {
+        let core = InferenceCore::new_with_initialize(
+            ExecutionProvider::Cpu,
+            &InitializeOptions {
+                acceleration_mode: AccelerationMode::Cpu,
+                load_all_models: true,
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+        let open_jtalk = OpenJtalk::new_with_initialize(OPEN_JTALK_DIC_DIR).unwrap();
+        let mut user_dict = UserDict::new();
+        user_dict
+            .add_contextual_word(ContextualWord {
+                surface: "今日".to_owned(),
+                pronunciation: "コンニチ".to_owned(),
+                accent_type: 1,
+                preceding: vec![],
+                following: vec!["の社会".to_owned()],
+            })
+            .unwrap();
+        open_jtalk.use_user_dict(&user_dict).unwrap();
+        let synthesis_engine = SynthesisEngine::new(core, open_jtalk.into());
+
+        for (text, expected_first_mora) in [
+            ("今日の社会", "コ"),
+            ("今日は晴れ", "キョ"),
+            ("今日{きょう:1}の社会", "キョ"),
+        ] {
+            let accent_phrases = synthesis_engine
+                .create_accent_phrases(text, StyleId::new(1))
+                .await
+                .unwrap();
+            assert_eq!(
+                expected_first_mora,
+                *accent_phrases[0].moras()[0].text(),
+                "{text}",
+            );
+        }
+    }
 }
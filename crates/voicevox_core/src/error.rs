@@ -1,57 +1,283 @@
 use self::engine::{FullContextLabelError, KanaParseError};
 use super::*;
 //use engine::
-use std::path::PathBuf;
+use std::{
+    ops::Range,
+    path::{Path, PathBuf},
+};
 use thiserror::Error;
 use uuid::Uuid;
 
 /// VOICEVOX COREのエラー。
+///
+/// [`Synthesizer`]などの失敗しうるメソッドは、このエラーを持つ[`Result`]を返す。各バリアントの
+/// 説明には、そのエラーを返す主なAPIを記す。エラーの種類を数値で扱いたい場合は[`code`]を用いる。
+///
+/// [`code`]: Self::code
 #[derive(Error, Debug)]
 pub enum Error {
+    /// OpenJTalkの辞書が読み込まれていない。
+    ///
+    /// [`OpenJtalk::new_without_dic`]で作ったOpenJTalkを使う[`Synthesizer`]で、テキストを解析する
+    /// メソッド([`Synthesizer::create_accent_phrases`]、[`Synthesizer::audio_query`]、
+    /// [`Synthesizer::tts`]など)を呼んだときに返る。
     #[error("OpenJTalkの辞書が読み込まれていません")]
     NotLoadedOpenjtalkDict,
 
-    #[error("GPU機能をサポートすることができません")]
-    GpuSupport,
+    /// GPUが使えない。
+    ///
+    /// [`AccelerationMode::Gpu`]を指定した[`Synthesizer::new_with_initialize`]などが返す。
+    #[error("GPU機能をサポートすることができません: {0}")]
+    GpuSupport(GpuUnsupportedReason),
 
+    /// 音声モデルの読み込みに失敗した。
+    ///
+    /// [`VoiceModel::from_path`]や[`Synthesizer::load_voice_model`]などが返す。詳細は
+    /// [`LoadModelError::context`]で得られる。
     #[error(transparent)]
     LoadModel(#[from] LoadModelError),
 
+    /// 指定された音声モデルが読み込まれていない。
+    ///
+    /// [`Synthesizer::unload_voice_model`]が返す。
     #[error("Modelが読み込まれていません ({model_id:?})")]
     UnloadedModel { model_id: VoiceModelId },
 
+    /// 利用可能なデバイスの情報を取得できなかった。
+    ///
+    /// [`SupportedDevices::create`]が返す。
     #[error("サポートされているデバイス情報取得中にエラーが発生しました,{0}")]
     GetSupportedDevices(#[source] anyhow::Error),
 
+    /// 指定されたスタイルIDのスタイルが読み込まれていない。
+    ///
+    /// スタイルIDを受け取る[`Synthesizer`]のメソッド([`Synthesizer::audio_query`]、
+    /// [`Synthesizer::synthesis`]など)が返す。
     #[error("無効なspeaker_idです: {style_id:?}")]
     InvalidStyleId { style_id: StyleId },
 
+    /// 指定された話者名とスタイル名のスタイルが読み込まれていない。
+    ///
+    /// [`Synthesizer::tts_by_name`]が返す。
+    #[error(
+        "話者`{speaker}`のスタイル`{style}`は読み込まれていません (読み込まれているスタイル: {})",
+        available.join(", ")
+    )]
+    StyleNotFound {
+        speaker: String,
+        style: String,
+        /// 読み込まれているスタイルの一覧。各要素は`"話者名 (スタイル名)"`の形式である。
+        available: Vec<String>,
+    },
+
+    /// 音声モデルのIDとして不正な文字列である。
+    ///
+    /// [`VoiceModelId`]の[`FromStr`]実装が返す。
+    ///
+    /// [`FromStr`]: std::str::FromStr
     #[error("無効なmodel_idです: {model_id:?}")]
     InvalidModelId { model_id: VoiceModelId },
 
+    /// 推論に失敗した。
+    ///
+    /// 推論を行うメソッド([`Synthesizer::audio_query`]、[`Synthesizer::synthesis`]、
+    /// [`Synthesizer::predict_duration`]など)が返す。
     #[error("推論に失敗しました")]
     InferenceFailed,
 
+    /// OpenJTalkによるテキストの解析に失敗した。
+    ///
+    /// テキストを解析するメソッド([`Synthesizer::create_accent_phrases`]、
+    /// [`Synthesizer::audio_query`]、[`Synthesizer::tts`]など)が返す。
     #[error("入力テキストからのフルコンテキストラベル抽出に失敗しました,{0}")]
     ExtractFullContextLabel(#[from] FullContextLabelError),
 
+    /// AquesTalk風記法のテキストとして不正である。
+    ///
+    /// `kana`オプションを有効にした[`Synthesizer::create_accent_phrases`]、
+    /// [`Synthesizer::audio_query`]、[`Synthesizer::tts`]や、
+    /// [`Synthesizer::accent_phrases_from_kana`]などが返す。
     #[error("入力テキストをAquesTalk風記法としてパースすることに失敗しました,{0}")]
     ParseKana(#[from] KanaParseError),
 
+    /// ユーザー辞書のファイルを読み込めなかった。
+    ///
+    /// [`UserDict::load`]が返す。
     #[error("ユーザー辞書を読み込めませんでした: {0}")]
     LoadUserDict(String),
 
+    /// ユーザー辞書のファイルを書き込めなかった。
+    ///
+    /// [`UserDict::save`]が返す。
     #[error("ユーザー辞書を書き込めませんでした: {0}")]
     SaveUserDict(String),
 
+    /// 指定されたUUIDの単語がユーザー辞書に無い。
+    ///
+    /// [`UserDict::update_word`]と[`UserDict::remove_word`]が返す。
     #[error("ユーザー辞書に単語が見つかりませんでした: {0}")]
     UnknownWord(Uuid),
 
+    /// ユーザー辞書をOpenJTalkに設定できなかった。
+    ///
+    /// [`OpenJtalk::use_user_dict`]などが返す。
     #[error("OpenJTalkのユーザー辞書の設定に失敗しました: {0}")]
     UseUserDict(String),
 
+    /// ユーザー辞書の単語として不正である。
+    ///
+    /// [`UserDictWord::new`]と[`UserDict::update_word`]が返す。
     #[error("ユーザー辞書の単語のバリデーションに失敗しました: {0}")]
     InvalidWord(InvalidWordError),
+
+    /// 指定されたアクセント句の位置が範囲外である。
+    ///
+    /// [`AudioQueryModel::split_accent_phrase`]や[`AudioQueryModel::merge_accent_phrases`]などが
+    /// 返す。
+    #[error("アクセント句の位置が範囲外です: {index}")]
+    InvalidAccentPhraseIndex { index: usize },
+
+    /// 指定されたモーラの位置が範囲外である。
+    ///
+    /// [`AudioQueryModel::split_accent_phrase`]などが返す。
+    #[error("モーラの位置が範囲外です: {mora_offset}")]
+    InvalidMoraOffset { mora_offset: usize },
+
+    /// 音声合成に使えないアクセント句が含まれている。
+    ///
+    /// [`AudioQueryModel::from_accent_phrases`]、[`AccentPhraseModel::from_moras`]、
+    /// [`Synthesizer::synthesis`]などが返す。
+    #[error("{index}番目のアクセント句が不正です: {reason}")]
+    InconsistentAccentPhrase { index: usize, reason: String },
+
+    /// 入力テキストが[`AudioQueryOptions::max_text_length`]を超えている。
+    ///
+    /// [`Synthesizer::audio_query`]や[`Synthesizer::tts`]などが返す。
+    #[error("入力テキストが長すぎます: {len}文字 (上限: {max}文字)")]
+    TextTooLong { len: usize, max: usize },
+
+    /// 入力テキスト中のアクセント指定が不正である。
+    ///
+    /// テキストを解析するメソッド([`Synthesizer::create_accent_phrases`]、
+    /// [`Synthesizer::audio_query`]など)が返す。
+    #[error("{}..{}バイト目のアクセント指定が不正です: {reason}", span.start, span.end)]
+    InvalidAccentHint { span: Range<usize>, reason: String },
+
+    /// 処理に必要なネットワークを、音声モデルが含んでいない。
+    ///
+    /// 推論を行うメソッドが、そのネットワークを含まない音声モデルのスタイルで呼ばれたときに返る。
+    #[error("音声モデル`{model_id}`は`{network}`のネットワークを含んでいません")]
+    MissingNetwork {
+        model_id: VoiceModelId,
+        network: Network,
+    },
+
+    /// [`AudioQueryModel::from_bytes`]などに渡されたバイナリが不正。
+    ///
+    /// [`AudioQueryModel::from_bytes`]、[`AccentPhraseModel::from_bytes`]、
+    /// [`OpenJtalk::use_user_dict_bytes`]が返す。
+    #[error("バイナリ形式のデータが不正です: {reason}")]
+    InvalidBinaryData { reason: String },
+
+    /// 音声の出力先への書き込みに失敗した。
+    ///
+    /// [`Synthesizer::synthesis_to_writer`]が返す。
+    #[error("出力先への書き込みに失敗しました")]
+    WriteOutput(#[source] std::io::Error),
+
+    /// AudioQueryの出力のサンプリングレートに対応していない。
+    ///
+    /// サンプリングレートは24000の正の倍数である必要がある。[`Synthesizer::synthesis_described`]が
+    /// 返す。
+    #[error(
+        "対応していない出力のサンプリングレートです: {sampling_rate} (24000の正の倍数である必要があります)"
+    )]
+    UnsupportedSamplingRate { sampling_rate: u32 },
+}
+
+impl Error {
+    /// エラーの種類を表す数値を返す。
+    ///
+    /// 値は[`error_code`]の定数のいずれかである。C APIの`VoicevoxResultCode`と同じ値であり、一度
+    /// 割り当てた値は変更しない。C API以外のバインディングがエラーを分類するのに使える。
+    pub fn code(&self) -> u32 {
+        use error_code::*;
+
+        match self {
+            Self::NotLoadedOpenjtalkDict => NOT_LOADED_OPENJTALK_DICT,
+            Self::GpuSupport(_) => GPU_SUPPORT,
+            Self::LoadModel(err) => match err.context() {
+                LoadModelErrorKind::OpenZipFile
+                | LoadModelErrorKind::ReadStream
+                | LoadModelErrorKind::TooLarge { .. } => OPEN_ZIP_FILE,
+                LoadModelErrorKind::ReadZipEntry { .. }
+                | LoadModelErrorKind::MissingEntry { .. } => READ_ZIP_ENTRY,
+                LoadModelErrorKind::ModelAlreadyLoaded { .. } => MODEL_ALREADY_LOADED,
+                LoadModelErrorKind::StyleAlreadyLoaded { .. } => STYLE_ALREADY_LOADED,
+                LoadModelErrorKind::InvalidModelData
+                | LoadModelErrorKind::ChecksumMismatch { .. } => INVALID_MODEL_DATA,
+                LoadModelErrorKind::Timeout => LOAD_MODEL_TIMEOUT,
+                LoadModelErrorKind::UnsupportedModelVersion { .. } => UNSUPPORTED_MODEL_VERSION,
+            },
+            Self::UnloadedModel { .. } => UNLOADED_MODEL,
+            Self::GetSupportedDevices(_) => GET_SUPPORTED_DEVICES,
+            Self::InvalidStyleId { .. } | Self::StyleNotFound { .. } => INVALID_STYLE_ID,
+            Self::InvalidModelId { .. } => INVALID_MODEL_ID,
+            Self::InferenceFailed => INFERENCE,
+            Self::ExtractFullContextLabel(_) => EXTRACT_FULL_CONTEXT_LABEL,
+            Self::ParseKana(_) => PARSE_KANA,
+            Self::LoadUserDict(_) => LOAD_USER_DICT,
+            Self::SaveUserDict(_) => SAVE_USER_DICT,
+            Self::UnknownWord(_) => UNKNOWN_USER_DICT_WORD,
+            Self::UseUserDict(_) => USE_USER_DICT,
+            Self::InvalidWord(_) => INVALID_USER_DICT_WORD,
+            Self::InvalidAccentPhraseIndex { .. } | Self::InvalidMoraOffset { .. } => {
+                INVALID_ACCENT_PHRASE_POSITION
+            }
+            Self::InconsistentAccentPhrase { .. } => INCONSISTENT_ACCENT_PHRASE,
+            Self::TextTooLong { .. } => TEXT_TOO_LONG,
+            Self::InvalidAccentHint { .. } => INVALID_ACCENT_HINT,
+            Self::MissingNetwork { .. } => MISSING_NETWORK,
+            Self::InvalidBinaryData { .. } => INVALID_BINARY_DATA,
+            Self::WriteOutput(_) => WRITE_OUTPUT,
+            Self::UnsupportedSamplingRate { .. } => UNSUPPORTED_SAMPLING_RATE,
+        }
+    }
+}
+
+/// [`Error::code`]が返す値。
+///
+/// それぞれC APIの`VOICEVOX_RESULT_{名前}_ERROR`と同じ値である。
+pub mod error_code {
+    pub const NOT_LOADED_OPENJTALK_DICT: u32 = 1;
+    pub const GET_SUPPORTED_DEVICES: u32 = 3;
+    pub const GPU_SUPPORT: u32 = 4;
+    pub const INVALID_STYLE_ID: u32 = 6;
+    pub const INVALID_MODEL_ID: u32 = 7;
+    pub const INFERENCE: u32 = 8;
+    pub const EXTRACT_FULL_CONTEXT_LABEL: u32 = 11;
+    pub const PARSE_KANA: u32 = 13;
+    pub const OPEN_ZIP_FILE: u32 = 16;
+    pub const READ_ZIP_ENTRY: u32 = 17;
+    pub const MODEL_ALREADY_LOADED: u32 = 18;
+    pub const UNLOADED_MODEL: u32 = 19;
+    pub const LOAD_USER_DICT: u32 = 20;
+    pub const SAVE_USER_DICT: u32 = 21;
+    pub const UNKNOWN_USER_DICT_WORD: u32 = 22;
+    pub const USE_USER_DICT: u32 = 23;
+    pub const INVALID_USER_DICT_WORD: u32 = 24;
+    pub const STYLE_ALREADY_LOADED: u32 = 26;
+    pub const INVALID_MODEL_DATA: u32 = 27;
+    pub const LOAD_MODEL_TIMEOUT: u32 = 28;
+    pub const INVALID_ACCENT_PHRASE_POSITION: u32 = 29;
+    pub const TEXT_TOO_LONG: u32 = 30;
+    pub const INVALID_ACCENT_HINT: u32 = 32;
+    pub const UNSUPPORTED_MODEL_VERSION: u32 = 33;
+    pub const MISSING_NETWORK: u32 = 34;
+    pub const INVALID_BINARY_DATA: u32 = 35;
+    pub const WRITE_OUTPUT: u32 = 36;
+    pub const INCONSISTENT_ACCENT_PHRASE: u32 = 37;
+    pub const UNSUPPORTED_SAMPLING_RATE: u32 = 38;
 }
 
 pub(crate) type LoadModelResult<T> = std::result::Result<T, LoadModelError>;
@@ -73,6 +299,28 @@ impl LoadModelError {
     pub fn context(&self) -> &LoadModelErrorKind {
         &self.context
     }
+
+    /// 読み込もうとした音声モデルのパス。
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+/// GPUが使えない理由。
+#[derive(derive_more::Display, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GpuUnsupportedReason {
+    /// `cuda`と`directml`のどちらのフィーチャも有効化されずにビルドされている。
+    #[display(fmt = "GPUに対応しないビルドです")]
+    NotBuiltWithGpuSupport,
+    /// 指定された[`ExecutionProvider`]に対応しないビルドである。
+    #[display(fmt = "{_0}に対応しないビルドです")]
+    NotBuiltWithExecutionProvider(ExecutionProvider),
+    /// ONNX RuntimeからCUDA Execution Providerが利用できない。
+    #[display(fmt = "CUDAが利用できません。ONNX RuntimeのCUDA版とそのドライバが必要です")]
+    CudaUnavailable,
+    /// ONNX RuntimeからDirectML Execution Providerが利用できない。
+    #[display(fmt = "DirectMLが利用できません。ONNX RuntimeのDirectML版が必要です")]
+    DirectMlUnavailable,
 }
 
 #[derive(derive_more::Display, Debug)]
@@ -81,10 +329,67 @@ pub enum LoadModelErrorKind {
     OpenZipFile,
     #[display(fmt = "`{filename}`を読み取れませんでした")]
     ReadZipEntry { filename: String },
+    #[display(fmt = "`{filename}`が見つかりませんでした")]
+    MissingEntry { filename: String },
+    #[display(fmt = "`{filename}`のチェックサムが一致しませんでした")]
+    ChecksumMismatch { filename: String },
     #[display(fmt = "モデル`{id}`は既に読み込まれています")]
     ModelAlreadyLoaded { id: VoiceModelId },
     #[display(fmt = "スタイル`{id}`は既に読み込まれています")]
     StyleAlreadyLoaded { id: StyleId },
     #[display(fmt = "モデルデータを読むことができませんでした")]
     InvalidModelData,
+    #[display(fmt = "ストリームを読み取れませんでした")]
+    ReadStream,
+    #[display(fmt = "サイズが上限の{limit}バイトを超えています")]
+    TooLarge { limit: u64 },
+    #[display(fmt = "制限時間内に読み込みが完了しませんでした")]
+    Timeout,
+    /// マニフェストのバージョンに対応していない。より新しいバージョンのVOICEVOX COREが必要である。
+    #[display(
+        fmt = "バージョン{found}の音声モデルには対応していません (対応しているバージョン: {})。\
+               VOICEVOX COREを更新してください",
+        "supported.join(\", \")"
+    )]
+    UnsupportedModelVersion {
+        found: ManifestVersion,
+        supported: &'static [&'static str],
+    },
+}
+
+impl LoadModelErrorKind {
+    /// [`ModelAlreadyLoaded`]の場合、既に読み込まれている音声モデルのIDを返す。
+    ///
+    /// [`ModelAlreadyLoaded`]: Self::ModelAlreadyLoaded
+    pub fn model_id(&self) -> Option<&VoiceModelId> {
+        match self {
+            Self::ModelAlreadyLoaded { id } => Some(id),
+            _ => None,
+        }
+    }
+
+    /// [`StyleAlreadyLoaded`]の場合、既に読み込まれているスタイルのIDを返す。
+    ///
+    /// [`StyleAlreadyLoaded`]: Self::StyleAlreadyLoaded
+    pub fn style_id(&self) -> Option<StyleId> {
+        match self {
+            Self::StyleAlreadyLoaded { id } => Some(*id),
+            _ => None,
+        }
+    }
+
+    /// [`ReadZipEntry`]、[`MissingEntry`]、[`ChecksumMismatch`]の場合、対象のZIP内のファイル名を
+    /// 返す。
+    ///
+    /// [`ReadZipEntry`]: Self::ReadZipEntry
+    /// [`MissingEntry`]: Self::MissingEntry
+    /// [`ChecksumMismatch`]: Self::ChecksumMismatch
+    pub fn filename(&self) -> Option<&str> {
+        match self {
+            Self::ReadZipEntry { filename }
+            | Self::MissingEntry { filename }
+            | Self::ChecksumMismatch { filename } => Some(filename),
+            _ => None,
+        }
+    }
 }
@@ -1,6 +1,7 @@
 use self::status::*;
 use super::*;
 use onnxruntime::{ndarray, session::NdArray};
+use std::time::{Duration, Instant};
 
 const PHONEME_LENGTH_MINIMAL: f32 = 0.01;
 
@@ -9,44 +10,111 @@ pub struct InferenceCore {
 }
 
 impl InferenceCore {
+    /// `options`の`acceleration_mode`と`execution_provider`から決めた`execution_provider`で初期化する。
     pub(crate) async fn new_with_initialize(
-        use_gpu: bool,
-        cpu_num_threads: u16,
-        load_all_models: bool,
+        execution_provider: ExecutionProvider,
+        options: &InitializeOptions,
     ) -> Result<Self> {
-        if !use_gpu || Self::can_support_gpu_feature()? {
-            let status = Status::new(use_gpu, cpu_num_threads);
+        if execution_provider.is_gpu() {
+            Self::check_gpu_support(execution_provider)?;
+        }
+        let status = Status::new(
+            execution_provider,
+            options
+                .intra_op_num_threads
+                .unwrap_or(options.cpu_num_threads),
+            options
+                .inter_op_num_threads
+                .unwrap_or(options.cpu_num_threads),
+        )
+        .with_cpu_fallback(
+            options.execution_provider.is_none()
+                && options.acceleration_mode == AccelerationMode::Auto,
+        )
+        .with_flush_denormals(options.flush_denormals)
+        .with_inference_retries(options.inference_retries);
 
-            if load_all_models {
-                for model in &VoiceModel::get_all_models().await? {
-                    status.load_model(model).await?;
-                }
+        if options.load_all_models {
+            let models = VoiceModel::get_all_models().await?;
+            let total = models
+                .iter()
+                .map(|model| model.networks().len())
+                .sum::<usize>();
+            let mut offset = 0;
+            for model in &models {
+                status
+                    .load_model_with_progress(model, |loaded, _| {
+                        if let Some(progress) = &options.load_all_models_progress {
+                            progress(offset + loaded, total);
+                        }
+                    })
+                    .await?;
+                offset += model.networks().len();
             }
-            Ok(Self { status })
-        } else {
-            Err(Error::GpuSupport)
         }
+        Ok(Self { status })
     }
 
-    fn can_support_gpu_feature() -> Result<bool> {
-        let supported_devices = SupportedDevices::create()?;
+    /// このビルドと実行環境で`execution_provider`が使えるかを確認し、使えない場合はその理由と共に
+    /// エラーを返す。
+    fn check_gpu_support(execution_provider: ExecutionProvider) -> Result<()> {
+        if ExecutionProvider::gpu() != Some(execution_provider) {
+            return Err(Error::GpuSupport(
+                GpuUnsupportedReason::NotBuiltWithExecutionProvider(execution_provider),
+            ));
+        }
 
-        cfg_if! {
-            if #[cfg(feature = "directml")]{
-                Ok(*supported_devices.dml())
-            } else{
-                Ok(*supported_devices.cuda())
-            }
+        if SupportedDevices::create()?.supports(execution_provider) {
+            return Ok(());
         }
+        Err(Error::GpuSupport(match execution_provider {
+            ExecutionProvider::DirectMl => GpuUnsupportedReason::DirectMlUnavailable,
+            _ => GpuUnsupportedReason::CudaUnavailable,
+        }))
+    }
+
+    pub fn is_gpu_mode(&self) -> bool {
+        self.status.is_gpu_mode()
+    }
+
+    pub fn execution_provider(&self) -> ExecutionProvider {
+        self.status.execution_provider()
     }
 
     pub async fn load_model(&self, model: &VoiceModel) -> Result<()> {
         self.status.load_model(model).await
     }
 
+    pub async fn load_model_with_progress(
+        &self,
+        model: &VoiceModel,
+        progress: impl Fn(usize, usize),
+    ) -> Result<()> {
+        self.status.load_model_with_progress(model, progress).await
+    }
+
+    pub async fn load_model_with_timeout(
+        &self,
+        model: &VoiceModel,
+        timeout: Duration,
+    ) -> Result<()> {
+        self.status
+            .load_model_with(model, |_, _| {}, Some(Instant::now() + timeout))
+            .await
+    }
+
     pub fn unload_model(&self, voice_model_id: &VoiceModelId) -> Result<()> {
         self.status.unload_model(voice_model_id)
     }
+
+    pub fn unload_all_models(&self) {
+        self.status.unload_all_models()
+    }
+
+    #[cfg(any(test, feature = "session-accounting"))]
+    pub fn loaded_session_count(&self) -> usize {
+        self.status.loaded_session_count()
+    }
     pub fn metas(&self) -> VoiceModelMeta {
         self.status.metas()
     }
@@ -70,6 +138,13 @@ impl InferenceCore {
 
         let (model_id, model_inner_id) = self.status.ids_for(style_id)?;
 
+        TensorCapture::record(|captured| {
+            captured.predict_duration.push(PredictDurationInputs {
+                phoneme_vector: phoneme_vector.to_owned(),
+                speaker_id: model_inner_id.raw_id().into(),
+            });
+        });
+
         let phoneme_vector_array = NdArray::new(ndarray::arr1(phoneme_vector));
         let speaker_id_array = NdArray::new(ndarray::arr1(&[model_inner_id.raw_id().into()]));
 
@@ -98,6 +173,34 @@ impl InferenceCore {
         start_accent_phrase_vector: &[i64],
         end_accent_phrase_vector: &[i64],
         style_id: StyleId,
+    ) -> Result<Vec<f32>> {
+        self.predict_intonation_nd(
+            length,
+            ndarray::arr1(vowel_phoneme_vector),
+            ndarray::arr1(consonant_phoneme_vector),
+            ndarray::arr1(start_accent_vector),
+            ndarray::arr1(end_accent_vector),
+            ndarray::arr1(start_accent_phrase_vector),
+            ndarray::arr1(end_accent_phrase_vector),
+            style_id,
+        )
+        .await
+    }
+
+    /// [`predict_intonation`]の、`ndarray`の配列を受け取る版。
+    ///
+    /// [`predict_intonation`]: Self::predict_intonation
+    #[allow(clippy::too_many_arguments)]
+    pub async fn predict_intonation_nd(
+        &self,
+        length: usize,
+        vowel_phoneme_vector: ndarray::Array1<i64>,
+        consonant_phoneme_vector: ndarray::Array1<i64>,
+        start_accent_vector: ndarray::Array1<i64>,
+        end_accent_vector: ndarray::Array1<i64>,
+        start_accent_phrase_vector: ndarray::Array1<i64>,
+        end_accent_phrase_vector: ndarray::Array1<i64>,
+        style_id: StyleId,
     ) -> Result<Vec<f32>> {
         if !self.status.validate_speaker_id(style_id) {
             return Err(Error::InvalidStyleId { style_id });
@@ -105,26 +208,32 @@ impl InferenceCore {
 
         let (model_id, model_inner_id) = self.status.ids_for(style_id)?;
 
+        TensorCapture::record(|captured| {
+            captured.predict_intonation.push(PredictIntonationInputs {
+                length: length as i64,
+                vowel_phoneme_vector: vowel_phoneme_vector.to_vec(),
+                consonant_phoneme_vector: consonant_phoneme_vector.to_vec(),
+                start_accent_vector: start_accent_vector.to_vec(),
+                end_accent_vector: end_accent_vector.to_vec(),
+                start_accent_phrase_vector: start_accent_phrase_vector.to_vec(),
+                end_accent_phrase_vector: end_accent_phrase_vector.to_vec(),
+                speaker_id: model_inner_id.raw_id().into(),
+            });
+        });
+
         let length_array = NdArray::new(ndarray::arr0(length as i64));
-        let vowel_phoneme_vector_array = NdArray::new(ndarray::arr1(vowel_phoneme_vector));
-        let consonant_phoneme_vector_array = NdArray::new(ndarray::arr1(consonant_phoneme_vector));
-        let start_accent_vector_array = NdArray::new(ndarray::arr1(start_accent_vector));
-        let end_accent_vector_array = NdArray::new(ndarray::arr1(end_accent_vector));
-        let start_accent_phrase_vector_array =
-            NdArray::new(ndarray::arr1(start_accent_phrase_vector));
-        let end_accent_phrase_vector_array = NdArray::new(ndarray::arr1(end_accent_phrase_vector));
         let speaker_id_array = NdArray::new(ndarray::arr1(&[model_inner_id.raw_id().into()]));
 
         self.status
             .predict_intonation_session_run(
                 &model_id,
                 length_array,
-                vowel_phoneme_vector_array,
-                consonant_phoneme_vector_array,
-                start_accent_vector_array,
-                end_accent_vector_array,
-                start_accent_phrase_vector_array,
-                end_accent_phrase_vector_array,
+                NdArray::new(vowel_phoneme_vector),
+                NdArray::new(consonant_phoneme_vector),
+                NdArray::new(start_accent_vector),
+                NdArray::new(end_accent_vector),
+                NdArray::new(start_accent_phrase_vector),
+                NdArray::new(end_accent_phrase_vector),
                 speaker_id_array,
             )
             .await
@@ -160,6 +269,16 @@ impl InferenceCore {
             padding_size,
         );
 
+        TensorCapture::record(|captured| {
+            captured.decode.push(DecodeInputs {
+                f0: f0_with_padding.clone(),
+                phoneme: phoneme_with_padding.clone(),
+                length: length_with_padding,
+                phoneme_size,
+                speaker_id: model_inner_id.raw_id().into(),
+            });
+        });
+
         let f0_array = NdArray::new(
             ndarray::arr1(&f0_with_padding)
                 .into_shape([length_with_padding, 1])
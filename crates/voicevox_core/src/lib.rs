@@ -1,4 +1,9 @@
 //! 無料で使える中品質なテキスト読み上げソフトウェア、VOICEVOXのコア。
+//!
+//! # エラー
+//!
+//! 失敗しうるAPIは[`Result`]を返し、そのエラーは[`Error`]である。[`Error`]の各バリアントが含む
+//! 型([`LoadModelError`]、[`KanaParseError`]など)も、全てクレートのルートから参照できる。
 
 #![deny(unsafe_code)]
 
@@ -13,10 +18,13 @@ mod metas;
 mod numerics;
 mod result;
 mod status;
+mod streaming_synthesizer;
+mod tensor_capture;
 mod user_dict;
 mod version;
 mod voice_model;
 mod voice_synthesizer;
+pub mod wav;
 
 use self::inference_core::*;
 
@@ -26,10 +34,15 @@ mod test_util;
 #[cfg(test)]
 use self::test_util::*;
 
-pub use self::engine::{AccentPhraseModel, AudioQueryModel, OpenJtalk};
+pub use self::engine::{
+    AccentPhraseDiff, AccentPhraseModel, Analyzer, AudioQueryModel, BiquadBand, BiquadKind,
+    FullContextLabelError, KanaParseError, MoraModel, OpenJtalk, OpenJtalkError, ProsodyParams,
+};
 pub use self::error::*;
 pub use self::metas::*;
-pub use self::result::*;
+pub use self::result::Result;
+pub use self::streaming_synthesizer::*;
+pub use self::tensor_capture::*;
 pub use self::voice_model::*;
 pub use devices::*;
 pub use manifest::*;
@@ -37,6 +50,10 @@ pub use user_dict::*;
 pub use version::*;
 pub use voice_synthesizer::*;
 
+/// [`Synthesizer::predict_intonation_nd`]で使われる[ndarray](https://docs.rs/ndarray)。
+#[cfg(feature = "ndarray")]
+pub use onnxruntime::ndarray;
+
 use derive_getters::*;
 use derive_new::new;
 use nanoid::nanoid;
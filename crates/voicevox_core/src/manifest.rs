@@ -6,6 +6,17 @@ use serde::{Deserialize, Serialize};
 
 use super::*;
 
+/// このバージョンのVOICEVOX COREが読み込める、VVMファイルのマニフェストのバージョン。
+const SUPPORTED_MANIFEST_VERSIONS: &[&str] = &["0.0.0"];
+
+/// このバージョンのVOICEVOX COREが読み込める音声モデル(VVMファイル)のバージョンの一覧を返す。
+///
+/// VVMファイルの`manifest.json`の`manifest_version`がこれらのいずれでもない場合、音声モデルの読み込みは
+/// [`LoadModelErrorKind::UnsupportedModelVersion`]で失敗する。
+pub fn supported_model_versions() -> &'static [&'static str] {
+    SUPPORTED_MANIFEST_VERSIONS
+}
+
 pub type RawManifestVersion = String;
 #[derive(Deserialize, Clone, Debug, PartialEq, new)]
 pub struct ManifestVersion(RawManifestVersion);
@@ -14,6 +25,10 @@ impl ManifestVersion {
     pub fn raw_manifest_version(&self) -> &RawManifestVersion {
         &self.0
     }
+
+    pub(crate) fn is_supported(&self) -> bool {
+        SUPPORTED_MANIFEST_VERSIONS.contains(&&*self.0)
+    }
 }
 
 impl Display for ManifestVersion {
@@ -45,8 +60,20 @@ pub struct Manifest {
     manifest_version: ManifestVersion,
     metas_filename: String,
     decode_filename: String,
-    predict_duration_filename: String,
-    predict_intonation_filename: String,
+    /// 音素長を推論するネットワークのファイル名。音声モデルがこのネットワークを含まない場合は`None`。
+    #[serde(default)]
+    predict_duration_filename: Option<String>,
+    /// 音高を推論するネットワークのファイル名。音声モデルがこのネットワークを含まない場合は`None`。
+    #[serde(default)]
+    predict_intonation_filename: Option<String>,
     #[serde(default)]
     style_id_to_model_inner_id: BTreeMap<StyleId, ModelInnerId>,
+    /// VVM内のファイル名から、その内容のSHA-256(16進数)への対応。[`VoiceModel::verify`]で使われる。
+    ///
+    /// [`VoiceModel::verify`]: crate::VoiceModel::verify
+    #[serde(default)]
+    sha256: BTreeMap<String, String>,
+    /// 音声モデルの利用規約が書かれた、VVM内のテキストファイル(UTF-8)のファイル名。
+    #[serde(default)]
+    terms_filename: Option<String>,
 }
@@ -1,4 +1,4 @@
-use std::fmt::Display;
+use std::{fmt::Display, num::ParseIntError, str::FromStr};
 
 use super::*;
 use derive_getters::Getters;
@@ -30,6 +30,15 @@ impl Display for StyleId {
     }
 }
 
+/// [`RawStyleId`]として解釈する。
+impl FromStr for StyleId {
+    type Err = ParseIntError;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        s.parse().map(Self)
+    }
+}
+
 /// [`StyleVersion`]の実体。
 ///
 /// [`StyleVersion`]: StyleVersion
@@ -65,6 +74,21 @@ pub struct SpeakerMeta {
     version: StyleVersion,
     /// 話者のUUID。
     speaker_uuid: String,
+    /// 話者の利用規約。
+    ///
+    /// `metas.json`で指定されていない場合、VVMファイルに含まれる利用規約([`VoiceModel::terms`])が
+    /// 入る。
+    ///
+    /// [`VoiceModel::terms`]: crate::VoiceModel::terms
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    terms: Option<String>,
+}
+
+impl SpeakerMeta {
+    /// 利用規約が指定されていなければ、`terms`で補う。
+    pub(crate) fn fill_terms(&mut self, terms: &str) {
+        self.terms.get_or_insert_with(|| terms.to_owned());
+    }
 }
 
 /// **スタイル**(_style_)のメタ情報。
@@ -74,4 +98,68 @@ pub struct StyleMeta {
     id: StyleId,
     /// スタイル名。
     name: String,
+    /// スタイルごとのAudioQueryの既定値。
+    ///
+    /// VVMファイル内の`metas.json`における、各スタイルの`default_scales`フィールドから読まれる。
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    default_scales: Option<StyleDefaultScales>,
+}
+
+/// スタイルごとの、[`Synthesizer::audio_query`]が返すAudioQueryの既定値。
+///
+/// 指定されていない値には、スタイルによらない既定値が使われる。
+///
+/// ```json
+/// { "id": 0, "name": "ノーマル", "default_scales": { "speed_scale": 1.1 } }
+/// ```
+///
+/// [`Synthesizer::audio_query`]: crate::Synthesizer::audio_query
+#[derive(Deserialize, Serialize, Getters, Clone, Copy, Default, Debug, PartialEq)]
+pub struct StyleDefaultScales {
+    /// 全体の話速。
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    speed_scale: Option<f32>,
+    /// 全体の音高。
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pitch_scale: Option<f32>,
+    /// 全体の抑揚。
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    intonation_scale: Option<f32>,
+    /// 全体の音量。
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    volume_scale: Option<f32>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[rstest]
+    #[case("0", Some(0))]
+    #[case("302", Some(302))]
+    #[case("-1", None)]
+    #[case("style", None)]
+    #[case("", None)]
+    fn style_id_from_str_works(#[case] s: &str, #[case] expected: Option<RawStyleId>) {
+        let style_id = s.parse::<StyleId>().ok();
+        assert_eq!(expected, style_id.map(StyleId::raw_id));
+        if let Some(style_id) = style_id {
+            assert_eq!(s, style_id.to_string());
+        }
+    }
+
+    #[rstest]
+    #[case(r#"{"id": 0, "name": "ノーマル"}"#, None)]
+    #[case(
+        r#"{"id": 0, "name": "ノーマル", "default_scales": {"speed_scale": 1.1}}"#,
+        Some(StyleDefaultScales { speed_scale: Some(1.1), ..Default::default() })
+    )]
+    fn style_meta_default_scales_works(
+        #[case] json: &str,
+        #[case] expected: Option<StyleDefaultScales>,
+    ) {
+        let style = serde_json::from_str::<StyleMeta>(json).unwrap();
+        assert_eq!(expected, *style.default_scales());
+    }
 }
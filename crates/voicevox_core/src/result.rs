@@ -1,2 +1,7 @@
 use super::*;
+
+/// VOICEVOX COREの失敗しうるAPIが返す`Result`。エラーは[`Error`]である。
+///
+/// 音声モデルの読み込み([`VoiceModel::from_path`]など)は、代わりに[`LoadModelError`]を返す。これは
+/// `?`で[`Error::LoadModel`]に変換できる。
 pub type Result<T> = std::result::Result<T, Error>;
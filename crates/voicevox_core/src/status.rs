@@ -7,14 +7,21 @@ use onnxruntime::{
     session::{NdArray, Session},
     GraphOptimizationLevel, LoggingLevel,
 };
+use std::future::Future;
+use std::ops::{Deref, DerefMut};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
 use std::sync::Arc;
+use std::time::{Duration, Instant};
 use std::{env, path::Path};
-use tracing::error;
+use tracing::{error, warn};
 
+mod denormals;
 mod model_file;
 
+use self::denormals::flush_denormals_during;
+
 cfg_if! {
-    if #[cfg(not(feature="directml"))]{
+    if #[cfg(all(feature = "cuda", not(feature = "directml")))]{
         use onnxruntime::CudaProviderOptions;
     }
 }
@@ -24,12 +31,23 @@ pub struct Status {
     loaded_models: std::sync::Mutex<LoadedModels>,
     light_session_options: SessionOptions, // 軽いモデルはこちらを使う
     heavy_session_options: SessionOptions, // 重いモデルはこちらを使う
+    /// GPUでのセッションの作成に失敗したときに、CPUで作成しなおすかどうか。
+    fallback_to_cpu: bool,
+    /// GPUからCPUへのフォールバックが起きたかどうか。
+    fell_back_to_cpu: AtomicBool,
+    /// 推論中に非正規化数を0として扱うかどうか。
+    flush_denormals: bool,
+    /// 推論が失敗したときに再試行する回数。
+    inference_retries: u8,
+    /// 破棄されていない`Session`の数。
+    live_sessions: Arc<AtomicUsize>,
 }
 
-#[derive(new, Getters)]
+#[derive(new, Getters, Clone, Copy)]
 struct SessionOptions {
-    cpu_num_threads: u16,
-    use_gpu: bool,
+    intra_op_num_threads: u16,
+    inter_op_num_threads: u16,
+    execution_provider: ExecutionProvider,
 }
 
 #[derive(thiserror::Error, Debug)]
@@ -52,37 +70,157 @@ static ENVIRONMENT: Lazy<Environment> = Lazy::new(|| {
 });
 
 impl Status {
-    pub fn new(use_gpu: bool, cpu_num_threads: u16) -> Self {
+    /// `intra_op_num_threads`と`inter_op_num_threads`は、それぞれONNX Runtimeの演算子内・演算子間の
+    /// 並列化に使うスレッド数。0の場合はONNX Runtimeの既定値となる。
+    pub fn new(
+        execution_provider: ExecutionProvider,
+        intra_op_num_threads: u16,
+        inter_op_num_threads: u16,
+    ) -> Self {
         Self {
             loaded_models: Default::default(),
-            light_session_options: SessionOptions::new(cpu_num_threads, false),
-            heavy_session_options: SessionOptions::new(cpu_num_threads, use_gpu),
+            light_session_options: SessionOptions::new(
+                intra_op_num_threads,
+                inter_op_num_threads,
+                ExecutionProvider::Cpu,
+            ),
+            heavy_session_options: SessionOptions::new(
+                intra_op_num_threads,
+                inter_op_num_threads,
+                execution_provider,
+            ),
+            fallback_to_cpu: false,
+            fell_back_to_cpu: AtomicBool::new(false),
+            flush_denormals: false,
+            inference_retries: 0,
+            live_sessions: Default::default(),
         }
     }
 
+    /// GPUでのセッションの作成に失敗したときに、警告を出した上でCPUで作成しなおすようにする。
+    ///
+    /// 一度フォールバックが起きると、以降に読み込むモデルは全てCPUで実行される。
+    pub fn with_cpu_fallback(mut self, fallback_to_cpu: bool) -> Self {
+        self.fallback_to_cpu = fallback_to_cpu;
+        self
+    }
+
+    /// 推論を実行するスレッドで、非正規化数を0として扱うようにする。
+    pub fn with_flush_denormals(mut self, flush_denormals: bool) -> Self {
+        self.flush_denormals = flush_denormals;
+        self
+    }
+
+    /// 推論が失敗したときに、`inference_retries`回まで再試行するようにする。
+    pub fn with_inference_retries(mut self, inference_retries: u8) -> Self {
+        self.inference_retries = inference_retries;
+        self
+    }
+
+    /// 重いモデルをGPUで実行するかどうか。CPUへのフォールバックが起きた後は`false`となる。
+    pub fn is_gpu_mode(&self) -> bool {
+        self.execution_provider().is_gpu()
+    }
+
+    /// 重いモデルの実行に使うExecution Provider。CPUへのフォールバックが起きた後は
+    /// [`ExecutionProvider::Cpu`]となる。
+    pub fn execution_provider(&self) -> ExecutionProvider {
+        if self.fell_back_to_cpu.load(Ordering::SeqCst) {
+            ExecutionProvider::Cpu
+        } else {
+            self.heavy_session_options.execution_provider
+        }
+    }
+
+    /// 1つの音声モデルに含まれるネットワークの最大数。
+    pub const NUM_NETWORKS_PER_MODEL: usize = 3;
+
     pub async fn load_model(&self, model: &VoiceModel) -> Result<()> {
+        self.load_model_with_progress(model, |_, _| {}).await
+    }
+
+    /// 音声モデルを読み込み、ネットワークを1つ読み込むごとに`progress`に
+    /// `(読み込み済みのネットワーク数, 全体のネットワーク数)`を渡す。
+    ///
+    /// `progress`に最後の`(N, N)`が渡されるのは読み込みが完了した後である。
+    pub async fn load_model_with_progress(
+        &self,
+        model: &VoiceModel,
+        progress: impl Fn(usize, usize),
+    ) -> Result<()> {
+        self.load_model_with(model, progress, None).await
+    }
+
+    /// 音声モデルを読み込む。
+    ///
+    /// ZIPの読み込みは非同期に行い、ネットワークの復号と`Session`の作成は
+    /// [`tokio::task::spawn_blocking`]で行うため、読み込みの間も非同期ランタイムをブロックしない。
+    ///
+    /// `deadline`を過ぎていた場合、ZIPの読み込みの後とネットワークの読み込みの合間で中断し、
+    /// [`LoadModelErrorKind::Timeout`]を返す。中断した場合、音声モデルは読み込まれていない状態に
+    /// なる。
+    pub async fn load_model_with(
+        &self,
+        model: &VoiceModel,
+        progress: impl Fn(usize, usize),
+        deadline: Option<Instant>,
+    ) -> Result<()> {
+        let total = model.networks().len();
+        let mut loaded = 0;
+        let mut report_progress = || {
+            loaded += 1;
+            progress(loaded, total);
+        };
+
+        let check_deadline = || match deadline {
+            Some(deadline) if Instant::now() > deadline => Err(LoadModelError {
+                path: model.path().clone(),
+                context: LoadModelErrorKind::Timeout,
+                source: None,
+            }),
+            _ => Ok(()),
+        };
+
         self.loaded_models
             .lock()
             .unwrap()
             .ensure_acceptable(model)?;
 
         let models = model.read_inference_models().await?;
-
-        let predict_duration_session = self.new_session(
-            models.predict_duration_model(),
-            &self.light_session_options,
-            model.path(),
-        )?;
-        let predict_intonation_session = self.new_session(
-            models.predict_intonation_model(),
-            &self.light_session_options,
-            model.path(),
-        )?;
-        let decode_model = self.new_session(
-            models.decode_model(),
-            &self.heavy_session_options,
-            model.path(),
-        )?;
+        check_deadline()?;
+
+        let mut predict_duration_session = None;
+        if let Some(predict_duration_model) = models.predict_duration_model() {
+            predict_duration_session = Some(
+                self.new_session(
+                    predict_duration_model.clone(),
+                    self.light_session_options,
+                    model.path(),
+                )
+                .await?,
+            );
+            report_progress();
+            check_deadline()?;
+        }
+        let mut predict_intonation_session = None;
+        if let Some(predict_intonation_model) = models.predict_intonation_model() {
+            predict_intonation_session = Some(
+                self.new_session(
+                    predict_intonation_model.clone(),
+                    self.light_session_options,
+                    model.path(),
+                )
+                .await?,
+            );
+            report_progress();
+            check_deadline()?;
+        }
+        let decode_model = self
+            .new_heavy_session(|session_options| {
+                self.new_session(models.decode_model().clone(), session_options, model.path())
+            })
+            .await?;
+        check_deadline()?;
 
         self.loaded_models.lock().unwrap().insert(
             model,
@@ -90,6 +228,7 @@ impl Status {
             predict_intonation_session,
             decode_model,
         )?;
+        report_progress();
         Ok(())
     }
 
@@ -97,6 +236,19 @@ impl Status {
         self.loaded_models.lock().unwrap().remove(voice_model_id)
     }
 
+    pub fn unload_all_models(&self) {
+        self.loaded_models.lock().unwrap().clear();
+    }
+
+    /// 破棄されていない`Session`の数を返す。
+    ///
+    /// 推論中のものも含むため、読み込み中の音声モデルの数の[`Self::NUM_NETWORKS_PER_MODEL`]倍より
+    /// 一時的に大きくなることがある。
+    #[cfg(any(test, feature = "session-accounting"))]
+    pub fn loaded_session_count(&self) -> usize {
+        self.live_sessions.load(Ordering::SeqCst)
+    }
+
     pub fn metas(&self) -> VoiceModelMeta {
         self.loaded_models.lock().unwrap().metas()
     }
@@ -116,45 +268,80 @@ impl Status {
         self.loaded_models.lock().unwrap().contains_style(style_id)
     }
 
-    fn new_session(
+    /// 重いモデル用のセッションを作成する。
+    ///
+    /// GPUでの作成に失敗した場合、CPUへのフォールバックが有効であればその理由を警告として出力し、
+    /// CPUで作成しなおす。
+    async fn new_heavy_session<T, F: Future<Output = LoadModelResult<T>>>(
         &self,
-        model: &[u8],
-        session_options: &SessionOptions,
+        new_session: impl Fn(SessionOptions) -> F,
+    ) -> LoadModelResult<T> {
+        let cpu_session_options = SessionOptions {
+            execution_provider: ExecutionProvider::Cpu,
+            ..self.heavy_session_options
+        };
+
+        if !self.is_gpu_mode() {
+            return new_session(cpu_session_options).await;
+        }
+        match new_session(self.heavy_session_options).await {
+            Err(err) if self.fallback_to_cpu => {
+                warn!("GPUでのセッションの作成に失敗したため、CPUにフォールバックします: {err}");
+                self.fell_back_to_cpu.store(true, Ordering::SeqCst);
+                new_session(cpu_session_options).await
+            }
+            result => result,
+        }
+    }
+
+    /// `Session`を作成する。時間のかかる処理であるため、[`tokio::task::spawn_blocking`]で行う。
+    async fn new_session(
+        &self,
+        model: Arc<[u8]>,
+        session_options: SessionOptions,
         path: impl AsRef<Path>,
-    ) -> LoadModelResult<Session<'static>> {
-        self.new_session_from_bytes(|| model_file::decrypt(model), session_options)
-            .map_err(|source| LoadModelError {
-                path: path.as_ref().to_owned(),
-                context: LoadModelErrorKind::InvalidModelData,
-                source: Some(source),
-            })
+    ) -> LoadModelResult<CountedSession> {
+        let live_sessions = self.live_sessions.clone();
+
+        tokio::task::spawn_blocking(move || {
+            Self::new_session_from_bytes(|| model_file::decrypt(&model), &session_options)
+                .map(|session| AssertSend::from(CountedSession::new(session, &live_sessions)))
+        })
+        .await
+        .unwrap()
+        .map(AssertSend::into_inner)
+        .map_err(|source| LoadModelError {
+            path: path.as_ref().to_owned(),
+            context: LoadModelErrorKind::InvalidModelData,
+            source: Some(source),
+        })
     }
 
     fn new_session_from_bytes(
-        &self,
         model_bytes: impl FnOnce() -> std::result::Result<Vec<u8>, DecryptModelError>,
         session_options: &SessionOptions,
     ) -> anyhow::Result<Session<'static>> {
         let session_builder = ENVIRONMENT
             .new_session_builder()?
             .with_optimization_level(GraphOptimizationLevel::Basic)?
-            .with_intra_op_num_threads(*session_options.cpu_num_threads() as i32)?
-            .with_inter_op_num_threads(*session_options.cpu_num_threads() as i32)?;
-
-        let session_builder = if *session_options.use_gpu() {
-            cfg_if! {
-                if #[cfg(feature = "directml")]{
-                    session_builder
-                        .with_disable_mem_pattern()?
-                        .with_execution_mode(onnxruntime::ExecutionMode::ORT_SEQUENTIAL)?
-                        .with_append_execution_provider_directml(0)?
-                } else {
-                    let options = CudaProviderOptions::default();
-                    session_builder.with_append_execution_provider_cuda(options)?
-                }
+            .with_intra_op_num_threads(*session_options.intra_op_num_threads() as i32)?
+            .with_inter_op_num_threads(*session_options.inter_op_num_threads() as i32)?;
+
+        let session_builder = match session_options.execution_provider {
+            ExecutionProvider::Cpu => session_builder,
+            #[cfg(feature = "directml")]
+            ExecutionProvider::DirectMl => session_builder
+                .with_disable_mem_pattern()?
+                .with_execution_mode(onnxruntime::ExecutionMode::ORT_SEQUENTIAL)?
+                .with_append_execution_provider_directml(0)?,
+            #[cfg(all(feature = "cuda", not(feature = "directml")))]
+            ExecutionProvider::Cuda => {
+                let options = CudaProviderOptions::default();
+                session_builder.with_append_execution_provider_cuda(options)?
+            }
+            execution_provider => {
+                unreachable!("{execution_provider}は`InferenceCore`の初期化時に弾かれているはず",)
             }
-        } else {
-            session_builder
         };
 
         Ok(session_builder.with_model_from_memory(model_bytes()?)?)
@@ -173,20 +360,29 @@ impl Status {
         mut phoneme_vector_array: NdArray<i64, Ix1>,
         mut speaker_id_array: NdArray<i64, Ix1>,
     ) -> Result<Vec<f32>> {
-        let predict_duration = self.loaded_models.lock().unwrap().get(
-            model_id,
-            |SessionSet {
-                 predict_duration, ..
-             }| predict_duration,
-        );
+        let predict_duration = self
+            .loaded_models
+            .lock()
+            .unwrap()
+            .get(model_id, Network::PredictDuration)?;
+
+        let flush_denormals = self.flush_denormals;
+        let retries = self.inference_retries;
 
         tokio::task::spawn_blocking(move || {
             let mut predict_duration = predict_duration.lock().unwrap();
 
-            let output_tensors = predict_duration
-                .run(vec![&mut phoneme_vector_array, &mut speaker_id_array])
+            run_with_retries(retries, Network::PredictDuration, || {
+                // SAFETY: `run`はONNX Runtimeの推論を呼び出すのみである。
+                #[allow(unsafe_code)]
+                let output_tensors = unsafe {
+                    flush_denormals_during(flush_denormals, || {
+                        predict_duration.run(vec![&mut phoneme_vector_array, &mut speaker_id_array])
+                    })
+                }
                 .map_err(|_| Error::InferenceFailed)?;
-            Ok(output_tensors[0].as_slice().unwrap().to_owned())
+                Ok(output_tensors[0].as_slice().unwrap().to_owned())
+            })
         })
         .await
         .unwrap()
@@ -208,29 +404,38 @@ impl Status {
         mut end_accent_phrase_vector_array: NdArray<i64, Ix1>,
         mut speaker_id_array: NdArray<i64, Ix1>,
     ) -> Result<Vec<f32>> {
-        let predict_intonation = self.loaded_models.lock().unwrap().get(
-            model_id,
-            |SessionSet {
-                 predict_intonation, ..
-             }| predict_intonation,
-        );
+        let predict_intonation = self
+            .loaded_models
+            .lock()
+            .unwrap()
+            .get(model_id, Network::PredictIntonation)?;
+
+        let flush_denormals = self.flush_denormals;
+        let retries = self.inference_retries;
 
         tokio::task::spawn_blocking(move || {
             let mut predict_intonation = predict_intonation.lock().unwrap();
 
-            let output_tensors = predict_intonation
-                .run(vec![
-                    &mut length_array,
-                    &mut vowel_phoneme_vector_array,
-                    &mut consonant_phoneme_vector_array,
-                    &mut start_accent_vector_array,
-                    &mut end_accent_vector_array,
-                    &mut start_accent_phrase_vector_array,
-                    &mut end_accent_phrase_vector_array,
-                    &mut speaker_id_array,
-                ])
+            run_with_retries(retries, Network::PredictIntonation, || {
+                // SAFETY: `run`はONNX Runtimeの推論を呼び出すのみである。
+                #[allow(unsafe_code)]
+                let output_tensors = unsafe {
+                    flush_denormals_during(flush_denormals, || {
+                        predict_intonation.run(vec![
+                            &mut length_array,
+                            &mut vowel_phoneme_vector_array,
+                            &mut consonant_phoneme_vector_array,
+                            &mut start_accent_vector_array,
+                            &mut end_accent_vector_array,
+                            &mut start_accent_phrase_vector_array,
+                            &mut end_accent_phrase_vector_array,
+                            &mut speaker_id_array,
+                        ])
+                    })
+                }
                 .map_err(|_| Error::InferenceFailed)?;
-            Ok(output_tensors[0].as_slice().unwrap().to_owned())
+                Ok(output_tensors[0].as_slice().unwrap().to_owned())
+            })
         })
         .await
         .unwrap()
@@ -250,25 +455,63 @@ impl Status {
             .loaded_models
             .lock()
             .unwrap()
-            .get(model_id, |SessionSet { decode, .. }| decode);
+            .get(model_id, Network::Decode)?;
+
+        let flush_denormals = self.flush_denormals;
+        let retries = self.inference_retries;
 
         tokio::task::spawn_blocking(move || {
             let mut decode = decode.lock().unwrap();
 
-            let output_tensors = decode
-                .run(vec![
-                    &mut f0_array,
-                    &mut phoneme_array,
-                    &mut speaker_id_array,
-                ])
+            run_with_retries(retries, Network::Decode, || {
+                // SAFETY: `run`はONNX Runtimeの推論を呼び出すのみである。
+                #[allow(unsafe_code)]
+                let output_tensors = unsafe {
+                    flush_denormals_during(flush_denormals, || {
+                        decode.run(vec![
+                            &mut f0_array,
+                            &mut phoneme_array,
+                            &mut speaker_id_array,
+                        ])
+                    })
+                }
                 .map_err(|_| Error::InferenceFailed)?;
-            Ok(output_tensors[0].as_slice().unwrap().to_owned())
+                Ok(output_tensors[0].as_slice().unwrap().to_owned())
+            })
         })
         .await
         .unwrap()
     }
 }
 
+/// 最初の再試行の前に待つ時間。以降は再試行するごとに倍にする。
+const INITIAL_RETRY_BACKOFF: Duration = Duration::from_millis(10);
+
+/// `run`を、[`Error::InferenceFailed`]で失敗した場合に最大`retries`回まで再試行する。
+///
+/// 推論用のスレッドで呼ばれることを前提に、待機はスレッドをブロックして行う。
+fn run_with_retries<T>(
+    retries: u8,
+    network: Network,
+    mut run: impl FnMut() -> Result<T>,
+) -> Result<T> {
+    let mut backoff = INITIAL_RETRY_BACKOFF;
+    for attempt in 1..=retries {
+        match run() {
+            Err(Error::InferenceFailed) => {
+                warn!(
+                    "`{network}`の推論に失敗しました。{backoff:?}後に再試行します \
+                     ({attempt}/{retries}回目)",
+                );
+                std::thread::sleep(backoff);
+                backoff *= 2;
+            }
+            result => return result,
+        }
+    }
+    run()
+}
+
 /// 読み込んだモデルの`Session`とそのメタ情報を保有し、追加/削除/取得の操作を提供する。
 ///
 /// この構造体のメソッドは、すべて一瞬で完了すべきである。
@@ -314,15 +557,32 @@ impl LoadedModels {
         Ok((model_id.clone(), model_inner_id))
     }
 
+    /// # Errors
+    ///
+    /// 音声モデルが`network`を含んでいないとき、[`Error::MissingNetwork`]を返す。
+    ///
     /// # Panics
     ///
     /// `self`が`model_id`を含んでいないとき、パニックする。
     fn get(
         &self,
         model_id: &VoiceModelId,
-        which: fn(&SessionSet) -> &Arc<std::sync::Mutex<AssertSend<Session<'static>>>>,
-    ) -> Arc<std::sync::Mutex<AssertSend<Session<'static>>>> {
-        which(&self.0[model_id].session_set).clone()
+        network: Network,
+    ) -> Result<Arc<std::sync::Mutex<AssertSend<CountedSession>>>> {
+        let SessionSet {
+            predict_duration,
+            predict_intonation,
+            decode,
+        } = &self.0[model_id].session_set;
+        let session = match network {
+            Network::PredictDuration => predict_duration.as_ref(),
+            Network::PredictIntonation => predict_intonation.as_ref(),
+            Network::Decode => Some(decode),
+        };
+        session.cloned().ok_or_else(|| Error::MissingNetwork {
+            model_id: model_id.clone(),
+            network,
+        })
     }
 
     fn contains_voice_model(&self, model_id: &VoiceModelId) -> bool {
@@ -366,9 +626,9 @@ impl LoadedModels {
     fn insert(
         &mut self,
         model: &VoiceModel,
-        predict_duration: Session<'static>,
-        predict_intonation: Session<'static>,
-        decode: Session<'static>,
+        predict_duration: Option<CountedSession>,
+        predict_intonation: Option<CountedSession>,
+        decode: CountedSession,
     ) -> Result<()> {
         self.ensure_acceptable(model)?;
 
@@ -378,8 +638,10 @@ impl LoadedModels {
                 model_inner_ids: model.model_inner_ids(),
                 metas: model.metas().clone(),
                 session_set: SessionSet {
-                    predict_duration: Arc::new(std::sync::Mutex::new(predict_duration.into())),
-                    predict_intonation: Arc::new(std::sync::Mutex::new(predict_intonation.into())),
+                    predict_duration: predict_duration
+                        .map(|session| Arc::new(std::sync::Mutex::new(session.into()))),
+                    predict_intonation: predict_intonation
+                        .map(|session| Arc::new(std::sync::Mutex::new(session.into()))),
                     decode: Arc::new(std::sync::Mutex::new(decode.into())),
                 },
             },
@@ -397,6 +659,10 @@ impl LoadedModels {
         Ok(())
     }
 
+    fn clear(&mut self) {
+        self.0.clear();
+    }
+
     fn styles(&self) -> impl Iterator<Item = &StyleMeta> {
         self.0
             .values()
@@ -405,10 +671,62 @@ impl LoadedModels {
     }
 }
 
+/// 音声モデルのネットワークごとの`Session`。音声モデルが含まないネットワークは`None`となる。
 struct SessionSet {
-    predict_duration: Arc<std::sync::Mutex<AssertSend<Session<'static>>>>,
-    predict_intonation: Arc<std::sync::Mutex<AssertSend<Session<'static>>>>,
-    decode: Arc<std::sync::Mutex<AssertSend<Session<'static>>>>,
+    predict_duration: Option<Arc<std::sync::Mutex<AssertSend<CountedSession>>>>,
+    predict_intonation: Option<Arc<std::sync::Mutex<AssertSend<CountedSession>>>>,
+    decode: Arc<std::sync::Mutex<AssertSend<CountedSession>>>,
+}
+
+/// `Status::live_sessions`で数えられる`Session`。
+struct CountedSession {
+    session: Session<'static>,
+    live_sessions: Arc<AtomicUsize>,
+}
+
+impl CountedSession {
+    fn new(session: Session<'static>, live_sessions: &Arc<AtomicUsize>) -> Self {
+        live_sessions.fetch_add(1, Ordering::SeqCst);
+        Self {
+            session,
+            live_sessions: live_sessions.clone(),
+        }
+    }
+}
+
+impl Deref for CountedSession {
+    type Target = Session<'static>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.session
+    }
+}
+
+impl DerefMut for CountedSession {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.session
+    }
+}
+
+impl Drop for CountedSession {
+    fn drop(&mut self) {
+        self.live_sessions.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+#[cfg(feature = "session-accounting")]
+impl Drop for Status {
+    fn drop(&mut self) {
+        // 読み込まれている音声モデルを先に破棄し、それでも残っている`Session`があれば報告する
+        *self
+            .loaded_models
+            .get_mut()
+            .unwrap_or_else(|e| e.into_inner()) = Default::default();
+        let remaining = self.live_sessions.load(Ordering::SeqCst);
+        if remaining > 0 {
+            warn!("破棄されていないセッションが{remaining}個残っています");
+        }
+    }
 }
 
 // FIXME: 以下のことをちゃんと確認した後、onnxruntime-rs側で`Session`が`Send`であると宣言する。
@@ -419,16 +737,22 @@ use self::assert_send::AssertSend;
 mod assert_send {
     use std::ops::{Deref, DerefMut};
 
-    use onnxruntime::session::Session;
+    use super::CountedSession;
 
     pub(super) struct AssertSend<T>(T);
 
-    impl From<Session<'static>> for AssertSend<Session<'static>> {
-        fn from(session: Session<'static>) -> Self {
+    impl From<CountedSession> for AssertSend<CountedSession> {
+        fn from(session: CountedSession) -> Self {
             Self(session)
         }
     }
 
+    impl<T> AssertSend<T> {
+        pub(super) fn into_inner(self) -> T {
+            self.0
+        }
+    }
+
     impl<T> Deref for AssertSend<T> {
         type Target = T;
 
@@ -456,41 +780,138 @@ mod tests {
     use pretty_assertions::assert_eq;
 
     #[rstest]
-    #[case(true, 0)]
-    #[case(true, 1)]
-    #[case(true, 8)]
-    #[case(false, 2)]
-    #[case(false, 4)]
-    #[case(false, 8)]
-    #[case(false, 0)]
-    fn status_new_works(#[case] use_gpu: bool, #[case] cpu_num_threads: u16) {
-        let status = Status::new(use_gpu, cpu_num_threads);
-        assert_eq!(false, status.light_session_options.use_gpu);
-        assert_eq!(use_gpu, status.heavy_session_options.use_gpu);
+    #[case(ExecutionProvider::Cuda, 0, 0)]
+    #[case(ExecutionProvider::Cuda, 1, 1)]
+    #[case(ExecutionProvider::DirectMl, 8, 8)]
+    #[case(ExecutionProvider::Cpu, 2, 2)]
+    #[case(ExecutionProvider::Cpu, 4, 1)]
+    #[case(ExecutionProvider::Cpu, 8, 2)]
+    #[case(ExecutionProvider::Cpu, 0, 0)]
+    fn status_new_works(
+        #[case] execution_provider: ExecutionProvider,
+        #[case] intra_op_num_threads: u16,
+        #[case] inter_op_num_threads: u16,
+    ) {
+        let status = Status::new(
+            execution_provider,
+            intra_op_num_threads,
+            inter_op_num_threads,
+        );
         assert_eq!(
-            cpu_num_threads,
-            status.light_session_options.cpu_num_threads
+            ExecutionProvider::Cpu,
+            status.light_session_options.execution_provider,
         );
         assert_eq!(
-            cpu_num_threads,
-            status.heavy_session_options.cpu_num_threads
+            execution_provider,
+            status.heavy_session_options.execution_provider,
         );
+        for session_options in [status.light_session_options, status.heavy_session_options] {
+            assert_eq!(intra_op_num_threads, session_options.intra_op_num_threads);
+            assert_eq!(inter_op_num_threads, session_options.inter_op_num_threads);
+        }
         assert!(status.loaded_models.lock().unwrap().0.is_empty());
     }
 
+    #[rstest]
+    #[case(false, true, true)]
+    #[case(true, true, true)]
+    #[case(false, false, false)]
+    #[tokio::test]
+    async fn new_heavy_session_falls_back_to_cpu(
+        #[case] gpu_available: bool,
+        #[case] fallback_to_cpu: bool,
+        #[case] expected_ok: bool,
+    ) {
+        let status = Status::new(ExecutionProvider::Cuda, 0, 0).with_cpu_fallback(fallback_to_cpu);
+
+        // GPUが使えない環境を模擬する
+        let result = status
+            .new_heavy_session(|session_options| async move {
+                if session_options.execution_provider().is_gpu() && !gpu_available {
+                    return Err(LoadModelError {
+                        path: "".into(),
+                        context: LoadModelErrorKind::InvalidModelData,
+                        source: None,
+                    });
+                }
+                Ok(session_options.execution_provider().is_gpu())
+            })
+            .await;
+
+        assert_eq!(expected_ok, result.is_ok());
+        assert_eq!(
+            gpu_available || !fallback_to_cpu,
+            status.is_gpu_mode(),
+            "フォールバックが起きたときのみCPUモードになる",
+        );
+        assert_eq!(
+            if status.is_gpu_mode() {
+                ExecutionProvider::Cuda
+            } else {
+                ExecutionProvider::Cpu
+            },
+            status.execution_provider(),
+        );
+        if let Ok(used_gpu) = result {
+            assert_eq!(gpu_available, used_gpu);
+        }
+    }
+
     #[rstest]
     #[tokio::test]
     async fn status_load_model_works() {
-        let status = Status::new(false, 0);
+        let status = Status::new(ExecutionProvider::Cpu, 0, 0);
         let result = status.load_model(&open_default_vvm_file().await).await;
         assert_debug_fmt_eq!(Ok(()), result);
         assert_eq!(1, status.loaded_models.lock().unwrap().0.len());
     }
 
+    #[rstest]
+    #[tokio::test]
+    async fn status_load_model_reports_already_loaded_model() {
+        let status = Status::new(ExecutionProvider::Cpu, 0, 0);
+        let vvm = open_default_vvm_file().await;
+        status.load_model(&vvm).await.unwrap();
+
+        let Err(Error::LoadModel(err)) = status.load_model(&vvm).await else {
+            panic!("should fail with `Error::LoadModel`");
+        };
+        assert_eq!(Some(vvm.id()), err.context().model_id());
+        assert_eq!(None, err.context().style_id());
+        assert_eq!(vvm.path(), err.path());
+    }
+
+    #[rstest]
+    #[tokio::test(flavor = "current_thread")]
+    async fn status_load_model_does_not_block_runtime() {
+        let status = Status::new(ExecutionProvider::Cpu, 0, 0);
+        let vvm = open_default_vvm_file().await;
+
+        let ticks = Arc::new(AtomicUsize::new(0));
+        let ticker = tokio::spawn({
+            let ticks = ticks.clone();
+            async move {
+                loop {
+                    ticks.fetch_add(1, Ordering::SeqCst);
+                    tokio::task::yield_now().await;
+                }
+            }
+        });
+
+        let result = status.load_model(&vvm).await;
+        ticker.abort();
+
+        assert_debug_fmt_eq!(Ok(()), result);
+        assert!(
+            ticks.load(Ordering::SeqCst) > Status::NUM_NETWORKS_PER_MODEL,
+            "other tasks should make progress while loading a model",
+        );
+    }
+
     #[rstest]
     #[tokio::test]
     async fn status_is_model_loaded_works() {
-        let status = Status::new(false, 0);
+        let status = Status::new(ExecutionProvider::Cpu, 0, 0);
         let vvm = open_default_vvm_file().await;
         assert!(
             !status.is_loaded_model(vvm.id()),
@@ -500,4 +921,40 @@ mod tests {
         assert_debug_fmt_eq!(Ok(()), result);
         assert!(status.is_loaded_model(vvm.id()), "model should be loaded");
     }
+
+    #[rstest]
+    #[case::succeeds_first(0, 0, true, 1)]
+    #[case::succeeds_after_retries(2, 2, true, 3)]
+    #[case::runs_out_of_retries(1, 2, false, 2)]
+    fn run_with_retries_works(
+        #[case] retries: u8,
+        #[case] failures: usize,
+        #[case] expected_ok: bool,
+        #[case] expected_attempts: usize,
+    ) {
+        let mut attempts = 0;
+        let result = run_with_retries(retries, Network::Decode, || {
+            attempts += 1;
+            if attempts > failures {
+                Ok(())
+            } else {
+                Err(Error::InferenceFailed)
+            }
+        });
+        assert_eq!(expected_ok, result.is_ok());
+        assert_eq!(expected_attempts, attempts);
+    }
+
+    #[rstest]
+    fn run_with_retries_does_not_retry_other_errors() {
+        let mut attempts = 0;
+        let result = run_with_retries::<()>(3, Network::Decode, || {
+            attempts += 1;
+            Err(Error::InvalidStyleId {
+                style_id: StyleId::new(0),
+            })
+        });
+        assert!(matches!(result, Err(Error::InvalidStyleId { .. })));
+        assert_eq!(1, attempts);
+    }
 }
@@ -0,0 +1,139 @@
+//! 非正規化数のフラッシュ(FTZ/DAZ)。
+//!
+//! 非正規化数を含む浮動小数点演算は、一部のCPUで極端に遅くなる。推論中だけ非正規化数を0として扱う
+//! ことで、最悪時のレイテンシを抑える。
+//!
+//! Rust(LLVM)は既定の浮動小数点環境を前提にコードを生成するため、それ以外の環境でRustのコードが
+//! 浮動小数点演算を行うことは未定義動作となる。そのため設定の変更はONNX Runtimeの推論の呼び出しの
+//! 前後のみに留める。
+
+use cfg_if::cfg_if;
+
+cfg_if! {
+    if #[cfg(any(target_arch = "x86", target_arch = "x86_64"))] {
+        use std::arch::asm;
+
+        type ControlRegister = u32;
+
+        /// MXCSRのFTZ(Flush To Zero)とDAZ(Denormals Are Zero)のビット。
+        const FLUSH_BITS: ControlRegister = 0x8040;
+
+        #[allow(unsafe_code)]
+        fn get_control_register() -> ControlRegister {
+            let mut mxcsr: ControlRegister = 0;
+            // SAFETY: MXCSRを、有効な`u32`である`mxcsr`に書き出すだけである。
+            unsafe {
+                asm!("stmxcsr [{}]", in(reg) &mut mxcsr, options(nostack, preserves_flags));
+            }
+            mxcsr
+        }
+
+        /// # Safety
+        ///
+        /// 既定と異なる値を設定した場合、元に戻すまでの間にRustのコードで浮動小数点演算を行っては
+        /// ならない。
+        #[allow(unsafe_code)]
+        unsafe fn set_control_register(mxcsr: ControlRegister) {
+            // SAFETY: 読み込むのは有効な`u32`である`mxcsr`のみである。浮動小数点環境についての条件は
+            // 呼び出し元が満たす。
+            unsafe {
+                asm!(
+                    "ldmxcsr [{}]",
+                    in(reg) &mxcsr,
+                    options(nostack, readonly, preserves_flags),
+                );
+            }
+        }
+    } else if #[cfg(target_arch = "aarch64")] {
+        use std::arch::asm;
+
+        type ControlRegister = u64;
+
+        /// FPCRのFZ(Flush-to-zero)のビット。
+        const FLUSH_BITS: ControlRegister = 1 << 24;
+
+        #[allow(unsafe_code)]
+        fn get_control_register() -> ControlRegister {
+            let fpcr: ControlRegister;
+            // SAFETY: FPCRを読むだけである。
+            unsafe {
+                asm!("mrs {}, fpcr", out(reg) fpcr, options(nomem, nostack, preserves_flags));
+            }
+            fpcr
+        }
+
+        /// # Safety
+        ///
+        /// 既定と異なる値を設定した場合、元に戻すまでの間にRustのコードで浮動小数点演算を行っては
+        /// ならない。
+        #[allow(unsafe_code)]
+        unsafe fn set_control_register(fpcr: ControlRegister) {
+            // SAFETY: メモリには触れない。浮動小数点環境についての条件は呼び出し元が満たす。
+            unsafe {
+                asm!("msr fpcr, {}", in(reg) fpcr, options(nomem, nostack, preserves_flags));
+            }
+        }
+    }
+}
+
+/// `enabled`であれば、現在のスレッドで非正規化数を0として扱うようにした上で`run`を実行する。
+///
+/// `run`の終了後(パニックした場合も含む)は元の設定に戻す。x86、x86-64、AArch64以外では単に`run`を
+/// 実行する。
+///
+/// # Safety
+///
+/// `enabled`の場合、`run`はONNX Runtimeの推論を呼び出すのみとし、Rustのコードで浮動小数点演算を
+/// 行ってはならない。
+#[allow(unsafe_code)]
+pub(super) unsafe fn flush_denormals_during<R>(enabled: bool, run: impl FnOnce() -> R) -> R {
+    cfg_if! {
+        if #[cfg(any(target_arch = "x86", target_arch = "x86_64", target_arch = "aarch64"))] {
+            struct Restore(ControlRegister);
+
+            impl Drop for Restore {
+                fn drop(&mut self) {
+                    // SAFETY: 元の設定に戻すだけである。
+                    unsafe { set_control_register(self.0) };
+                }
+            }
+
+            if !enabled {
+                return run();
+            }
+            let original = get_control_register();
+            let _restore = Restore(original);
+            // SAFETY: `Restore`で元に戻すまでの間は`run`のみを実行し、その条件は呼び出し元が満たす。
+            unsafe { set_control_register(original | FLUSH_BITS) };
+            run()
+        } else {
+            let _ = enabled;
+            run()
+        }
+    }
+}
+
+#[cfg(all(
+    test,
+    any(target_arch = "x86", target_arch = "x86_64", target_arch = "aarch64"),
+))]
+mod tests {
+    use rstest::rstest;
+
+    use super::{flush_denormals_during, get_control_register, FLUSH_BITS};
+
+    #[rstest]
+    #[case(true, u64::from(FLUSH_BITS))]
+    #[case(false, 0)]
+    #[allow(unsafe_code)]
+    fn flush_denormals_during_works(#[case] enabled: bool, #[case] expected_bits: u64) {
+        let original = get_control_register();
+        assert_eq!(0, original & FLUSH_BITS);
+
+        // SAFETY: 制御レジスタを読むだけであり、浮動小数点演算は行わない。
+        let during = unsafe { flush_denormals_during(enabled, get_control_register) };
+
+        assert_eq!(expected_bits, u64::from(during & FLUSH_BITS));
+        assert_eq!(original, get_control_register());
+    }
+}
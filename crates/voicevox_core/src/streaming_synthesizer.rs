@@ -0,0 +1,160 @@
+use futures::{Stream, StreamExt as _};
+use tokio::sync::mpsc;
+
+use crate::{engine::split_sentences, Result, StyleId, SynthesizerHandle, TtsOptions};
+
+/// テキストを断片ごとに受け取り、文が完結するたびにテキスト音声合成を行うもの。
+///
+/// 音声認識の結果を逐次読み上げる場合のように、テキストが少しずつ届く用途のためのものである。
+/// [`push`]で追加されたテキストは[`Synthesizer::tts_many`]と同じ規則で文に分割され、完結した文から
+/// 順に、[`new`]が返すストリームで`(文, WAVデータ)`として返される。合成済みの文が再び合成されることは
+/// 無い。
+///
+/// 最後の文は、後ろに閉じ括弧や文末記号が続く可能性があるため、次の文の文字が追加されるか[`finish`]が
+/// 呼ばれるまでは合成しない。[`finish`]を呼ばずにこれを破棄した場合、残っているテキストは合成されずに
+/// ストリームが終わる。
+///
+/// 合成はストリームがポーリングされるたびに一文ずつ行われる。ストリームを破棄した後の[`push`]は何も
+/// しない。
+///
+/// [`push`]: Self::push
+/// [`new`]: Self::new
+/// [`finish`]: Self::finish
+/// [`Synthesizer::tts_many`]: crate::Synthesizer::tts_many
+pub struct StreamingSynthesizer {
+    /// まだ完結していない文。
+    buffer: String,
+    sentences: mpsc::UnboundedSender<String>,
+}
+
+impl StreamingSynthesizer {
+    /// `synthesizer`の`style_id`のスタイルで、`options`を使ってテキスト音声合成を行うものを作る。
+    ///
+    /// 戻り値のストリームは、完結した文の`(文, WAVデータ)`を文の順に返す。
+    pub fn new(
+        synthesizer: SynthesizerHandle,
+        style_id: StyleId,
+        options: TtsOptions,
+    ) -> (Self, impl Stream<Item = Result<(String, Vec<u8>)>>) {
+        let (sentences, receiver) = mpsc::unbounded_channel::<String>();
+        let wavs = futures::stream::unfold(receiver, |mut receiver| async move {
+            let sentence = receiver.recv().await?;
+            Some((sentence, receiver))
+        })
+        .then(move |sentence| {
+            let synthesizer = synthesizer.clone();
+            let options = options.clone();
+            async move {
+                let wav = synthesizer
+                    .tts_sentence(&sentence, style_id, &options)
+                    .await?;
+                Ok((sentence, wav))
+            }
+        });
+        let this = Self {
+            buffer: String::new(),
+            sentences,
+        };
+        (this, wavs)
+    }
+
+    /// テキストの断片を追加する。これによって完結した文があれば、その合成を予約する。
+    pub fn push(&mut self, fragment: &str) {
+        self.buffer += fragment;
+        for sentence in take_completed_sentences(&mut self.buffer) {
+            self.send(sentence);
+        }
+    }
+
+    /// 残っているテキストを最後の文として合成を予約し、ストリームを終わらせる。
+    pub fn finish(self) {
+        let rest = self.buffer.trim();
+        if !rest.is_empty() {
+            self.send(rest.to_owned());
+        }
+    }
+
+    fn send(&self, sentence: String) {
+        // ストリームが破棄されている場合は、合成する必要が無い
+        let _ = self.sentences.send(sentence);
+    }
+}
+
+/// `buffer`のうち最後の文を除いた、完結した文を取り出す。`buffer`には最後の文のみが残る。
+fn take_completed_sentences(buffer: &mut String) -> Vec<String> {
+    let (completed, last_start) = match split_sentences(buffer).split_last() {
+        Some((last, completed)) => (
+            completed.iter().map(|&s| s.to_owned()).collect(),
+            // `last`はテキストの末尾までを、前後の空白を除いて切り出したものである
+            buffer.trim_end().len() - last.len(),
+        ),
+        None => (vec![], buffer.len()),
+    };
+    buffer.drain(..last_start);
+    completed
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use futures::TryStreamExt as _;
+    use pretty_assertions::assert_eq;
+    use rstest::rstest;
+
+    use super::{take_completed_sentences, StreamingSynthesizer};
+    use crate::{AccelerationMode, InitializeOptions, OpenJtalk, StyleId, Synthesizer};
+    use ::test_util::OPEN_JTALK_DIC_DIR;
+
+    #[rstest]
+    #[case("", &[], "")]
+    #[case("こんにち", &[], "こんにち")]
+    #[case("こんにちは。", &[], "こんにちは。")]
+    #[case("こんにちは。元", &["こんにちは。"], "元")]
+    #[case("「はい。」と言った。そ", &["「はい。」と言った。"], "そ")]
+    #[case("おはよう！　元気？ ", &["おはよう！"], "元気？ ")]
+    #[case("　", &[], "")]
+    fn take_completed_sentences_works(
+        #[case] buffer: &str,
+        #[case] expected: &[&str],
+        #[case] expected_rest: &str,
+    ) {
+        let mut buffer = buffer.to_owned();
+        assert_eq!(expected, take_completed_sentences(&mut buffer));
+        assert_eq!(expected_rest, buffer);
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn streaming_synthesizer_works() {
+        let synthesizer = Synthesizer::new_with_initialize(
+            Arc::new(OpenJtalk::new_with_initialize(OPEN_JTALK_DIC_DIR).unwrap()),
+            &InitializeOptions {
+                acceleration_mode: AccelerationMode::Cpu,
+                load_all_models: true,
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap()
+        .into_handle();
+
+        let (mut streaming, wavs) =
+            StreamingSynthesizer::new(synthesizer.clone(), StyleId::new(0), Default::default());
+        for fragment in ["こんにち", "は。元", "気ですか？", "はい"] {
+            streaming.push(fragment);
+        }
+        streaming.finish();
+        let wavs = wavs.try_collect::<Vec<_>>().await.unwrap();
+
+        let sentences = wavs.iter().map(|(s, _)| &**s).collect::<Vec<_>>();
+        assert_eq!(vec!["こんにちは。", "元気ですか？", "はい"], sentences);
+        for (sentence, wav) in &wavs {
+            let expected = synthesizer
+                .tts(sentence, StyleId::new(0), &Default::default())
+                .await
+                .unwrap();
+            assert_eq!(expected, *wav);
+        }
+    }
+}
@@ -0,0 +1,100 @@
+use std::{
+    future::Future,
+    sync::{Arc, Mutex},
+};
+
+use serde::Serialize;
+
+tokio::task_local! {
+    static CURRENT: TensorCapture;
+}
+
+/// 推論に渡されたテンソルの記録先。
+///
+/// [`SynthesisOptions::capture_tensors`]や[`TtsOptions::capture_tensors`]に設定すると、その音声合成
+/// 中に各ONNXセッションに渡された入力が記録される。モデルの不具合を報告する際などに、入力を正確に
+/// 再現するためのデバッグ用の機能である。
+///
+/// 複製したものは同じ記録先を指す。
+///
+/// [`SynthesisOptions::capture_tensors`]: crate::SynthesisOptions::capture_tensors
+/// [`TtsOptions::capture_tensors`]: crate::TtsOptions::capture_tensors
+#[derive(Clone, Default, Debug)]
+pub struct TensorCapture(Arc<Mutex<CapturedTensors>>);
+
+impl TensorCapture {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 記録されたテンソルを取り出す。記録先は空になる。
+    pub fn take(&self) -> CapturedTensors {
+        std::mem::take(&mut self.0.lock().unwrap())
+    }
+
+    /// `capture`が`Some`であれば、それを記録先として`future`を実行する。
+    pub(crate) async fn scope<F: Future>(capture: Option<&TensorCapture>, future: F) -> F::Output {
+        match capture {
+            Some(capture) => CURRENT.scope(capture.clone(), future).await,
+            None => future.await,
+        }
+    }
+
+    /// 記録先が設定されている場合のみ`record`を呼ぶ。
+    pub(crate) fn record(record: impl FnOnce(&mut CapturedTensors)) {
+        let _ = CURRENT.try_with(|capture| record(&mut capture.0.lock().unwrap()));
+    }
+}
+
+/// 同じ記録先を指すかどうかで比較する。
+impl PartialEq for TensorCapture {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+/// 記録されたテンソル。各フィールドには、推論が行われた順に入力が並ぶ。
+#[derive(Clone, Default, Debug, PartialEq, Serialize)]
+pub struct CapturedTensors {
+    pub predict_duration: Vec<PredictDurationInputs>,
+    pub predict_intonation: Vec<PredictIntonationInputs>,
+    pub decode: Vec<DecodeInputs>,
+}
+
+/// 音素長推論のセッションへの入力。
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct PredictDurationInputs {
+    /// 形状は`[phoneme_vector.len()]`。
+    pub phoneme_vector: Vec<i64>,
+    /// 形状は`[1]`。
+    pub speaker_id: i64,
+}
+
+/// 音高推論のセッションへの入力。
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct PredictIntonationInputs {
+    /// 形状は`[]`。
+    pub length: i64,
+    /// 以下、形状は全て`[length]`。
+    pub vowel_phoneme_vector: Vec<i64>,
+    pub consonant_phoneme_vector: Vec<i64>,
+    pub start_accent_vector: Vec<i64>,
+    pub end_accent_vector: Vec<i64>,
+    pub start_accent_phrase_vector: Vec<i64>,
+    pub end_accent_phrase_vector: Vec<i64>,
+    /// 形状は`[1]`。
+    pub speaker_id: i64,
+}
+
+/// 波形推論のセッションへの入力。前後の無音のパディングを含む。
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct DecodeInputs {
+    /// 形状は`[length, 1]`。
+    pub f0: Vec<f32>,
+    /// 形状は`[length, phoneme_size]`。
+    pub phoneme: Vec<f32>,
+    pub length: usize,
+    pub phoneme_size: usize,
+    /// 形状は`[1]`。
+    pub speaker_id: i64,
+}
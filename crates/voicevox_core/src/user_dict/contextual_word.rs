@@ -0,0 +1,162 @@
+use serde::{Deserialize, Serialize};
+
+use super::word::{UserDictWord, UserDictWordType};
+use crate::Result;
+
+/// 前後の文脈によって読みを変える単語。「今日」の「キョー」と「コンニチ」のような同形異音語のための
+/// ものである。
+///
+/// テキスト中に[`surface`]が現れたとき、その直前が[`preceding`]のいずれかで終わり、かつ直後が
+/// [`following`]のいずれかで始まる場合に、その箇所だけを[`pronunciation`]と[`accent_type`]で読む。
+/// 同じ表記に対して複数の`ContextualWord`がある場合は、[`UserDict`]に追加された順に調べ、最初に
+/// 当てはまったものを使う。どれにも当てはまらない箇所は、通常どおりシステム辞書とユーザー辞書で
+/// 読む。
+///
+/// 文脈の判定は前後の文字列の単純な比較であり、形態素解析の結果は見ない。そのため例えば
+/// 「コンニチワ」と読ませるために`following`を`["は"]`とすると、挨拶の「今日は」だけでなく
+/// 「今日は晴れ」にも当てはまる。あくまで経験則に基づく補助として使うこと。
+///
+/// [`surface`]: Self::surface
+/// [`preceding`]: Self::preceding
+/// [`following`]: Self::following
+/// [`pronunciation`]: Self::pronunciation
+/// [`accent_type`]: Self::accent_type
+/// [`UserDict`]: super::UserDict
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ContextualWord {
+    /// 単語の表記。
+    pub surface: String,
+    /// この文脈での単語の読み。
+    pub pronunciation: String,
+    /// この文脈でのアクセント型。
+    pub accent_type: usize,
+    /// 直前の文字列の候補。空の場合は直前を問わない。
+    pub preceding: Vec<String>,
+    /// 直後の文字列の候補。空の場合は直後を問わない。
+    pub following: Vec<String>,
+}
+
+/// 文脈に当てはまった箇所を置き換えて作る単語の優先度。置き換えた箇所を確実にこの読みにするため、
+/// 最も高い値とする。
+const CONTEXTUAL_WORD_PRIORITY: u32 = 10;
+
+impl ContextualWord {
+    /// 文脈に当てはまった箇所を置き換える単語を作る。
+    ///
+    /// 表記は読みそのものとする。これにより、同じテキスト中の文脈に当てはまらない箇所の
+    /// `surface`には影響しない。
+    ///
+    /// # Errors
+    ///
+    /// `pronunciation`と`accent_type`が[`UserDictWord::new`]で不正とされるものの場合、
+    /// [`Error::InvalidWord`]を返す。
+    ///
+    /// [`Error::InvalidWord`]: crate::Error::InvalidWord
+    pub(crate) fn to_user_dict_word(&self) -> Result<UserDictWord> {
+        UserDictWord::new(
+            &self.pronunciation,
+            self.pronunciation.clone(),
+            self.accent_type,
+            UserDictWordType::ProperNoun,
+            CONTEXTUAL_WORD_PRIORITY,
+        )
+    }
+
+    /// `text`の`pos`バイト目から始まる箇所が、この単語とその文脈に当てはまるかどうか。
+    fn matches_at(&self, text: &str, pos: usize) -> bool {
+        let (before, rest) = text.split_at(pos);
+        let Some(after) = rest.strip_prefix(&*self.surface) else {
+            return false;
+        };
+        !self.surface.is_empty()
+            && (self.preceding.is_empty() || self.preceding.iter().any(|p| before.ends_with(&**p)))
+            && (self.following.is_empty() || self.following.iter().any(|f| after.starts_with(&**f)))
+    }
+}
+
+/// `text`中の`contextual_words`に当てはまる箇所を読みに置き換え、置き換えたテキストと、読みを
+/// 与えるための単語を返す。
+///
+/// 表記が`excluded_surfaces`のいずれかと同じ`ContextualWord`は使わない。
+pub(crate) fn apply_contextual_words<'a>(
+    text: &str,
+    contextual_words: impl IntoIterator<Item = &'a ContextualWord>,
+    excluded_surfaces: &[&str],
+) -> Result<(String, Vec<UserDictWord>)> {
+    let contextual_words = contextual_words
+        .into_iter()
+        .filter(|word| !excluded_surfaces.contains(&&*word.surface))
+        .collect::<Vec<_>>();
+    if contextual_words.is_empty() {
+        return Ok((text.to_owned(), vec![]));
+    }
+
+    let mut replaced = String::with_capacity(text.len());
+    let mut words = Vec::<UserDictWord>::new();
+    let mut rest_start = 0;
+    let mut pos = 0;
+    while pos < text.len() {
+        if let Some(word) = contextual_words.iter().find(|w| w.matches_at(text, pos)) {
+            let user_dict_word = word.to_user_dict_word()?;
+            replaced += &text[rest_start..pos];
+            replaced += &user_dict_word.surface;
+            if !words.iter().any(|w| w.surface == user_dict_word.surface) {
+                words.push(user_dict_word);
+            }
+            pos += word.surface.len();
+            rest_start = pos;
+        } else {
+            pos += text[pos..].chars().next().unwrap().len_utf8();
+        }
+    }
+    replaced += &text[rest_start..];
+
+    Ok((replaced, words))
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+    use rstest::rstest;
+
+    use super::{apply_contextual_words, ContextualWord};
+
+    fn kyou_words() -> [ContextualWord; 2] {
+        [
+            ContextualWord {
+                surface: "今日".to_owned(),
+                pronunciation: "コンニチ".to_owned(),
+                accent_type: 1,
+                preceding: vec![],
+                following: vec!["の社会".to_owned(), "では".to_owned()],
+            },
+            ContextualWord {
+                surface: "今日".to_owned(),
+                pronunciation: "キョー".to_owned(),
+                accent_type: 1,
+                preceding: vec!["明日と".to_owned()],
+                following: vec![],
+            },
+        ]
+    }
+
+    #[rstest]
+    #[case("今日は晴れ", &[], "今日は晴れ", &[])]
+    #[case("今日の社会", &[], "コンニチの社会", &["コンニチ"])]
+    #[case("明日と今日では", &[], "明日とコンニチでは", &["コンニチ"])]
+    #[case("明日と今日", &[], "明日とキョー", &["キョー"])]
+    #[case("今日では、明日と今日", &[], "コンニチでは、明日とキョー", &["コンニチ", "キョー"])]
+    #[case("今日では", &["今日"], "今日では", &[])]
+    fn apply_contextual_words_works(
+        #[case] text: &str,
+        #[case] excluded_surfaces: &[&str],
+        #[case] expected_text: &str,
+        #[case] expected_surfaces: &[&str],
+    ) {
+        let (replaced, words) =
+            apply_contextual_words(text, &kyou_words(), excluded_surfaces).unwrap();
+        assert_eq!(expected_text, replaced);
+        let surfaces = words.iter().map(|w| &*w.surface).collect::<Vec<_>>();
+        assert_eq!(expected_surfaces, surfaces);
+    }
+}
@@ -4,14 +4,20 @@ use indexmap::IndexMap;
 use itertools::join;
 use uuid::Uuid;
 
-use super::word::*;
-use crate::{Error, Result};
+use super::{
+    contextual_word::{apply_contextual_words, ContextualWord},
+    word::*,
+};
+use crate::{Error, OpenJtalk, Result};
 
 /// ユーザー辞書。
 /// 単語はJSONとの相互変換のために挿入された順序を保つ。
 #[derive(Clone, Debug, Default, Getters)]
 pub struct UserDict {
+    /// 登録されている単語と、そのUUID。挿入された順に並ぶ。
     words: IndexMap<Uuid, UserDictWord>,
+    /// 登録されている、文脈によって読みを変える単語と、そのUUID。挿入された順に並ぶ。
+    contextual_words: IndexMap<Uuid, ContextualWord>,
 }
 
 impl UserDict {
@@ -20,6 +26,16 @@ impl UserDict {
         Default::default()
     }
 
+    pub(crate) fn from_parts(
+        words: IndexMap<Uuid, UserDictWord>,
+        contextual_words: IndexMap<Uuid, ContextualWord>,
+    ) -> Self {
+        Self {
+            words,
+            contextual_words,
+        }
+    }
+
     /// ユーザー辞書をファイルから読み込む。
     ///
     /// # Errors
@@ -37,6 +53,16 @@ impl UserDict {
         Ok(())
     }
 
+    /// 登録されている単語の数。[`ContextualWord`]は含まない。
+    pub fn len(&self) -> usize {
+        self.words.len()
+    }
+
+    /// 単語が1つも登録されていないかどうか。[`ContextualWord`]は考慮しない。
+    pub fn is_empty(&self) -> bool {
+        self.words.is_empty()
+    }
+
     /// ユーザー辞書に単語を追加する。
     pub fn add_word(&mut self, word: UserDictWord) -> Result<Uuid> {
         let word_uuid = Uuid::new_v4();
@@ -45,11 +71,25 @@ impl UserDict {
     }
 
     /// ユーザー辞書の単語を変更する。
+    ///
+    /// UUIDと辞書内での順序は変わらない。`new_word`のフィールドが作成後に書き換えられている場合に
+    /// 備え、[`UserDictWord::new`]と同じ検証をやり直す。
+    ///
+    /// # Errors
+    ///
+    /// `word_uuid`の単語が無い場合は[`Error::UnknownWord`]を、`new_word`が不正な場合は
+    /// [`Error::InvalidWord`]を返す。
     pub fn update_word(&mut self, word_uuid: Uuid, new_word: UserDictWord) -> Result<()> {
-        if !self.words.contains_key(&word_uuid) {
+        let Some(word) = self.words.get_mut(&word_uuid) else {
             return Err(Error::UnknownWord(word_uuid));
-        }
-        self.words.insert(word_uuid, new_word);
+        };
+        *word = UserDictWord::new(
+            &new_word.surface,
+            new_word.pronunciation,
+            new_word.accent_type,
+            new_word.word_type,
+            new_word.priority,
+        )?;
         Ok(())
     }
 
@@ -61,15 +101,43 @@ impl UserDict {
         Ok(word)
     }
 
+    /// ユーザー辞書に、文脈によって読みを変える単語を追加する。
+    ///
+    /// 同じ表記の`ContextualWord`が既にある場合は、それらの後に調べられる。
+    ///
+    /// # Errors
+    ///
+    /// `word`の読みとアクセント型が[`UserDictWord::new`]で不正とされるものの場合、
+    /// [`Error::InvalidWord`]を返す。
+    pub fn add_contextual_word(&mut self, word: ContextualWord) -> Result<Uuid> {
+        word.to_user_dict_word()?;
+        let word_uuid = Uuid::new_v4();
+        self.contextual_words.insert(word_uuid, word);
+        Ok(word_uuid)
+    }
+
+    /// ユーザー辞書から、文脈によって読みを変える単語を削除する。
+    pub fn remove_contextual_word(&mut self, word_uuid: Uuid) -> Result<ContextualWord> {
+        let Some(word) = self.contextual_words.shift_remove(&word_uuid) else {
+            return Err(Error::UnknownWord(word_uuid));
+        };
+        Ok(word)
+    }
+
     /// 他のユーザー辞書をインポートする。
     pub fn import(&mut self, other: &Self) -> Result<()> {
         for (word_uuid, word) in &other.words {
             self.words.insert(*word_uuid, word.clone());
         }
+        for (word_uuid, word) in &other.contextual_words {
+            self.contextual_words.insert(*word_uuid, word.clone());
+        }
         Ok(())
     }
 
     /// ユーザー辞書を保存する。
+    ///
+    /// 保存されるのは[`UserDictWord`]のみであり、[`ContextualWord`]は保存されない。
     pub fn save(&self, store_path: &str) -> Result<()> {
         let mut file = File::create(store_path).map_err(|e| Error::SaveUserDict(e.to_string()))?;
         serde_json::to_writer(&mut file, &self.words)
@@ -77,8 +145,119 @@ impl UserDict {
         Ok(())
     }
 
+    /// ユーザー辞書を、Open JTalkの辞書としてコンパイルしたバイナリに変換する。
+    ///
+    /// [`OpenJtalk::use_user_dict_bytes`]に渡すと、コンパイルを行わずにユーザー辞書を設定できる。
+    /// 大きなユーザー辞書をディスクにキャッシュし、起動時のコンパイルを省くためのものである。
+    /// コンパイルには`open_jtalk`に読み込まれているシステム辞書を使う。
+    ///
+    /// 出力はこのライブラリに組み込まれたOpen JTalk(MeCab)の辞書形式と、コンパイルに使ったシステム
+    /// 辞書に依存する。ライブラリやシステム辞書を更新した場合は、作りなおす必要がある。
+    ///
+    /// # Errors
+    ///
+    /// `open_jtalk`にシステム辞書が読み込まれていない場合は[`Error::NotLoadedOpenjtalkDict`]を、
+    /// コンパイルに失敗した場合は[`Error::UseUserDict`]を返す。
+    ///
+    /// [`OpenJtalk::use_user_dict_bytes`]: crate::OpenJtalk::use_user_dict_bytes
+    pub fn to_openjtalk_dict_bytes(&self, open_jtalk: &OpenJtalk) -> Result<Vec<u8>> {
+        open_jtalk.compile_user_dict_to_bytes(self)
+    }
+
     /// MeCabで使用する形式に変換する。
     pub(crate) fn to_mecab_format(&self) -> String {
         join(self.words.values().map(UserDictWord::to_mecab_format), "\n")
     }
+
+    /// `text`中の[`ContextualWord`]に当てはまる箇所を読みに置き換え、置き換えたテキストと、読みを
+    /// 与えるための単語を返す。表記が`excluded_surfaces`のいずれかと同じものは使わない。
+    pub(crate) fn apply_contextual_words(
+        &self,
+        text: &str,
+        excluded_surfaces: &[&str],
+    ) -> Result<(String, Vec<UserDictWord>)> {
+        apply_contextual_words(text, self.contextual_words.values(), excluded_surfaces)
+    }
+
+    /// 登録されている単語の表記を列挙する。
+    pub(crate) fn surfaces(&self) -> impl Iterator<Item = &String> {
+        self.words.values().map(|word| &word.surface)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use uuid::Uuid;
+
+    use super::UserDict;
+    use crate::{Error, UserDictWord, UserDictWordType};
+
+    #[rstest]
+    fn len_works() {
+        let word = || {
+            UserDictWord::new(
+                "単語",
+                "ヨミ".to_owned(),
+                0,
+                UserDictWordType::ProperNoun,
+                5,
+            )
+            .unwrap()
+        };
+        let mut dict = UserDict::new();
+        assert!(dict.is_empty());
+
+        let first = dict.add_word(word()).unwrap();
+        let second = dict.add_word(word()).unwrap();
+        assert_eq!(2, dict.len());
+        assert_eq!(
+            vec![first, second],
+            dict.words().keys().copied().collect::<Vec<_>>(),
+        );
+
+        dict.remove_word(first).unwrap();
+        assert_eq!(1, dict.len());
+        assert!(!dict.is_empty());
+    }
+
+    #[rstest]
+    fn update_word_works() {
+        let word = |surface: &str, pronunciation: &str| {
+            UserDictWord::new(
+                surface,
+                pronunciation.to_owned(),
+                0,
+                UserDictWordType::ProperNoun,
+                5,
+            )
+            .unwrap()
+        };
+        let mut dict = UserDict::new();
+        let first = dict.add_word(word("単語", "タンゴ")).unwrap();
+        let second = dict.add_word(word("言葉", "コトバ")).unwrap();
+
+        dict.update_word(first, word("語", "ゴ")).unwrap();
+        assert_eq!(
+            vec![first, second],
+            dict.words().keys().copied().collect::<Vec<_>>(),
+        );
+        let updated = &dict.words()[&first];
+        assert_eq!(("語", 1), (&*updated.surface, *updated.mora_count()));
+
+        let mut invalid = word("語", "ゴ");
+        invalid.pronunciation = "ご".to_owned();
+        assert!(matches!(
+            dict.update_word(first, invalid),
+            Err(Error::InvalidWord(_)),
+        ));
+        assert_eq!("ゴ", dict.words()[&first].pronunciation);
+
+        let unknown = Uuid::new_v4();
+        assert!(matches!(
+            dict.update_word(unknown, word("語", "ゴ")),
+            Err(Error::UnknownWord(uuid)) if uuid == unknown,
+        ));
+    }
 }
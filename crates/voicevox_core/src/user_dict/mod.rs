@@ -1,6 +1,8 @@
+mod contextual_word;
 mod dict;
 mod part_of_speech_data;
 mod word;
 
+pub use contextual_word::ContextualWord;
 pub use dict::*;
 pub use word::*;
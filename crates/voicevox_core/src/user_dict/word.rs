@@ -22,7 +22,12 @@ pub struct UserDictWord {
     pub accent_type: usize,
     /// 単語の種類。
     pub word_type: UserDictWordType,
-    /// 単語の優先度。
+    /// 単語の優先度。0以上10以下。
+    ///
+    /// 優先度はMeCabの辞書の生起コストに変換される。形態素解析では、システム辞書の単語も含めて
+    /// コストの合計が最も小さくなる分割が選ばれるため、優先度が高いほどこの単語が選ばれやすくなる。
+    /// 5はシステム辞書の一般的な単語と同程度であり、表記が同じ単語がシステム辞書にある場合に確実に
+    /// こちらを使わせたいときは、より大きい値にする。0は生起コストが非常に大きく、ほぼ選ばれない。
     pub priority: u32,
 
     /// モーラ数。
@@ -94,6 +99,15 @@ impl Default for UserDictWord {
 }
 
 impl UserDictWord {
+    /// 単語を作成する。
+    ///
+    /// # Errors
+    ///
+    /// 以下の場合、[`Error::InvalidWord`]を返す。
+    ///
+    /// - `priority`が0以上10以下でない。
+    /// - `pronunciation`がカタカナで書かれた発音として有効でない。
+    /// - `accent_type`が`pronunciation`のモーラ数より大きい。
     pub fn new(
         surface: &str,
         pronunciation: String,
@@ -265,6 +279,36 @@ mod tests {
         );
     }
 
+    #[rstest]
+    #[case(0, true)]
+    #[case(5, true)]
+    #[case(10, true)]
+    #[case(11, false)]
+    #[case(u32::MAX, false)]
+    fn priority_validation_works(#[case] priority: u32, #[case] expected_ok: bool) {
+        let result = UserDictWord::new(
+            "単語",
+            "ヨミ".to_string(),
+            0,
+            UserDictWordType::ProperNoun,
+            priority,
+        );
+
+        if expected_ok {
+            let word = result.unwrap();
+            assert_eq!(priority, word.priority);
+            // 生起コストに変換できること
+            word.to_mecab_format();
+        } else {
+            match result {
+                Err(Error::InvalidWord(InvalidWordError::InvalidPriority(err_priority))) => {
+                    assert_eq!(priority, err_priority);
+                }
+                _ => unreachable!(),
+            }
+        }
+    }
+
     #[rstest]
     #[case("ヨミ", None)]
     #[case("漢字", Some("カタカナ以外の文字"))]
@@ -1,12 +1,20 @@
-use async_zip::{read::fs::ZipFileReader, ZipEntry};
+use async_zip::{
+    read::{fs, seek},
+    ZipEntry,
+};
 use futures::future::{join3, join_all};
 use serde::{de::DeserializeOwned, Deserialize};
+use sha2::{Digest as _, Sha256};
+use tokio::io::{AsyncRead, AsyncReadExt as _};
 
 use super::*;
 use std::{
-    collections::{BTreeMap, HashMap},
-    env, io,
+    collections::{BTreeMap, BTreeSet, HashMap},
+    env,
+    io::Cursor,
     path::{Path, PathBuf},
+    str::FromStr,
+    sync::Arc,
 };
 
 /// [`VoiceModelId`]の実体。
@@ -22,6 +30,38 @@ pub struct VoiceModelId {
     raw_voice_model_id: RawVoiceModelId,
 }
 
+impl VoiceModelId {
+    /// [`VoiceModel`]が生成するIDの長さ。
+    const LEN: usize = 21;
+}
+
+/// [`VoiceModel`]が生成するIDの形式(`A-Za-z0-9_-`からなる21文字)であるかを検証した上で変換する。
+///
+/// 音声モデルIDはUUIDではなく、[`VoiceModel`]を開くたびに生成されるものであることに注意。
+impl FromStr for VoiceModelId {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let is_valid = s.len() == Self::LEN
+            && s.bytes()
+                .all(|c| c.is_ascii_alphanumeric() || c == b'_' || c == b'-');
+        if !is_valid {
+            return Err(Error::InvalidModelId {
+                model_id: Self::new(s.to_owned()),
+            });
+        }
+        Ok(Self::new(s.to_owned()))
+    }
+}
+
+impl TryFrom<&str> for VoiceModelId {
+    type Error = Error;
+
+    fn try_from(s: &str) -> Result<Self> {
+        s.parse()
+    }
+}
+
 /// 音声モデル。
 ///
 /// VVMファイルと対応する。
@@ -32,47 +72,246 @@ pub struct VoiceModel {
     manifest: Manifest,
     /// メタ情報。
     metas: VoiceModelMeta,
+    /// 利用規約。
+    ///
+    /// マニフェストの`terms_filename`が指すファイルの内容であり、VVMファイルが利用規約を含まない
+    /// 場合は`None`。アプリケーションは読み込んだ音声モデルごとにこれを表示することが望ましい。
+    terms: Option<String>,
     path: PathBuf,
+    /// [`VoiceModel::from_async_read`]で読んだVVMファイルの中身。
+    #[getter(skip)]
+    content: Option<Arc<[u8]>>,
+}
+
+/// 音声モデルに含まれる推論用のネットワーク。
+///
+/// `Decode`は全ての音声モデルが含む。`PredictDuration`と`PredictIntonation`は含まない音声モデルも
+/// あり、その場合はそのネットワークを使う処理が[`Error::MissingNetwork`]で失敗する。
+#[derive(Clone, Copy, Debug, PartialEq, Eq, derive_more::Display)]
+pub enum Network {
+    /// 音素長の推論。[`Synthesizer::audio_query`]などで使われる。
+    ///
+    /// [`Synthesizer::audio_query`]: crate::Synthesizer::audio_query
+    #[display(fmt = "predict_duration")]
+    PredictDuration,
+    /// 音高の推論。[`Synthesizer::audio_query`]などで使われる。
+    ///
+    /// [`Synthesizer::audio_query`]: crate::Synthesizer::audio_query
+    #[display(fmt = "predict_intonation")]
+    PredictIntonation,
+    /// 波形の推論。[`Synthesizer::synthesis`]などで使われる。
+    ///
+    /// [`Synthesizer::synthesis`]: crate::Synthesizer::synthesis
+    #[display(fmt = "decode")]
+    Decode,
 }
 
 #[derive(Getters)]
 pub(crate) struct InferenceModels {
-    decode_model: Vec<u8>,
-    predict_duration_model: Vec<u8>,
-    predict_intonation_model: Vec<u8>,
+    decode_model: Arc<[u8]>,
+    predict_duration_model: Option<Arc<[u8]>>,
+    predict_intonation_model: Option<Arc<[u8]>>,
 }
 
 impl VoiceModel {
     pub(crate) async fn read_inference_models(&self) -> LoadModelResult<InferenceModels> {
-        let reader = VvmEntryReader::open(&self.path).await?;
+        let reader = match &self.content {
+            Some(content) => VvmEntryReader::open_bytes(&self.path, content.clone()).await?,
+            None => VvmEntryReader::open(&self.path).await?,
+        };
         let (decode_model_result, predict_duration_model_result, predict_intonation_model_result) =
             join3(
-                reader.read_vvm_entry(self.manifest.decode_filename()),
-                reader.read_vvm_entry(self.manifest.predict_duration_filename()),
-                reader.read_vvm_entry(self.manifest.predict_intonation_filename()),
+                reader.read_vvm_model(self.manifest.decode_filename()),
+                reader
+                    .read_optional_vvm_model(self.manifest.predict_duration_filename().as_deref()),
+                reader.read_optional_vvm_model(
+                    self.manifest.predict_intonation_filename().as_deref(),
+                ),
             )
             .await;
 
         Ok(InferenceModels {
-            predict_duration_model: predict_duration_model_result?,
-            predict_intonation_model: predict_intonation_model_result?,
-            decode_model: decode_model_result?,
+            predict_duration_model: predict_duration_model_result?.map(Into::into),
+            predict_intonation_model: predict_intonation_model_result?.map(Into::into),
+            decode_model: decode_model_result?.into(),
         })
     }
+
+    /// この音声モデルを読み込むのに必要なメモリの量の目安を、バイト単位で返す。
+    ///
+    /// VVMファイル内の、展開後のネットワークのサイズの合計である。GPUで推論する場合はVRAM、そうで
+    /// ない場合はRAMの使用量の目安となる。ONNX Runtimeの作業領域などは含まないため、実際の使用量は
+    /// これよりも多くなる。
+    ///
+    /// ネットワークはZIPのエントリのサイズから求めるため、通常は全体を読むことは無い。ただしzstdで
+    /// 圧縮されたネットワークについては、そのエントリを読んで展開しながらサイズを数える。
+    pub async fn estimated_memory(&self) -> LoadModelResult<u64> {
+        let reader = match &self.content {
+            Some(content) => VvmEntryReader::open_bytes(&self.path, content.clone()).await?,
+            None => VvmEntryReader::open(&self.path).await?,
+        };
+        let filenames = [
+            Some(self.manifest.decode_filename()),
+            self.manifest.predict_duration_filename().as_ref(),
+            self.manifest.predict_intonation_filename().as_ref(),
+        ];
+        let mut total = 0;
+        for filename in filenames.into_iter().flatten() {
+            total += reader.network_size(filename).await?;
+        }
+        Ok(total)
+    }
     /// VVMファイルから`VoiceModel`をコンストラクトする。
+    ///
+    /// VVM内のネットワークは、ONNXのファイルをそのまま格納したものの他に、zstdで圧縮したものでも
+    /// よい。どちらであるかは内容から判別される。圧縮されたネットワークは配布時のサイズが小さくなる
+    /// 一方、[`Synthesizer::load_voice_model`]の際に展開の時間がかかり、展開の間は圧縮前後の両方を
+    /// メモリ上に保持することになる。
+    ///
+    /// [`Synthesizer::load_voice_model`]: crate::Synthesizer::load_voice_model
     pub async fn from_path(path: impl AsRef<Path>) -> LoadModelResult<Self> {
         let reader = VvmEntryReader::open(path.as_ref()).await?;
-        let manifest = reader.read_vvm_json::<Manifest>("manifest.json").await?;
-        let metas = reader
+        Self::from_entry_reader(&reader, path.as_ref().into(), None).await
+    }
+
+    /// VVMファイルが壊れていないかを、読み込む前に検証する。
+    ///
+    /// 以下を確認する。ONNXのモデルとして正しいかどうかまでは検証しない。
+    ///
+    /// - ZIPファイルとして開けること。
+    /// - マニフェストとメタ情報がJSONとして読めること。
+    /// - マニフェストが指すネットワークのエントリが全て存在し、CRCとサイズが一致すること。zstdで
+    ///   圧縮されている場合は、展開できること。
+    /// - マニフェストにSHA-256が記載されているエントリについて、それが一致すること。
+    ///
+    /// # Errors
+    ///
+    /// 失敗した場合、その原因に応じて以下の[`LoadModelErrorKind`]を持つエラーを返す。
+    ///
+    /// - ZIPファイルとして開けない: [`OpenZipFile`]
+    /// - エントリが存在しない: [`MissingEntry`]
+    /// - エントリが破損している: [`ReadZipEntry`]
+    /// - SHA-256が一致しない: [`ChecksumMismatch`]
+    ///
+    /// [`OpenZipFile`]: LoadModelErrorKind::OpenZipFile
+    /// [`MissingEntry`]: LoadModelErrorKind::MissingEntry
+    /// [`ReadZipEntry`]: LoadModelErrorKind::ReadZipEntry
+    /// [`ChecksumMismatch`]: LoadModelErrorKind::ChecksumMismatch
+    pub async fn verify(path: impl AsRef<Path>) -> LoadModelResult<()> {
+        let reader = VvmEntryReader::open(path.as_ref()).await?;
+        let manifest = reader.read_manifest().await?;
+        reader
+            .read_vvm_json::<VoiceModelMeta>(manifest.metas_filename())
+            .await?;
+
+        let networks = [
+            Some(manifest.decode_filename()),
+            manifest.predict_duration_filename().as_ref(),
+            manifest.predict_intonation_filename().as_ref(),
+        ]
+        .into_iter()
+        .flatten()
+        .collect::<Vec<_>>();
+        if let Some(terms_filename) = manifest.terms_filename() {
+            reader.read_vvm_text(terms_filename).await?;
+        }
+
+        let filenames = networks
+            .iter()
+            .copied()
+            .chain(manifest.sha256().keys())
+            .collect::<BTreeSet<_>>();
+
+        for filename in filenames {
+            let content = reader.read_vvm_entry(filename).await?;
+            if let Some(expected) = manifest.sha256().get(filename) {
+                if !format!("{:x}", Sha256::digest(&content)).eq_ignore_ascii_case(expected) {
+                    return Err(LoadModelError {
+                        path: path.as_ref().to_owned(),
+                        context: LoadModelErrorKind::ChecksumMismatch {
+                            filename: filename.clone(),
+                        },
+                        source: None,
+                    });
+                }
+            }
+            if networks.contains(&filename) {
+                reader.decompress_model(filename, content).await?;
+            }
+        }
+        Ok(())
+    }
+
+    /// [`from_async_read`]で読み込めるVVMファイルの最大サイズ。
+    ///
+    /// [`from_async_read`]: Self::from_async_read
+    pub const MAX_IN_MEMORY_SIZE: u64 = 2 * 1024 * 1024 * 1024;
+
+    /// HTTPのレスポンスボディなどの[`AsyncRead`]から、VVMファイルとして`VoiceModel`を
+    /// コンストラクトする。
+    ///
+    /// ZIPのセントラルディレクトリはファイルの末尾にあるため、`reader`は終端まで読み切られ、その内容は
+    /// メモリ上に保持される。[`MAX_IN_MEMORY_SIZE`]を超える場合はエラーとなる。
+    ///
+    /// エラーやメタ情報における`path`は`<memory>`となる。
+    ///
+    /// [`MAX_IN_MEMORY_SIZE`]: Self::MAX_IN_MEMORY_SIZE
+    pub async fn from_async_read(reader: impl AsyncRead + Unpin) -> LoadModelResult<Self> {
+        let path = PathBuf::from(Self::IN_MEMORY_PATH);
+
+        let mut content = vec![];
+        reader
+            .take(Self::MAX_IN_MEMORY_SIZE + 1)
+            .read_to_end(&mut content)
+            .await
+            .map_err(|source| LoadModelError {
+                path: path.clone(),
+                context: LoadModelErrorKind::ReadStream,
+                source: Some(source.into()),
+            })?;
+        if content.len() as u64 > Self::MAX_IN_MEMORY_SIZE {
+            return Err(LoadModelError {
+                path,
+                context: LoadModelErrorKind::TooLarge {
+                    limit: Self::MAX_IN_MEMORY_SIZE,
+                },
+                source: None,
+            });
+        }
+
+        let content = Arc::<[u8]>::from(content);
+        let reader = VvmEntryReader::open_bytes(&path, content.clone()).await?;
+        Self::from_entry_reader(&reader, path, Some(content)).await
+    }
+    const IN_MEMORY_PATH: &str = "<memory>";
+
+    async fn from_entry_reader(
+        reader: &VvmEntryReader,
+        path: PathBuf,
+        content: Option<Arc<[u8]>>,
+    ) -> LoadModelResult<Self> {
+        let manifest = reader.read_manifest().await?;
+        let mut metas = reader
             .read_vvm_json::<VoiceModelMeta>(manifest.metas_filename())
             .await?;
+        let terms = match manifest.terms_filename() {
+            Some(terms_filename) => Some(reader.read_vvm_text(terms_filename).await?),
+            None => None,
+        };
+        if let Some(terms) = &terms {
+            for speaker in &mut metas {
+                speaker.fill_terms(terms);
+            }
+        }
         let id = VoiceModelId::new(nanoid!());
 
         Ok(Self {
             id,
             metas,
+            terms,
             manifest,
-            path: path.as_ref().into(),
+            path,
+            content,
         })
     }
 
@@ -110,6 +349,24 @@ impl VoiceModel {
     }
     const ROOT_DIR_ENV_NAME: &str = "VV_MODELS_ROOT_DIR";
 
+    /// この音声モデルが含むネットワークを返す。
+    pub fn networks(&self) -> Vec<Network> {
+        [
+            self.manifest
+                .predict_duration_filename()
+                .as_ref()
+                .map(|_| Network::PredictDuration),
+            self.manifest
+                .predict_intonation_filename()
+                .as_ref()
+                .map(|_| Network::PredictIntonation),
+            Some(Network::Decode),
+        ]
+        .into_iter()
+        .flatten()
+        .collect()
+    }
+
     /// モデル内のすべてのスタイルに対するモデル内IDを取得する。
     ///
     /// モデル内IDのマッピングが存在しない場合はそのままスタイルIDを返す。
@@ -136,43 +393,99 @@ struct VvmEntry {
     entry: ZipEntry,
 }
 
+enum ZipFileReader {
+    Fs(fs::ZipFileReader),
+    /// メモリ上のVVMファイルの中身。
+    ///
+    /// `seek::ZipFileReader`はエントリを読むのに`&mut`を要するため、エントリを読むたびに`Cursor`で
+    /// 開き直す。中身そのものは共有され、コピーされない。
+    Mem(Arc<[u8]>),
+}
+
 #[derive(new)]
 struct VvmEntryReader {
     reader: ZipFileReader,
+    path: PathBuf,
     entry_map: HashMap<String, VvmEntry>,
 }
 
 impl VvmEntryReader {
     async fn open(path: &Path) -> LoadModelResult<Self> {
-        let reader = ZipFileReader::new(path)
+        let reader = fs::ZipFileReader::new(path)
             .await
             .map_err(|source| LoadModelError {
                 path: path.to_owned(),
                 context: LoadModelErrorKind::OpenZipFile,
                 source: Some(source.into()),
             })?;
-        let entry_map: HashMap<_, _> = reader
-            .file()
-            .entries()
-            .iter()
-            .filter(|e| !e.entry().dir())
+        let entry_map = Self::entry_map(reader.file().entries().iter().map(|e| e.entry()));
+        Ok(VvmEntryReader::new(
+            ZipFileReader::Fs(reader),
+            path.to_owned(),
+            entry_map,
+        ))
+    }
+
+    async fn open_bytes(path: &Path, content: Arc<[u8]>) -> LoadModelResult<Self> {
+        let reader = seek::ZipFileReader::new(Cursor::new(content.clone()))
+            .await
+            .map_err(|source| LoadModelError {
+                path: path.to_owned(),
+                context: LoadModelErrorKind::OpenZipFile,
+                source: Some(source.into()),
+            })?;
+        let entry_map = Self::entry_map(reader.file().entries().iter().map(|e| e.entry()));
+        Ok(VvmEntryReader::new(
+            ZipFileReader::Mem(content),
+            path.to_owned(),
+            entry_map,
+        ))
+    }
+
+    fn entry_map<'a>(entries: impl Iterator<Item = &'a ZipEntry>) -> HashMap<String, VvmEntry> {
+        entries
+            .filter(|e| !e.dir())
             .enumerate()
             .map(|(i, e)| {
                 (
-                    e.entry().filename().to_string(),
+                    e.filename().to_string(),
                     VvmEntry {
                         index: i,
-                        entry: e.entry().clone(),
+                        entry: e.clone(),
                     },
                 )
             })
-            .collect();
-        Ok(VvmEntryReader::new(reader, entry_map))
+            .collect()
     }
+    /// マニフェストを読む。
+    ///
+    /// 対応していないバージョンのマニフェストは他のフィールドの形式も異なりうるため、先にバージョン
+    /// だけを読んで確かめる。
+    async fn read_manifest(&self) -> LoadModelResult<Manifest> {
+        #[derive(Deserialize)]
+        struct Versioned {
+            manifest_version: ManifestVersion,
+        }
+
+        let Versioned { manifest_version } = self.read_vvm_json(Self::MANIFEST_FILENAME).await?;
+        if !manifest_version.is_supported() {
+            return Err(LoadModelError {
+                path: self.path.clone(),
+                context: LoadModelErrorKind::UnsupportedModelVersion {
+                    found: manifest_version,
+                    supported: supported_model_versions(),
+                },
+                source: None,
+            });
+        }
+        self.read_vvm_json(Self::MANIFEST_FILENAME).await
+    }
+    const MANIFEST_FILENAME: &str = "manifest.json";
+
     async fn read_vvm_json<T: DeserializeOwned>(&self, filename: &str) -> LoadModelResult<T> {
         let bytes = self.read_vvm_entry(filename).await?;
         serde_json::from_slice(&bytes).map_err(|source| LoadModelError {
-            path: self.reader.path().to_owned(),
+            path: self.path.clone(),
             context: LoadModelErrorKind::ReadZipEntry {
                 filename: filename.to_owned(),
             },
@@ -180,38 +493,483 @@ impl VvmEntryReader {
         })
     }
 
+    /// UTF-8のテキストファイルのエントリを読む。
+    async fn read_vvm_text(&self, filename: &str) -> LoadModelResult<String> {
+        let bytes = self.read_vvm_entry(filename).await?;
+        String::from_utf8(bytes).map_err(|source| LoadModelError {
+            path: self.path.clone(),
+            context: LoadModelErrorKind::ReadZipEntry {
+                filename: filename.to_owned(),
+            },
+            source: Some(source.into()),
+        })
+    }
+
+    /// ネットワークのエントリを読む。zstdで圧縮されている場合は展開する。
+    async fn read_vvm_model(&self, filename: &str) -> LoadModelResult<Vec<u8>> {
+        let content = self.read_vvm_entry(filename).await?;
+        self.decompress_model(filename, content).await
+    }
+
+    async fn read_optional_vvm_model(
+        &self,
+        filename: Option<&str>,
+    ) -> LoadModelResult<Option<Vec<u8>>> {
+        match filename {
+            Some(filename) => self.read_vvm_model(filename).await.map(Some),
+            None => Ok(None),
+        }
+    }
+
+    /// `content`がzstdで圧縮されていれば展開し、そうでなければそのまま返す。
+    ///
+    /// ONNXのファイル(Protocol Buffers)はzstdのマジックナンバーから始まることは無いため、先頭の
+    /// 4バイトで判別できる。
+    async fn decompress_model(&self, filename: &str, content: Vec<u8>) -> LoadModelResult<Vec<u8>> {
+        if !content.starts_with(&Self::ZSTD_MAGIC_NUMBER) {
+            return Ok(content);
+        }
+        tokio::task::spawn_blocking(move || zstd::decode_all(&*content))
+            .await
+            .map_err(anyhow::Error::from)
+            .and_then(|result| result.map_err(Into::into))
+            .map_err(|source| LoadModelError {
+                path: self.path.clone(),
+                context: LoadModelErrorKind::ReadZipEntry {
+                    filename: filename.to_owned(),
+                },
+                source: Some(source),
+            })
+    }
+    const ZSTD_MAGIC_NUMBER: [u8; 4] = [0x28, 0xb5, 0x2f, 0xfd];
+
+    /// ネットワークのエントリの、[`decompress_model`]で展開した後のサイズを求める。
+    ///
+    /// zstdで圧縮されていない場合は、エントリの先頭のみを読む。
+    ///
+    /// [`decompress_model`]: Self::decompress_model
+    async fn network_size(&self, filename: &str) -> LoadModelResult<u64> {
+        let head = self
+            .read_vvm_entry_head(filename, Self::ZSTD_MAGIC_NUMBER.len() as u64)
+            .await?;
+        if head != Self::ZSTD_MAGIC_NUMBER {
+            return Ok(self.entry_map[filename].entry.uncompressed_size().into());
+        }
+
+        struct CountingWriter(u64);
+
+        impl std::io::Write for CountingWriter {
+            fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+                self.0 += buf.len() as u64;
+                Ok(buf.len())
+            }
+
+            fn flush(&mut self) -> std::io::Result<()> {
+                Ok(())
+            }
+        }
+
+        let content = self.read_vvm_entry(filename).await?;
+        tokio::task::spawn_blocking(move || {
+            let mut counter = CountingWriter(0);
+            zstd::stream::copy_decode(&*content, &mut counter).map(|()| counter.0)
+        })
+        .await
+        .map_err(anyhow::Error::from)
+        .and_then(|result| result.map_err(Into::into))
+        .map_err(|source| LoadModelError {
+            path: self.path.clone(),
+            context: LoadModelErrorKind::ReadZipEntry {
+                filename: filename.to_owned(),
+            },
+            source: Some(source),
+        })
+    }
+
     async fn read_vvm_entry(&self, filename: &str) -> LoadModelResult<Vec<u8>> {
+        let me = self.vvm_entry(filename)?;
         (|| async {
-            let me = self
-                .entry_map
-                .get(filename)
-                .ok_or_else(|| io::Error::from(io::ErrorKind::NotFound))?;
-            let mut manifest_reader = self.reader.entry(me.index).await?;
             let mut buf = Vec::with_capacity(me.entry.uncompressed_size() as usize);
-            manifest_reader
-                .read_to_end_checked(&mut buf, &me.entry)
-                .await?;
+            match &self.reader {
+                ZipFileReader::Fs(reader) => {
+                    reader
+                        .entry(me.index)
+                        .await?
+                        .read_to_end_checked(&mut buf, &me.entry)
+                        .await?
+                }
+                ZipFileReader::Mem(content) => {
+                    seek::ZipFileReader::new(Cursor::new(content.clone()))
+                        .await?
+                        .entry(me.index)
+                        .await?
+                        .read_to_end_checked(&mut buf, &me.entry)
+                        .await?
+                }
+            };
             Ok::<_, anyhow::Error>(buf)
         })()
         .await
         .map_err(|source| LoadModelError {
-            path: self.reader.path().to_owned(),
+            path: self.path.clone(),
             context: LoadModelErrorKind::ReadZipEntry {
                 filename: filename.to_owned(),
             },
             source: Some(source),
         })
     }
+
+    /// エントリの先頭の最大`len`バイトを読む。エントリ全体を読まないため、CRCは検証しない。
+    async fn read_vvm_entry_head(&self, filename: &str, len: u64) -> LoadModelResult<Vec<u8>> {
+        let me = self.vvm_entry(filename)?;
+        (|| async {
+            let mut buf = vec![];
+            match &self.reader {
+                ZipFileReader::Fs(reader) => {
+                    Box::pin(reader.entry(me.index).await?)
+                        .take(len)
+                        .read_to_end(&mut buf)
+                        .await?
+                }
+                ZipFileReader::Mem(content) => {
+                    let mut reader = seek::ZipFileReader::new(Cursor::new(content.clone())).await?;
+                    Box::pin(reader.entry(me.index).await?)
+                        .take(len)
+                        .read_to_end(&mut buf)
+                        .await?
+                }
+            };
+            Ok::<_, anyhow::Error>(buf)
+        })()
+        .await
+        .map_err(|source| LoadModelError {
+            path: self.path.clone(),
+            context: LoadModelErrorKind::ReadZipEntry {
+                filename: filename.to_owned(),
+            },
+            source: Some(source),
+        })
+    }
+
+    fn vvm_entry(&self, filename: &str) -> LoadModelResult<&VvmEntry> {
+        self.entry_map.get(filename).ok_or_else(|| LoadModelError {
+            path: self.path.clone(),
+            context: LoadModelErrorKind::MissingEntry {
+                filename: filename.to_owned(),
+            },
+            source: None,
+        })
+    }
 }
 
 #[cfg(test)]
-mod tests {
+pub(crate) mod tests {
     use super::*;
 
+    #[rstest]
+    #[case("V1StGXR8_Z5jdHi6B-myT", true)]
+    #[case("V1StGXR8_Z5jdHi6B-my", false)]
+    #[case("V1StGXR8_Z5jdHi6B-myT!", false)]
+    #[case("V1StGXR8_Z5jdHi6B/myT", false)]
+    #[case("", false)]
+    fn voice_model_id_from_str_works(#[case] s: &str, #[case] is_valid: bool) {
+        let result = VoiceModelId::try_from(s);
+        assert_eq!(is_valid, result.is_ok(), "{result:?}");
+        if let Ok(id) = result {
+            assert_eq!(s, id.to_string());
+        }
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn generated_voice_model_id_can_be_parsed() {
+        let model = open_default_vvm_file().await;
+        let id = model.id().to_string().parse::<VoiceModelId>().unwrap();
+        assert_eq!(*model.id(), id);
+    }
+
     #[rstest]
     #[tokio::test]
     async fn get_all_models_only_load_vvm() {
         let all_models = VoiceModel::get_all_models().await;
         assert!(all_models.is_ok());
     }
+
+    #[rstest]
+    #[tokio::test]
+    async fn from_async_read_works() {
+        let content =
+            fs_err::tokio::read(concat!(env!("CARGO_WORKSPACE_DIR"), "/model/sample.vvm"))
+                .await
+                .unwrap();
+
+        let model = VoiceModel::from_async_read(&*content).await.unwrap();
+        let expected =
+            VoiceModel::from_path(concat!(env!("CARGO_WORKSPACE_DIR"), "/model/sample.vvm"))
+                .await
+                .unwrap();
+        assert_eq!(
+            serde_json::to_value(expected.metas()).unwrap(),
+            serde_json::to_value(model.metas()).unwrap(),
+        );
+        assert!(model.read_inference_models().await.is_ok());
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn verify_accepts_sample_vvm() {
+        let result =
+            VoiceModel::verify(concat!(env!("CARGO_WORKSPACE_DIR"), "/model/sample.vvm")).await;
+        assert!(result.is_ok(), "{result:?}");
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn verify_rejects_non_zip_file() {
+        let file = tempfile::NamedTempFile::new().unwrap();
+        fs_err::write(file.path(), "not a zip file").unwrap();
+
+        let err = VoiceModel::verify(file.path()).await.unwrap_err();
+        assert!(matches!(err.context(), LoadModelErrorKind::OpenZipFile));
+    }
+
+    #[rstest]
+    #[case::missing_entry(
+        |entries: &mut BTreeMap<String, Vec<u8>>, decode_filename: &str| {
+            entries.remove(decode_filename);
+        },
+        |result: LoadModelResult<()>, decode_filename: &str| {
+            assert!(matches!(
+                result.unwrap_err().context(),
+                LoadModelErrorKind::MissingEntry { filename } if filename == decode_filename,
+            ));
+        },
+    )]
+    #[case::checksum_match(
+        |entries: &mut BTreeMap<String, Vec<u8>>, decode_filename: &str| {
+            let sha256 = format!("{:x}", Sha256::digest(&entries[decode_filename]));
+            set_sha256(entries, decode_filename, &sha256);
+        },
+        |result: LoadModelResult<()>, _: &str| assert!(result.is_ok(), "{result:?}"),
+    )]
+    #[case::checksum_mismatch(
+        |entries: &mut BTreeMap<String, Vec<u8>>, decode_filename: &str| {
+            set_sha256(entries, decode_filename, &"0".repeat(64));
+        },
+        |result: LoadModelResult<()>, decode_filename: &str| {
+            assert!(matches!(
+                result.unwrap_err().context(),
+                LoadModelErrorKind::ChecksumMismatch { filename } if filename == decode_filename,
+            ));
+        },
+    )]
+    #[tokio::test]
+    async fn verify_detects_broken_entries(
+        #[case] edit: fn(&mut BTreeMap<String, Vec<u8>>, &str),
+        #[case] check: fn(LoadModelResult<()>, &str),
+    ) {
+        let (file, manifest) =
+            rewrite_sample_vvm(|entries, manifest| edit(entries, manifest.decode_filename())).await;
+
+        check(
+            VoiceModel::verify(file.path()).await,
+            manifest.decode_filename(),
+        );
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn zstd_compressed_networks_can_be_read() {
+        let expected = open_default_vvm_file()
+            .await
+            .read_inference_models()
+            .await
+            .unwrap();
+
+        let (file, _) = rewrite_sample_vvm(|entries, manifest| {
+            for filename in [
+                Some(manifest.decode_filename()),
+                manifest.predict_duration_filename().as_ref(),
+                manifest.predict_intonation_filename().as_ref(),
+            ]
+            .into_iter()
+            .flatten()
+            {
+                let content = zstd::encode_all(&*entries[filename], 0).unwrap();
+                entries.insert(filename.clone(), content);
+            }
+        })
+        .await;
+
+        assert!(VoiceModel::verify(file.path()).await.is_ok());
+        let models = VoiceModel::from_path(file.path())
+            .await
+            .unwrap()
+            .read_inference_models()
+            .await
+            .unwrap();
+        assert!(expected.decode_model() == models.decode_model());
+        assert!(expected.predict_duration_model() == models.predict_duration_model());
+        assert!(expected.predict_intonation_model() == models.predict_intonation_model());
+    }
+
+    #[rstest]
+    #[case(false)]
+    #[case(true)]
+    #[tokio::test]
+    async fn estimated_memory_is_sum_of_network_sizes(#[case] compress: bool) {
+        let (file, _) = rewrite_sample_vvm(|entries, manifest| {
+            if !compress {
+                return;
+            }
+            for filename in [
+                Some(manifest.decode_filename()),
+                manifest.predict_duration_filename().as_ref(),
+                manifest.predict_intonation_filename().as_ref(),
+            ]
+            .into_iter()
+            .flatten()
+            {
+                let content = zstd::encode_all(&*entries[filename], 0).unwrap();
+                entries.insert(filename.clone(), content);
+            }
+        })
+        .await;
+
+        let model = VoiceModel::from_path(file.path()).await.unwrap();
+        let models = model.read_inference_models().await.unwrap();
+        let expected = [
+            Some(models.decode_model()),
+            models.predict_duration_model().as_ref(),
+            models.predict_intonation_model().as_ref(),
+        ]
+        .into_iter()
+        .flatten()
+        .map(|model| model.len() as u64)
+        .sum::<u64>();
+        assert_eq!(expected, model.estimated_memory().await.unwrap());
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn unsupported_model_version_is_rejected() {
+        let (file, _) = rewrite_sample_vvm(|entries, _| {
+            let manifest = serde_json::json!({ "manifest_version": "999.0.0" });
+            entries.insert(
+                "manifest.json".to_owned(),
+                serde_json::to_vec(&manifest).unwrap(),
+            );
+        })
+        .await;
+
+        for result in [
+            VoiceModel::verify(file.path()).await,
+            VoiceModel::from_path(file.path()).await.map(|_| ()),
+        ] {
+            assert!(matches!(
+                result.unwrap_err().context(),
+                LoadModelErrorKind::UnsupportedModelVersion { found, supported }
+                    if found.raw_manifest_version() == "999.0.0"
+                        && *supported == supported_model_versions(),
+            ));
+        }
+    }
+
+    #[rstest]
+    #[case(None)]
+    #[case(Some("この音声モデルを利用する際はクレジットを表記すること。"))]
+    #[tokio::test]
+    async fn terms_works(#[case] terms: Option<&str>) {
+        let (file, _) = rewrite_sample_vvm(|entries, _| {
+            let Some(terms) = terms else {
+                return;
+            };
+            entries.insert("terms.txt".to_owned(), terms.into());
+            let mut manifest =
+                serde_json::from_slice::<serde_json::Value>(&entries["manifest.json"]).unwrap();
+            manifest["terms_filename"] = "terms.txt".into();
+            entries.insert(
+                "manifest.json".to_owned(),
+                serde_json::to_vec(&manifest).unwrap(),
+            );
+        })
+        .await;
+
+        assert!(VoiceModel::verify(file.path()).await.is_ok());
+        let model = VoiceModel::from_path(file.path()).await.unwrap();
+        assert_eq!(terms, model.terms().as_deref());
+        for speaker in model.metas() {
+            assert_eq!(terms, speaker.terms().as_deref());
+        }
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn model_without_predict_intonation_can_be_read() {
+        let (file, _) = rewrite_sample_vvm(|entries, manifest| {
+            entries.remove(manifest.predict_intonation_filename().as_ref().unwrap());
+            let mut manifest =
+                serde_json::from_slice::<serde_json::Value>(&entries["manifest.json"]).unwrap();
+            manifest
+                .as_object_mut()
+                .unwrap()
+                .remove("predict_intonation_filename");
+            entries.insert(
+                "manifest.json".to_owned(),
+                serde_json::to_vec(&manifest).unwrap(),
+            );
+        })
+        .await;
+
+        assert!(VoiceModel::verify(file.path()).await.is_ok());
+        let model = VoiceModel::from_path(file.path()).await.unwrap();
+        assert_eq!(
+            [Network::PredictDuration, Network::Decode],
+            *model.networks(),
+        );
+        let models = model.read_inference_models().await.unwrap();
+        assert!(models.predict_duration_model().is_some());
+        assert!(models.predict_intonation_model().is_none());
+    }
+
+    /// sample.vvmの各エントリを`edit`で書き換えたVVMファイルを作る。
+    pub(crate) async fn rewrite_sample_vvm(
+        edit: impl FnOnce(&mut BTreeMap<String, Vec<u8>>, &Manifest),
+    ) -> (tempfile::NamedTempFile, Manifest) {
+        let path = Path::new(concat!(env!("CARGO_WORKSPACE_DIR"), "/model/sample.vvm"));
+        let reader = VvmEntryReader::open(path).await.unwrap();
+        let manifest = reader
+            .read_vvm_json::<Manifest>("manifest.json")
+            .await
+            .unwrap();
+        let mut entries = BTreeMap::new();
+        for filename in reader.entry_map.keys() {
+            let content = reader.read_vvm_entry(filename).await.unwrap();
+            entries.insert(filename.clone(), content);
+        }
+
+        edit(&mut entries, &manifest);
+
+        let file = tempfile::NamedTempFile::new().unwrap();
+        let mut writer = async_zip::write::ZipFileWriter::new(
+            tokio::fs::File::create(file.path()).await.unwrap(),
+        );
+        for (filename, content) in entries {
+            let entry = async_zip::ZipEntryBuilder::new(filename, async_zip::Compression::Stored);
+            writer.write_entry_whole(entry, &content).await.unwrap();
+        }
+        writer.close().await.unwrap();
+
+        (file, manifest)
+    }
+
+    fn set_sha256(entries: &mut BTreeMap<String, Vec<u8>>, filename: &str, sha256: &str) {
+        let mut manifest =
+            serde_json::from_slice::<serde_json::Value>(&entries["manifest.json"]).unwrap();
+        manifest["sha256"][filename] = sha256.into();
+        entries.insert(
+            "manifest.json".to_owned(),
+            serde_json::to_vec(&manifest).unwrap(),
+        );
+    }
 }
@@ -1,14 +1,120 @@
-use std::sync::Arc;
+use std::{
+    borrow::Cow,
+    collections::BTreeMap,
+    fmt,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
 
-use crate::engine::{create_kana, parse_kana, AccentPhraseModel, OpenJtalk, SynthesisEngine};
+use futures::{
+    future::{BoxFuture, Either},
+    FutureExt as _, Stream, StreamExt as _, TryStreamExt as _,
+};
+use serde::{Deserialize, Serialize};
+use tokio::io::{AsyncWrite, AsyncWriteExt as _};
+use tracing::{info, warn};
+
+use crate::engine::{
+    create_kana, equalize, merge_short_accent_phrases, normalize, normalize_width, output_gain,
+    parse_kana, sentence_terminator, split_into_moras, split_sentences, transliterate_alphabet,
+    AccentPhraseModel, Equalizer, OpenJtalk, SynthesisEngine,
+};
 
 use super::*;
 
 /// [`Synthesizer::synthesis`]のオプション。
 ///
 /// [`Synthesizer::synthesis`]: Synthesizer::synthesis
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct SynthesisOptions {
     pub enable_interrogative_upspeak: bool,
+    /// 設定されている場合、推論に渡されたテンソルをここに記録する。
+    ///
+    /// [`Synthesizer::synthesis`]では波形の推論のみが行われるため、記録されるのは
+    /// [`CapturedTensors::decode`]のみである。既定では無効であり、その場合のオーバーヘッドは無い。
+    #[serde(skip)]
+    pub capture_tensors: Option<TensorCapture>,
+    /// 音声合成の品質。
+    #[serde(default)]
+    pub quality: SynthesisQuality,
+    /// 出力の最終段で掛けるゲイン(dB)。
+    ///
+    /// AudioQueryの`volume_scale`を反映した後、量子化の前に掛けられる。0以外の場合、振幅が1に
+    /// 近づくにつれて滑らかに圧縮されるため、ゲインを上げても音割れしにくい。既定値の0では何もしない。
+    #[serde(default)]
+    pub output_gain_db: f32,
+    /// 各アクセント句の開始位置を、WAVの`cue `チャンクにキューポイントとして書き込む。
+    ///
+    /// 波形編集ソフトなどでマーカーとして表示される。`cue `チャンクは`data`チャンクの後に置かれる
+    /// ため、これを解釈しないプレイヤーでもそのまま再生できる。
+    #[serde(default)]
+    pub emit_cue_points: bool,
+    /// 声の出し方。
+    #[serde(default)]
+    pub voice_mode: VoiceMode,
+    /// 波形の推論に使う乱数のシード。
+    ///
+    /// 確率的な要素を持つ波形生成モデルにおいて、同じAudioQueryとシードから同一の音声を得るための
+    /// ものである。現在の音声モデルの波形生成は決定的であり、この値に関わらず同じAudioQueryからは常に
+    /// 同一の音声が得られる。
+    #[serde(default)]
+    pub seed: Option<u64>,
+    /// 出力に掛けるイコライザ。
+    ///
+    /// 推論した波形に対して各帯域のフィルタを順に掛けた後、音量と`output_gain_db`を反映する。再生
+    /// 機器に合わせた簡単な音色の補正のためのものである。既定値の空の場合は何もしない。
+    #[serde(default)]
+    pub eq: Vec<BiquadBand>,
+    /// [`Synthesizer::synthesis_stream`]などで、先頭のアクセント句の音声を文全体より先に返す。
+    ///
+    /// 最初の音声が得られるまでの時間を短くする代わりに、全体の処理時間は長くなる。詳細は
+    /// [`Synthesizer::synthesis_stream`]を参照。[`Synthesizer::synthesis`]のように音声全体を一度に
+    /// 返すものでは無視される。既定では無効。
+    #[serde(default)]
+    pub prioritize_first_packet: bool,
+}
+
+/// [`SynthesisOptions::quality`]の設定値。
+///
+/// シリアライズ時は`"full"`か`"preview"`の文字列となる。
+#[derive(Default, Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SynthesisQuality {
+    /// 通常の品質。
+    #[default]
+    Full,
+    /// 抑揚やタイミングを素早く確認するための、低品質な音声合成。
+    ///
+    /// 波形の推論をフレームを間引いて行うため、波形の推論にかかる時間は`Full`の約半分となる。
+    /// 音声の長さは`Full`と同じだが、音質は明確に劣化する。最終的な出力には使わないこと。
+    Preview,
+}
+
+/// [`SynthesisOptions::voice_mode`]の設定値。
+///
+/// これは既存の音声モデルに掛けるエフェクトであり、ささやき声用の音声モデルを使うわけではない。
+/// シリアライズ時は`"normal"`か`"whisper"`の文字列となる。
+#[derive(Default, Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum VoiceMode {
+    /// 通常の声。
+    #[default]
+    Normal,
+    /// ささやき声。
+    ///
+    /// 全てのモーラの音高を0として波形を推論することで、声帯の振動による成分を取り除く。音素長と
+    /// 音量はそのまま反映されるが、AudioQueryの音高に関する指定と`enable_interrogative_upspeak`は
+    /// 意味を持たなくなる。声質は音声モデルによって大きく異なる。
+    Whisper,
+}
+
+impl VoiceMode {
+    fn apply(self, audio_query: &AudioQueryModel) -> Cow<'_, AudioQueryModel> {
+        match self {
+            Self::Normal => Cow::Borrowed(audio_query),
+            Self::Whisper => Cow::Owned(audio_query.whispered()),
+        }
+    }
 }
 
 impl AsRef<SynthesisOptions> for SynthesisOptions {
@@ -21,6 +127,14 @@ impl From<&TtsOptions> for SynthesisOptions {
     fn from(options: &TtsOptions) -> Self {
         Self {
             enable_interrogative_upspeak: options.enable_interrogative_upspeak,
+            capture_tensors: options.capture_tensors.clone(),
+            quality: SynthesisQuality::Full,
+            output_gain_db: 0.,
+            emit_cue_points: false,
+            voice_mode: VoiceMode::Normal,
+            seed: None,
+            eq: vec![],
+            prioritize_first_packet: options.prioritize_first_packet,
         }
     }
 }
@@ -28,34 +142,351 @@ impl From<&TtsOptions> for SynthesisOptions {
 /// [`Synthesizer::create_accent_phrases`]のオプション。
 ///
 /// [`Synthesizer::create_accent_phrases`]: Synthesizer::create_accent_phrases
-#[derive(Default)]
+#[derive(Default, Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
 pub struct AccentPhrasesOptions {
     /// AquesTalk風記法としてテキストを解釈する。
     pub kana: bool,
+    /// 解析の前に、半角カタカナを全角に、全角英数字を半角にする。
+    ///
+    /// 半角カタカナの後ろに続く半角の濁点・半濁点は合成する (`ﾎﾞｲｽ` → `ボイス`)。NFKCとは異なり、
+    /// これ以外の文字は変えない。全角の記号(`！`、`？`など)は文の区切りや読みに影響するため、`①`や
+    /// `㌔`のように展開すると意味が変わる文字と同様にそのまま残す。
+    ///
+    /// [`kana`]が有効なときは無視される。
+    ///
+    /// [`kana`]: Self::kana
+    pub normalize_width: bool,
+    /// アクセント句の区切り方。
+    pub phrase_segmentation: PhraseSegmentation,
+}
+
+/// [`AccentPhrasesOptions::phrase_segmentation`]の設定値。
+///
+/// テキストの解析と推論の後に、その結果のアクセント句の配列を組み替えるものである。モーラの音高・
+/// 音素長は組み替える前の推論結果がそのまま使われ、アクセント核の位置は
+/// [`AudioQueryModel::split_accent_phrase`]と[`AudioQueryModel::merge_accent_phrases`]と同じ
+/// 規則で決まる。組み替えた後のアクセント句で推論し直す場合は、[`Synthesizer::replace_mora_data`]
+/// を用いる。
+///
+/// シリアライズ時は`"default"`、`"per_mora"`、`{"min_moras": n}`のいずれかとなる。
+#[derive(Default, Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PhraseSegmentation {
+    /// OpenJTalk(もしくはAquesTalk風記法)による区切りをそのまま使う。
+    #[default]
+    Default,
+    /// 1モーラずつ区切る。
+    ///
+    /// 無音と疑問系の指定は、元のアクセント句の最後のモーラに引き継がれる。
+    PerMora,
+    /// モーラ数がこの値未満のアクセント句を、後ろのアクセント句に結合する。
+    ///
+    /// 末尾のアクセント句は前のアクセント句に結合する。無音を挟むアクセント句同士は結合しない。
+    MinMoras(usize),
+}
+
+impl PhraseSegmentation {
+    fn apply(self, accent_phrases: Vec<AccentPhraseModel>) -> Vec<AccentPhraseModel> {
+        match self {
+            Self::Default => accent_phrases,
+            Self::PerMora => split_into_moras(accent_phrases),
+            Self::MinMoras(min_moras) => merge_short_accent_phrases(accent_phrases, min_moras),
+        }
+    }
 }
 
 /// [`Synthesizer::audio_query`]のオプション。
 ///
+/// [`kana`]が無効なとき、テキストに`会議{かいぎ:1}`のようなアクセント指定が含まれていると、設定済みの
+/// ユーザー辞書に指定された単語を加えたものをその都度コンパイルし、システム辞書と共に読み込み直す。
+/// ユーザー辞書の[`ContextualWord`]がテキストに当てはまる場合も同様である。そのためこれらを含まない
+/// テキストよりも解析に大幅に時間がかかる。記法と詳細は[`Synthesizer::create_accent_phrases`]を参照。
+///
 /// [`Synthesizer::audio_query`]: Synthesizer::audio_query
-#[derive(Default)]
+/// [`kana`]: Self::kana
+/// [`ContextualWord`]: crate::ContextualWord
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
 pub struct AudioQueryOptions {
     /// AquesTalk風記法としてテキストを解釈する。
     pub kana: bool,
+    /// 解析の前に、数字や日付、通貨記号などを読み上げやすい形に展開する。
+    ///
+    /// [`kana`]が有効なときは無視される。
+    ///
+    /// [`kana`]: Self::kana
+    pub normalize_text: bool,
+    /// 解析の前に、半角カタカナを全角に、全角英数字を半角にする。
+    /// [`AccentPhrasesOptions::normalize_width`]と同じ。
+    ///
+    /// [`normalize_text`]や[`transliterate_alphabet`]よりも先に適用される。[`kana`]が有効なときは
+    /// 無視される。
+    ///
+    /// [`normalize_text`]: Self::normalize_text
+    /// [`transliterate_alphabet`]: Self::transliterate_alphabet
+    /// [`kana`]: Self::kana
+    pub normalize_width: bool,
+    /// 解析の前に、アルファベットの単語をカタカナの読みに置き換える。
+    ///
+    /// 既知の単語は単語として、それ以外は一文字ずつ綴りで読む。ユーザー辞書に登録されている単語は
+    /// 置き換えずに、ユーザー辞書の読みを優先する。発音を推定するものではないため、読みは
+    /// ベストエフォートである。
+    ///
+    /// [`kana`]が有効なときは無視される。
+    ///
+    /// [`kana`]: Self::kana
+    pub transliterate_alphabet: bool,
+    /// 入力テキストの長さ(文字数)の上限。超えた場合は[`Error::TextTooLong`]となる。
+    ///
+    /// テキストは一度にOpenJTalkで解析されるため、長すぎるテキストはメモリを大量に消費する。長い
+    /// 文章は[`Synthesizer::tts_many`]などで文ごとに分けて音声合成することを推奨する。
+    ///
+    /// 既定値は[`DEFAULT_MAX_TEXT_LENGTH`]。
+    ///
+    /// [`DEFAULT_MAX_TEXT_LENGTH`]: Self::DEFAULT_MAX_TEXT_LENGTH
+    pub max_text_length: usize,
+    /// 設定されている場合、生成するAudioQueryの[`pre_phoneme_length`](音声の前の無音時間)をこの値に
+    /// する。
+    ///
+    /// 既定では[`DEFAULT_PHONEME_LENGTH`]となる。
+    ///
+    /// [`pre_phoneme_length`]: AudioQueryModel::pre_phoneme_length
+    /// [`DEFAULT_PHONEME_LENGTH`]: Self::DEFAULT_PHONEME_LENGTH
+    pub pre_phoneme_length: Option<f32>,
+    /// 設定されている場合、生成するAudioQueryの[`post_phoneme_length`](音声の後の無音時間)をこの値
+    /// にする。
+    ///
+    /// 既定では[`DEFAULT_PHONEME_LENGTH`]となる。
+    ///
+    /// [`post_phoneme_length`]: AudioQueryModel::post_phoneme_length
+    /// [`DEFAULT_PHONEME_LENGTH`]: Self::DEFAULT_PHONEME_LENGTH
+    pub post_phoneme_length: Option<f32>,
+    /// アクセント句の区切り方。[`AccentPhrasesOptions::phrase_segmentation`]と同じ。
+    ///
+    /// 生成されるAudioQueryの[`kana`]は、組み替えた後のアクセント句から作られる。
+    ///
+    /// [`kana`]: AudioQueryModel::kana
+    pub phrase_segmentation: PhraseSegmentation,
+    /// [`Synthesizer::audio_query_with_warnings`]で警告とする音素長の閾値。
+    ///
+    /// [`Synthesizer::audio_query`]では使われない。
+    pub duration_warning_thresholds: DurationWarningThresholds,
+}
+
+impl AudioQueryOptions {
+    /// [`max_text_length`]の既定値。
+    ///
+    /// [`max_text_length`]: Self::max_text_length
+    pub const DEFAULT_MAX_TEXT_LENGTH: usize = 10_000;
+
+    /// [`pre_phoneme_length`]と[`post_phoneme_length`]が設定されていないときに使われる値(秒)。
+    ///
+    /// [`pre_phoneme_length`]: Self::pre_phoneme_length
+    /// [`post_phoneme_length`]: Self::post_phoneme_length
+    pub const DEFAULT_PHONEME_LENGTH: f32 = 0.1;
+}
+
+impl Default for AudioQueryOptions {
+    fn default() -> Self {
+        Self {
+            kana: false,
+            normalize_text: false,
+            normalize_width: false,
+            transliterate_alphabet: false,
+            max_text_length: Self::DEFAULT_MAX_TEXT_LENGTH,
+            pre_phoneme_length: None,
+            post_phoneme_length: None,
+            phrase_segmentation: PhraseSegmentation::Default,
+            duration_warning_thresholds: DurationWarningThresholds::default(),
+        }
+    }
 }
 
 impl From<&TtsOptions> for AudioQueryOptions {
     fn from(options: &TtsOptions) -> Self {
-        Self { kana: options.kana }
+        Self {
+            kana: options.kana,
+            ..Default::default()
+        }
+    }
+}
+
+/// 推論された音素長がこれを超えたときに[`SynthesisWarning`]とする、閾値(秒)。
+/// [`AudioQueryOptions::duration_warning_thresholds`]で設定する。
+///
+/// 音素長は[`AudioQueryModel::speed_scale`]を適用する前の値で比較される。
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DurationWarningThresholds {
+    /// モーラの音長(子音と母音の音長の和)の上限。
+    pub max_mora_length: f32,
+    /// 句読点などによる無音の長さの上限。
+    pub max_pause_length: f32,
+}
+
+impl DurationWarningThresholds {
+    /// `accent_phrases`のうち、閾値を超える音素長のモーラを[`SynthesisWarning`]として返す。
+    pub fn check(&self, accent_phrases: &[AccentPhraseModel]) -> Vec<SynthesisWarning> {
+        let mut warnings = vec![];
+        for (accent_phrase_index, accent_phrase) in accent_phrases.iter().enumerate() {
+            for (mora_index, mora) in accent_phrase.moras().iter().enumerate() {
+                let length = mora.consonant_length().unwrap_or(0.) + *mora.vowel_length();
+                if length > self.max_mora_length {
+                    warnings.push(SynthesisWarning::ExtremeMoraLength {
+                        accent_phrase_index,
+                        mora_index,
+                        text: mora.text().clone(),
+                        length,
+                    });
+                }
+            }
+            if let Some(pause_mora) = accent_phrase.pause_mora() {
+                let length = *pause_mora.vowel_length();
+                if length > self.max_pause_length {
+                    warnings.push(SynthesisWarning::ExtremePauseLength {
+                        accent_phrase_index,
+                        length,
+                    });
+                }
+            }
+        }
+        warnings
+    }
+}
+
+impl Default for DurationWarningThresholds {
+    fn default() -> Self {
+        Self {
+            max_mora_length: 1.,
+            max_pause_length: 3.,
+        }
     }
 }
 
 /// [`Synthesizer::tts`]のオプション。
 ///
 /// [`Synthesizer::tts`]: Synthesizer::tts
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
 pub struct TtsOptions {
     /// AquesTalk風記法としてテキストを解釈する。
     pub kana: bool,
     pub enable_interrogative_upspeak: bool,
+    /// 設定されている場合、テキスト音声合成中に推論に渡されたテンソルを全てここに記録する。
+    ///
+    /// 既定では無効であり、その場合のオーバーヘッドは無い。
+    #[serde(skip)]
+    pub capture_tensors: Option<TensorCapture>,
+    /// 設定されている場合、[`Synthesizer::tts_many`]で各文の後の無音時間を文末記号に応じて変える。
+    ///
+    /// 既定では無効であり、各文の後の無音時間は[`AudioQueryOptions::DEFAULT_PHONEME_LENGTH`]となる。
+    pub pause_model: Option<PauseModel>,
+    /// 設定されている場合、生成したAudioQueryの[`speed_scale`](全体の話速)をこの値にしてから音声
+    /// 合成する。
+    ///
+    /// 既定ではスタイルの既定値(無い場合は`1.0`)となる。
+    ///
+    /// [`speed_scale`]: AudioQueryModel::speed_scale
+    pub speed_scale: Option<f32>,
+    /// 設定されている場合、生成したAudioQueryの[`pitch_scale`](全体の音高)をこの値にしてから音声
+    /// 合成する。
+    ///
+    /// 既定ではスタイルの既定値(無い場合は`0.0`)となる。
+    ///
+    /// [`pitch_scale`]: AudioQueryModel::pitch_scale
+    pub pitch_scale: Option<f32>,
+    /// 設定されている場合、生成したAudioQueryの[`intonation_scale`](全体の抑揚)をこの値にしてから
+    /// 音声合成する。
+    ///
+    /// 既定ではスタイルの既定値(無い場合は`1.0`)となる。
+    ///
+    /// [`intonation_scale`]: AudioQueryModel::intonation_scale
+    pub intonation_scale: Option<f32>,
+    /// 設定されている場合、生成したAudioQueryの[`volume_scale`](全体の音量)をこの値にしてから音声
+    /// 合成する。
+    ///
+    /// 既定ではスタイルの既定値(無い場合は`1.0`)となる。
+    ///
+    /// [`volume_scale`]: AudioQueryModel::volume_scale
+    pub volume_scale: Option<f32>,
+    /// [`Synthesizer::tts_stream`]で、先頭のアクセント句の音声を文全体より先に返す。
+    ///
+    /// [`SynthesisOptions::prioritize_first_packet`]を参照。既定では無効。
+    pub prioritize_first_packet: bool,
+}
+
+impl TtsOptions {
+    /// `audio_query`の全体のパラメータを、このオプションで設定されているものに置き換える。
+    fn override_scales(&self, audio_query: AudioQueryModel) -> AudioQueryModel {
+        if [
+            self.speed_scale,
+            self.pitch_scale,
+            self.intonation_scale,
+            self.volume_scale,
+        ]
+        .iter()
+        .all(Option::is_none)
+        {
+            return audio_query;
+        }
+        AudioQueryModel::new(
+            audio_query.accent_phrases().clone(),
+            self.speed_scale.unwrap_or(*audio_query.speed_scale()),
+            self.pitch_scale.unwrap_or(*audio_query.pitch_scale()),
+            self.intonation_scale
+                .unwrap_or(*audio_query.intonation_scale()),
+            self.volume_scale.unwrap_or(*audio_query.volume_scale()),
+            *audio_query.pre_phoneme_length(),
+            *audio_query.post_phoneme_length(),
+            *audio_query.output_sampling_rate(),
+            *audio_query.output_stereo(),
+            audio_query.kana().clone(),
+        )
+    }
+}
+
+/// 文末記号ごとの、文の後の無音時間(秒)。[`TtsOptions::pause_model`]で設定する。
+///
+/// 文ごとに合成した音声をつなげて長い文章を読み上げる際に、区切りを自然にするためのものである。
+/// 無音時間は各文のAudioQueryの[`post_phoneme_length`]として反映される。文の途中の「、」による
+/// 無音は、これまで通りAudioQueryのアクセント句の区切りによって決まる。
+///
+/// [`post_phoneme_length`]: AudioQueryModel::post_phoneme_length
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PauseModel {
+    /// 「。」で終わる文の後の無音時間。
+    pub period: f32,
+    /// 「！」で終わる文の後の無音時間。
+    pub exclamation: f32,
+    /// 「？」で終わる文の後の無音時間。
+    pub question: f32,
+    /// 文末記号で終わらない文(テキストの最後の文など)の後の無音時間。
+    pub other: f32,
+}
+
+impl PauseModel {
+    /// `sentence`の後に置く無音時間を返す。文末記号の後の閉じ括弧や三点リーダーは無視される。
+    pub fn pause_after(&self, sentence: &str) -> f32 {
+        match sentence_terminator(sentence) {
+            Some('。') => self.period,
+            Some('！') => self.exclamation,
+            Some('？') => self.question,
+            _ => self.other,
+        }
+    }
+}
+
+impl Default for PauseModel {
+    fn default() -> Self {
+        Self {
+            period: 0.5,
+            exclamation: 0.4,
+            question: 0.4,
+            other: AudioQueryOptions::DEFAULT_PHONEME_LENGTH,
+        }
+    }
 }
 
 impl AsRef<TtsOptions> for TtsOptions {
@@ -69,41 +500,332 @@ impl Default for TtsOptions {
         Self {
             enable_interrogative_upspeak: true,
             kana: Default::default(),
+            capture_tensors: None,
+            pause_model: None,
+            speed_scale: None,
+            pitch_scale: None,
+            intonation_scale: None,
+            volume_scale: None,
+            prioritize_first_packet: false,
         }
     }
 }
 
 /// ハードウェアアクセラレーションモードを設定する設定値。
-#[derive(Default, Debug, PartialEq, Eq)]
+///
+/// シリアライズ時は`"auto"`、`"cpu"`、`"gpu"`のいずれかの文字列となる。
+#[derive(Default, Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
 pub enum AccelerationMode {
     /// 実行環境に合った適切なハードウェアアクセラレーションモードを選択する。
+    ///
+    /// "GPU"が選ばれた上でGPUでのモデルの読み込みに失敗した場合、警告をログに出力した上で"CPU"に
+    /// フォールバックする。
+    ///
+    /// `cuda`と`directml`のどちらのフィーチャも無効なビルドでは、常に"CPU"となる。
     #[default]
     Auto,
     /// ハードウェアアクセラレーションモードを"CPU"に設定する。
     Cpu,
     /// ハードウェアアクセラレーションモードを"GPU"に設定する。
+    ///
+    /// `cuda`と`directml`のどちらのフィーチャも無効なビルドでは、[`Synthesizer::new_with_initialize`]
+    /// が[`Error::GpuSupport`]を返す。
     Gpu,
 }
 
+/// 推論に使うONNX RuntimeのExecution Provider。
+///
+/// [`InitializeOptions::execution_provider`]で指定する。シリアライズ時は`"cpu"`、`"cuda"`、
+/// `"directml"`のいずれかの文字列となる。
+///
+/// このビルドで使えないものを指定した場合、[`Synthesizer::new_with_initialize`]が
+/// [`Error::GpuSupport`]を返す。
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize, derive_more::Display)]
+#[serde(rename_all = "lowercase")]
+pub enum ExecutionProvider {
+    /// CPUで推論する。常に使える。
+    #[display(fmt = "CPU")]
+    Cpu,
+    /// CUDAで推論する。`cuda`フィーチャが有効かつ`directml`フィーチャが無効なビルドで使える。
+    #[display(fmt = "CUDA")]
+    Cuda,
+    /// DirectMLで推論する。`directml`フィーチャが有効なビルドで使える。
+    #[display(fmt = "DirectML")]
+    DirectMl,
+}
+
+impl ExecutionProvider {
+    /// [`AccelerationMode::Gpu`]のときに使われるもの。フィーチャによって決まり、`cuda`と`directml`の
+    /// どちらのフィーチャも無効なビルドでは`None`となる。
+    pub(crate) fn gpu() -> Option<Self> {
+        cfg_if! {
+            if #[cfg(feature = "directml")] {
+                Some(Self::DirectMl)
+            } else if #[cfg(feature = "cuda")] {
+                Some(Self::Cuda)
+            } else {
+                None
+            }
+        }
+    }
+
+    pub(crate) fn is_gpu(self) -> bool {
+        self != Self::Cpu
+    }
+}
+
 /// [`Synthesizer::new_with_initialize`]のオプション。
 ///
 /// [`Synthesizer::new_with_initialize`]: Synthesizer::new_with_initialize
-#[derive(Default)]
+#[derive(Default, Clone, Serialize, Deserialize)]
+#[serde(default)]
 pub struct InitializeOptions {
     pub acceleration_mode: AccelerationMode,
+    /// 設定されている場合、[`acceleration_mode`]の代わりにこのExecution Providerで推論する。
+    ///
+    /// [`AccelerationMode::Auto`]とは異なり、セッションの作成に失敗してもCPUへのフォールバックは
+    /// 行わない。
+    ///
+    /// [`acceleration_mode`]: Self::acceleration_mode
+    pub execution_provider: Option<ExecutionProvider>,
+    /// 推論に使うCPUのスレッド数。0の場合はONNX Runtimeの既定値となる。
+    ///
+    /// [`intra_op_num_threads`]と[`inter_op_num_threads`]が設定されていない場合に、それぞれの値として
+    /// 使われる。
+    ///
+    /// [`intra_op_num_threads`]: Self::intra_op_num_threads
+    /// [`inter_op_num_threads`]: Self::inter_op_num_threads
     pub cpu_num_threads: u16,
+    /// ONNX Runtimeが1つの演算子を並列に実行する際のスレッド数(intra-op)。
+    ///
+    /// 1つの発話を合成する際のレイテンシに直接効くのはこちらであり、物理コア数程度にするとよい。
+    pub intra_op_num_threads: Option<u16>,
+    /// ONNX Runtimeが独立した演算子を並列に実行する際のスレッド数(inter-op)。
+    ///
+    /// 本ライブラリのセッションは演算子を逐次実行するため、1つの発話のレイテンシにはほとんど
+    /// 影響しない。他の処理とCPUを分け合う場合は1にするとよい。
+    pub inter_op_num_threads: Option<u16>,
+    /// CPUでの推論中、非正規化数を0として扱う(x86とx86-64ではFTZとDAZ、AArch64ではFZ)。
+    ///
+    /// 一部のCPUでは非正規化数の演算が極端に遅く、波形の推論のレイテンシが突発的に大きくなることが
+    /// ある。有効にするとこれを避けられるが、出力される音声の最下位ビットがごくわずかに変わりうる。
+    ///
+    /// 設定は推論を呼び出すスレッドに対して行われ、ONNX Runtimeのスレッドプールのスレッドには及ばない。
+    /// 効果を確実にするには[`intra_op_num_threads`]を1にする。
+    ///
+    /// [`intra_op_num_threads`]: Self::intra_op_num_threads
+    pub flush_denormals: bool,
+    /// 推論(ONNX Runtimeのセッションの実行)が失敗したときに、再試行する回数。
+    ///
+    /// GPUのドライバなどで一時的に推論が失敗する環境向けのものである。再試行の前には10ミリ秒から
+    /// 倍々に待ち、再試行するごとに警告をログに出力する。全て失敗した場合は[`Error::InferenceFailed`]
+    /// を返す。スタイルIDが不正な場合など、推論を行う前に起きたエラーは再試行しない。
+    ///
+    /// 既定値は0であり、再試行しない。
+    pub inference_retries: u8,
     pub load_all_models: bool,
+    /// [`load_all_models`]が有効なときに、読み込みの進捗を受け取るコールバック。
+    ///
+    /// 引数は[`Synthesizer::load_voice_model_with_progress`]と同じく
+    /// `(読み込み済みのネットワーク数, 全体のネットワーク数)`であり、全ての音声モデルを通した値となる。
+    ///
+    /// シリアライズの対象にはならない。
+    ///
+    /// [`load_all_models`]: Self::load_all_models
+    #[serde(skip)]
+    pub load_all_models_progress: Option<Arc<dyn Fn(usize, usize) + Send + Sync>>,
+    /// 設定されている場合、[`Synthesizer::new_with_initialize`]に渡された[`OpenJtalk`]にこのユーザー
+    /// 辞書を適用する。
+    ///
+    /// [`OpenJtalk::use_user_dict`]と同じく、`OpenJtalk`に既に設定されていたユーザー辞書は置き換え
+    /// られる。そのため`OpenJtalk`を共有している場合、他の`Synthesizer`にも影響する。
+    ///
+    /// シリアライズの対象にはならない。
+    #[serde(skip)]
+    pub user_dict: Option<Arc<UserDict>>,
+}
+
+impl fmt::Debug for InitializeOptions {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("InitializeOptions")
+            .field("acceleration_mode", &self.acceleration_mode)
+            .field("execution_provider", &self.execution_provider)
+            .field("cpu_num_threads", &self.cpu_num_threads)
+            .field("intra_op_num_threads", &self.intra_op_num_threads)
+            .field("inter_op_num_threads", &self.inter_op_num_threads)
+            .field("flush_denormals", &self.flush_denormals)
+            .field("inference_retries", &self.inference_retries)
+            .field("load_all_models", &self.load_all_models)
+            .field(
+                "load_all_models_progress",
+                &self.load_all_models_progress.as_ref().map(|_| ".."),
+            )
+            .field("user_dict", &self.user_dict)
+            .finish()
+    }
+}
+
+/// `load_all_models_progress`と`user_dict`は、同一のものを指しているときに等しいとみなす。
+impl PartialEq for InitializeOptions {
+    fn eq(&self, other: &Self) -> bool {
+        self.acceleration_mode == other.acceleration_mode
+            && self.execution_provider == other.execution_provider
+            && self.cpu_num_threads == other.cpu_num_threads
+            && self.intra_op_num_threads == other.intra_op_num_threads
+            && self.inter_op_num_threads == other.inter_op_num_threads
+            && self.flush_denormals == other.flush_denormals
+            && self.inference_retries == other.inference_retries
+            && self.load_all_models == other.load_all_models
+            && match (
+                &self.load_all_models_progress,
+                &other.load_all_models_progress,
+            ) {
+                (Some(a), Some(b)) => Arc::as_ptr(a).cast::<()>() == Arc::as_ptr(b).cast::<()>(),
+                (None, None) => true,
+                _ => false,
+            }
+            && match (&self.user_dict, &other.user_dict) {
+                (Some(a), Some(b)) => Arc::ptr_eq(a, b),
+                (None, None) => true,
+                _ => false,
+            }
+    }
+}
+
+/// [`Synthesizer::benchmark`]の結果。
+#[derive(Clone, Debug, Default, PartialEq, Serialize)]
+pub struct BenchmarkReport {
+    /// 計測したテキストの数。
+    pub num_samples: usize,
+    /// 1テキストあたりのテキスト音声合成にかかった時間の平均。
+    pub mean_latency: Duration,
+    /// 1テキストあたりのテキスト音声合成にかかった時間の中央値。
+    pub median_latency: Duration,
+    /// 1テキストあたりのテキスト音声合成にかかった時間の99パーセンタイル。
+    pub p99_latency: Duration,
+    /// 1秒あたりに処理できたテキストの文字数。
+    pub chars_per_sec: f64,
+    /// 処理にかかった時間の合計を、生成された音声の長さの合計で割ったもの。
+    ///
+    /// 1未満であれば実時間よりも速く音声を生成できている。
+    pub realtime_factor: f64,
+}
+
+/// [`Synthesizer::text_stats`]の結果。
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct TextStats {
+    /// モーラの数。句読点による無音は含まない。
+    pub mora_count: usize,
+    /// アクセント句の数。
+    pub accent_phrase_count: usize,
+    /// テキスト音声合成したときの音声の長さの見積もり(秒)。
+    pub estimated_seconds: f32,
+}
+
+/// [`Synthesizer::synthesis_with_metrics`]が返す、音声の計測結果。
+///
+/// 音量と[`SynthesisOptions::output_gain_db`]を適用した後、16bitに量子化する前の音声から計測する。
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct SynthesisMetrics {
+    /// 量子化の際にクリッピングされた(絶対値が`1`を超えていた)サンプルの数。
+    pub clipped_sample_count: usize,
+    /// 最大の振幅をdBFSで表したもの。`0`を超える場合はクリッピングが起きている。無音の場合は負の
+    /// 無限大となる。
+    pub peak_dbfs: f32,
+}
+
+/// [`Synthesizer::synthesis_described`]が返す、WAVデータとその形式。
+#[derive(Clone, Debug, PartialEq)]
+pub struct AudioClip {
+    /// WAVデータ。[`Synthesizer::synthesis`]が返すものと同じ。
+    pub bytes: Vec<u8>,
+    /// サンプリングレート。
+    pub sampling_rate: u32,
+    /// チャンネル数。
+    pub channels: u16,
+    /// 量子化ビット数。
+    pub bit_depth: u16,
+    /// 音声の長さ(秒)。
+    pub duration_secs: f32,
+}
+
+/// [`Synthesizer::audio_query_with_warnings`]が返す、推論結果に対する警告。
+///
+/// 警告は報告のためだけのものであり、AudioQueryの内容や合成される音声には影響しない。
+///
+/// シリアライズ時は次のようになる。
+///
+/// ```json
+/// { "kind": "extreme_mora_length", "accent_phrase_index": 0, "mora_index": 2, "text": "ア", "length": 4.2 }
+/// ```
+#[derive(Clone, Debug, PartialEq, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum SynthesisWarning {
+    /// モーラの音長が[`DurationWarningThresholds::max_mora_length`]を超えている。
+    ExtremeMoraLength {
+        accent_phrase_index: usize,
+        mora_index: usize,
+        /// モーラの文字。
+        text: String,
+        /// 子音と母音の音長の和(秒)。
+        length: f32,
+    },
+    /// アクセント句の後の無音が[`DurationWarningThresholds::max_pause_length`]を超えている。
+    ExtremePauseLength {
+        accent_phrase_index: usize,
+        /// 無音の長さ(秒)。
+        length: f32,
+    },
 }
 
 /// 音声シンセサイザ。
+///
+/// # 並行性
+///
+/// `Synthesizer`は`Send + Sync + 'static`であり、`Arc<Synthesizer>`として複数のスレッドやタスクで
+/// 共有できる。全てのメソッドは`&self`で呼べ、どの組み合わせで同時に呼んでもよい。
+///
+/// - 推論は音声モデルのネットワーク(セッション)ごとに排他される。同じ音声モデルの同じネットワークを
+///   使う推論は順番に行われ、異なる音声モデルの推論は並行して行われる。推論は
+///   [`tokio::task::spawn_blocking`]で行うため、非同期ランタイムのワーカースレッドを塞がない。
+/// - [`load_voice_model`]と[`unload_voice_model`]は、他のメソッドと同時に呼んでもよい。読み込みの
+///   解除は、既に始まっている推論には影響しない。
+/// - テキストの解析は、[`OpenJtalk`]ごとに排他される。
+///
+/// [`load_voice_model`]: Self::load_voice_model
+/// [`unload_voice_model`]: Self::unload_voice_model
 pub struct Synthesizer {
     synthesis_engine: SynthesisEngine,
-    use_gpu: bool,
+    /// [`Synthesizer::preview`]の結果。音声モデルの読み込みを解除したときに破棄される。
+    previews: Mutex<BTreeMap<StyleId, Vec<u8>>>,
 }
 
+// `Synthesizer`と`OpenJtalk`が`Arc`で共有できることを、コンパイル時に確かめる
+const _: fn() = || {
+    fn assert_send_sync<T: Send + Sync + 'static>() {}
+    assert_send_sync::<Synthesizer>();
+    assert_send_sync::<SynthesizerHandle>();
+    assert_send_sync::<OpenJtalk>();
+};
+
 impl Synthesizer {
+    /// 合成される音声の既定のサンプリングレート。
+    ///
+    /// [`audio_query`]が返すAudioQueryの`output_sampling_rate`はこの値となる。
+    ///
+    /// [`audio_query`]: Self::audio_query
+    pub const DEFAULT_SAMPLING_RATE: u32 = SynthesisEngine::DEFAULT_SAMPLING_RATE;
+
+    /// [`preview`]で読み上げるテキスト。
+    ///
+    /// [`preview`]: Self::preview
+    pub const PREVIEW_TEXT: &'static str = "こんにちは、よろしくお願いします。";
+
     /// `Synthesizer`をコンストラクトする。
     ///
+    /// Windowsでは、DirectMLで推論する場合に限り、検出されたGPUの一覧をログに出力する。
+    ///
     /// # Example
     ///
     #[cfg_attr(windows, doc = "```no_run")] // https://github.com/VOICEVOX/voicevox_core/issues/537
@@ -134,45 +856,78 @@ impl Synthesizer {
         open_jtalk: Arc<OpenJtalk>,
         options: &InitializeOptions,
     ) -> Result<Self> {
-        #[cfg(windows)]
-        list_windows_video_cards();
-        let use_gpu = match options.acceleration_mode {
-            AccelerationMode::Auto => {
+        let execution_provider = match (options.execution_provider, options.acceleration_mode) {
+            (Some(execution_provider), _) => execution_provider,
+            (None, AccelerationMode::Auto) => {
                 let supported_devices = SupportedDevices::create()?;
-
-                cfg_if! {
-                    if #[cfg(feature="directml")]{
-                        *supported_devices.dml()
-
-                    } else {
-                        *supported_devices.cuda()
-                    }
+                match ExecutionProvider::gpu() {
+                    Some(gpu) if supported_devices.supports(gpu) => gpu,
+                    _ => ExecutionProvider::Cpu,
                 }
             }
-            AccelerationMode::Cpu => false,
-            AccelerationMode::Gpu => true,
+            (None, AccelerationMode::Cpu) => ExecutionProvider::Cpu,
+            (None, AccelerationMode::Gpu) => ExecutionProvider::gpu().ok_or(Error::GpuSupport(
+                GpuUnsupportedReason::NotBuiltWithGpuSupport,
+            ))?,
         };
+        info!(
+            "ハードウェアアクセラレーションモードとして{}が選択されました ({execution_provider})",
+            if execution_provider.is_gpu() {
+                "GPU"
+            } else {
+                "CPU"
+            },
+        );
+        // DirectMLを使わない場合、GPUの一覧は意味を持たないため列挙しない
+        #[cfg(windows)]
+        if execution_provider == ExecutionProvider::DirectMl {
+            list_windows_video_cards();
+        }
+
+        if let Some(user_dict) = &options.user_dict {
+            open_jtalk.use_user_dict(user_dict)?;
+        }
 
         Ok(Self {
             synthesis_engine: SynthesisEngine::new(
-                InferenceCore::new_with_initialize(
-                    use_gpu,
-                    options.cpu_num_threads,
-                    options.load_all_models,
-                )
-                .await?,
+                InferenceCore::new_with_initialize(execution_provider, options).await?,
                 open_jtalk,
             ),
-            use_gpu,
+            previews: Default::default(),
         })
     }
 
     /// ハードウェアアクセラレーションがGPUモードか判定する。
+    ///
+    /// [`AccelerationMode::Auto`]でGPUからCPUへのフォールバックが起きた後は`false`を返す。
     pub fn is_gpu_mode(&self) -> bool {
-        self.use_gpu
+        self.synthesis_engine.inference_core().is_gpu_mode()
+    }
+
+    /// 実際に使われているハードウェアアクセラレーションモードを返す。
+    ///
+    /// [`AccelerationMode::Auto`]を指定した場合でも、それがどちらに決まったかに応じて
+    /// [`AccelerationMode::Cpu`]か[`AccelerationMode::Gpu`]を返す。GPUからCPUへのフォールバックが
+    /// 起きた後は[`AccelerationMode::Cpu`]を返す。
+    pub fn effective_acceleration_mode(&self) -> AccelerationMode {
+        if self.is_gpu_mode() {
+            AccelerationMode::Gpu
+        } else {
+            AccelerationMode::Cpu
+        }
+    }
+
+    /// 重いモデルの推論に実際に使われている[`ExecutionProvider`]を返す。
+    ///
+    /// GPUからCPUへのフォールバックが起きた後は[`ExecutionProvider::Cpu`]を返す。
+    pub fn execution_provider(&self) -> ExecutionProvider {
+        self.synthesis_engine.inference_core().execution_provider()
     }
 
     /// 音声モデルを読み込む。
+    ///
+    /// 読み込み済みの音声モデルの一覧がロックされるのは、読み込みの開始時と完了時の一瞬のみである。
+    /// そのため読み込みの間も、既に読み込まれているスタイルでの音声合成は待たされずに行われる。
     pub async fn load_voice_model(&self, model: &VoiceModel) -> Result<()> {
         self.synthesis_engine
             .inference_core()
@@ -181,11 +936,68 @@ impl Synthesizer {
         Ok(())
     }
 
+    /// 音声モデルを読み込み、その進捗をコールバックで通知する。
+    ///
+    /// `progress`には推論用のネットワークを1つ読み込むごとに
+    /// `(読み込み済みのネットワーク数, 全体のネットワーク数)`が渡される。最後の呼び出しは読み込みが
+    /// 完了したときであり、この関数から戻った後に呼ばれることはない。読み込みに失敗した場合、
+    /// 最後まで呼ばれずにエラーが返る。
+    pub async fn load_voice_model_with_progress(
+        &self,
+        model: &VoiceModel,
+        progress: impl Fn(usize, usize),
+    ) -> Result<()> {
+        self.synthesis_engine
+            .inference_core()
+            .load_model_with_progress(model, progress)
+            .await?;
+        Ok(())
+    }
+
+    /// 制限時間付きで音声モデルを読み込む。
+    ///
+    /// `timeout`を過ぎた場合、[`LoadModelErrorKind::Timeout`]のエラーを返す。制限時間の判定はZIPの
+    /// 読み込みの後と、推論用のネットワークの読み込みの合間に行われるため、実際に戻るまでには
+    /// `timeout`を多少超過しうる。タイムアウトした場合、音声モデルは読み込まれていない状態となるため、
+    /// そのまま再試行できる。
+    pub async fn load_voice_model_with_timeout(
+        &self,
+        model: &VoiceModel,
+        timeout: Duration,
+    ) -> Result<()> {
+        self.synthesis_engine
+            .inference_core()
+            .load_model_with_timeout(model, timeout)
+            .await?;
+        Ok(())
+    }
+
     /// 音声モデルの読み込みを解除する。
     pub fn unload_voice_model(&self, voice_model_id: &VoiceModelId) -> Result<()> {
         self.synthesis_engine
             .inference_core()
-            .unload_model(voice_model_id)
+            .unload_model(voice_model_id)?;
+        self.previews.lock().unwrap().clear();
+        Ok(())
+    }
+
+    /// 読み込んでいる全ての音声モデルの読み込みを解除する。
+    ///
+    /// 推論用のセッションは、実行中の推論が終わり次第破棄される。[`OpenJtalk`]の辞書は解放されない。
+    pub fn unload_all_voice_models(&self) {
+        self.synthesis_engine.inference_core().unload_all_models();
+        self.previews.lock().unwrap().clear();
+    }
+
+    /// 破棄されていない推論用のセッションの数を返す。
+    ///
+    /// `session-accounting`フィーチャが有効なときのみ使える、リソースのリークを調べるための関数である。
+    /// このフィーチャが有効な場合、`Synthesizer`の破棄時にセッションが残っていれば警告が出力される。
+    #[cfg(any(test, feature = "session-accounting"))]
+    pub fn loaded_session_count(&self) -> usize {
+        self.synthesis_engine
+            .inference_core()
+            .loaded_session_count()
     }
 
     /// 指定したIDの音声モデルが読み込まれているか判定する。
@@ -202,30 +1014,443 @@ impl Synthesizer {
             .is_model_loaded_by_style_id(style_id)
     }
 
+    /// AudioQueryから`style_id`のスタイルで音声合成できるかどうかを、推論を行わずに検証する。
+    ///
+    /// 多数の音声合成を始める前に、失敗するものが無いかを安価に確かめるためのものである。
+    ///
+    /// # Errors
+    ///
+    /// - `style_id`のスタイルを含む音声モデルが読み込まれていない場合、[`Error::InvalidStyleId`]を
+    ///   返す。
+    /// - 音声合成に使えないアクセント句が含まれる場合、[`Error::InconsistentAccentPhrase`]を返す。
+    pub fn can_synthesize(&self, audio_query: &AudioQueryModel, style_id: StyleId) -> Result<()> {
+        if !self.is_loaded_model_by_style_id(style_id) {
+            return Err(Error::InvalidStyleId { style_id });
+        }
+        audio_query.validate()
+    }
+
     /// 今読み込んでいる音声モデルのメタ情報を返す。
     pub fn metas(&self) -> VoiceModelMeta {
         self.synthesis_engine.inference_core().metas()
     }
 
+    /// [`metas`]における話者の位置とその話者内でのスタイルの位置から、スタイルIDを求める。
+    ///
+    /// 話者やスタイルをスタイルIDではなく位置で指定していた、古い設定からの移行のためのものである。
+    /// 位置は読み込んでいる音声モデルによって変わりうる。範囲外の場合は`None`を返す。
+    ///
+    /// [`metas`]: Self::metas
+    pub fn style_id_from_indices(
+        &self,
+        speaker_index: usize,
+        style_index: usize,
+    ) -> Option<StyleId> {
+        let metas = self.metas();
+        let style = metas.get(speaker_index)?.styles().get(style_index)?;
+        Some(*style.id())
+    }
+
+    /// [`style_id_from_indices`]の逆で、スタイルIDから話者の位置とスタイルの位置を求める。
+    ///
+    /// `style_id`のスタイルが読み込まれていない場合は`None`を返す。
+    ///
+    /// [`style_id_from_indices`]: Self::style_id_from_indices
+    pub fn indices_from_style_id(&self, style_id: StyleId) -> Option<(usize, usize)> {
+        self.metas()
+            .iter()
+            .enumerate()
+            .find_map(|(speaker_index, speaker)| {
+                let style_index = speaker
+                    .styles()
+                    .iter()
+                    .position(|style| *style.id() == style_id)?;
+                Some((speaker_index, style_index))
+            })
+    }
+
     /// AudioQueryから音声合成を行う。
+    ///
+    /// AudioQueryが[実質的に無音]である場合、その理由を警告としてログに出力する。
+    ///
+    /// [実質的に無音]: AudioQueryModel::is_effectively_silent
     pub async fn synthesis(
         &self,
         audio_query: &AudioQueryModel,
         style_id: StyleId,
         options: &SynthesisOptions,
     ) -> Result<Vec<u8>> {
-        self.synthesis_engine
-            .synthesis_wave_format(audio_query, style_id, options.enable_interrogative_upspeak)
-            .await
+        let (wav, _) = self
+            .synthesis_with_metrics(audio_query, style_id, options)
+            .await?;
+        Ok(wav)
     }
 
-    #[doc(hidden)]
-    pub async fn predict_duration(
+    /// [`synthesis`]に加えて、クリッピングしたサンプルの数などの[`SynthesisMetrics`]を返す。
+    ///
+    /// 計測は量子化と同じループで行われるため、追加の推論やコピーは行われない。
+    ///
+    /// [`synthesis`]: Self::synthesis
+    pub async fn synthesis_with_metrics(
         &self,
-        phoneme_vector: &[i64],
+        audio_query: &AudioQueryModel,
         style_id: StyleId,
-    ) -> Result<Vec<f32>> {
-        self.synthesis_engine
+        options: &SynthesisOptions,
+    ) -> Result<(Vec<u8>, SynthesisMetrics)> {
+        if let Some(reason) = audio_query.silence_reason() {
+            warn!("音声合成の結果は実質的に無音となります: {reason}");
+        }
+        let audio_query = options.voice_mode.apply(audio_query);
+        let audio_query = &*audio_query;
+        TensorCapture::scope(options.capture_tensors.as_ref(), async {
+            let engine = &self.synthesis_engine;
+            let upspeak = options.enable_interrogative_upspeak;
+            let mut wave = match options.quality {
+                SynthesisQuality::Full => engine.synthesis(audio_query, style_id, upspeak).await?,
+                SynthesisQuality::Preview => {
+                    engine
+                        .synthesis_preview(audio_query, style_id, upspeak)
+                        .await?
+                }
+            };
+            equalize(
+                &options.eq,
+                SynthesisEngine::DEFAULT_SAMPLING_RATE,
+                &mut wave,
+            );
+            let cue_points = if options.emit_cue_points {
+                SynthesisEngine::accent_phrase_cue_points(audio_query, upspeak)
+            } else {
+                vec![]
+            };
+            Ok(SynthesisEngine::to_wav_with_metrics(
+                audio_query,
+                &wave,
+                options.output_gain_db,
+                &cue_points,
+            ))
+        })
+        .await
+    }
+
+    /// [`synthesis`]を行い、WAVデータをそのサンプリングレートやチャンネル数などと共に返す。
+    ///
+    /// 形式はAudioQueryの[`output_sampling_rate`]と[`output_stereo`]から決まるため、WAVデータの
+    /// ヘッダを読み直す必要は無い。音声の長さも、PCMのバイト数とその形式から求める。
+    ///
+    /// # Errors
+    ///
+    /// [`output_sampling_rate`]が24000の正の倍数でない場合、推論を行う前に
+    /// [`Error::UnsupportedSamplingRate`]を返す。
+    ///
+    /// [`synthesis`]: Self::synthesis
+    /// [`output_sampling_rate`]: AudioQueryModel::output_sampling_rate
+    /// [`output_stereo`]: AudioQueryModel::output_stereo
+    pub async fn synthesis_described(
+        &self,
+        audio_query: &AudioQueryModel,
+        style_id: StyleId,
+        options: &SynthesisOptions,
+    ) -> Result<AudioClip> {
+        let sampling_rate = *audio_query.output_sampling_rate();
+        if sampling_rate == 0 || sampling_rate % SynthesisEngine::DEFAULT_SAMPLING_RATE != 0 {
+            return Err(Error::UnsupportedSamplingRate { sampling_rate });
+        }
+        let channels = if *audio_query.output_stereo() { 2 } else { 1 };
+        let bit_depth = SynthesisEngine::BIT_DEPTH;
+
+        let pcm_len = SynthesisEngine::pcm_len(
+            audio_query,
+            SynthesisEngine::wave_len(
+                &options.voice_mode.apply(audio_query),
+                options.enable_interrogative_upspeak,
+            ),
+        );
+        let byte_rate = sampling_rate * u32::from(channels) * u32::from(bit_depth / 8);
+        let duration_secs = pcm_len as f32 / byte_rate as f32;
+
+        let bytes = self.synthesis(audio_query, style_id, options).await?;
+        Ok(AudioClip {
+            bytes,
+            sampling_rate,
+            channels,
+            bit_depth,
+            duration_secs,
+        })
+    }
+
+    /// アクセント句の配列から音声合成を行う。
+    ///
+    /// アクセント句と`prosody`から[`AudioQueryModel::from_accent_phrases`]でAudioQueryを組み立て、
+    /// [`synthesis`]を行う。
+    ///
+    /// # Errors
+    ///
+    /// 音声合成に使えないアクセント句が含まれる場合、推論を行う前に
+    /// [`Error::InconsistentAccentPhrase`]を返す。
+    ///
+    /// [`synthesis`]: Self::synthesis
+    pub async fn synthesis_from_accent_phrases(
+        &self,
+        accent_phrases: &[AccentPhraseModel],
+        style_id: StyleId,
+        prosody: &ProsodyParams,
+        options: &SynthesisOptions,
+    ) -> Result<Vec<u8>> {
+        let audio_query = AudioQueryModel::from_accent_phrases(accent_phrases.to_vec(), prosody)?;
+        self.synthesis(&audio_query, style_id, options).await
+    }
+
+    /// AudioQueryから音声合成を行い、その音声をアクセント句ごとに少しずつ読み出す[`SampleReader`]を
+    /// 返す。
+    ///
+    /// 音声全体を一度に保持する[`synthesis`]とは異なり、保持するのは一度に推論するアクセント句1つ分の
+    /// 音声のみである。そのため固定長のバッファに音声を流し込むような用途に向く。
+    ///
+    /// 読み出されるのは[`SampleReader::SAMPLING_RATE`]のモノラルの音声である。`options`の`eq`、
+    /// `output_gain_db`、`voice_mode`は[`synthesis`]と同様に反映され、`eq`はアクセント句の境界を
+    /// 越えて連続して掛けられる。出力サンプリングレートとステレオ出力の指定、及び`options`の
+    /// `quality`は無視される。
+    ///
+    /// アクセント句ごとに推論するため、前後の文脈が無い分、アクセント句の境界付近の波形は
+    /// [`synthesis`]の結果とわずかに異なりうる。
+    ///
+    /// [`synthesis`]: Self::synthesis
+    pub fn synthesis_samples(
+        &self,
+        audio_query: &AudioQueryModel,
+        style_id: StyleId,
+        options: &SynthesisOptions,
+    ) -> SampleReader<'_> {
+        let audio_query = options.voice_mode.apply(audio_query);
+        let audio_query = &*audio_query;
+        let upspeak = options.enable_interrogative_upspeak;
+        let (f0, flatten_phoneme) = SynthesisEngine::decode_inputs(audio_query, upspeak);
+        SampleReader {
+            synthesis_engine: &self.synthesis_engine,
+            style_id,
+            volume_scale: *audio_query.volume_scale(),
+            output_gain_db: options.output_gain_db,
+            equalizer: Equalizer::new(&options.eq, SampleReader::SAMPLING_RATE),
+            capture_tensors: options.capture_tensors.clone(),
+            f0,
+            flatten_phoneme,
+            frame_ends: SynthesisEngine::accent_phrase_frame_ends(audio_query, upspeak).into_iter(),
+            frame_start: 0,
+            buffer: vec![],
+            position: 0,
+        }
+    }
+
+    /// AudioQueryから音声合成を行い、ヘッダの無い16bitのリニアPCMをチャンクに分けて返す。
+    ///
+    /// チャンクの形式は[`synthesis`]が返すWAVのdataチャンクと同じである。既定では音声全体を推論した後に
+    /// 1つのチャンクとして返し、その内容は[`synthesis`]の結果のdataチャンクとバイト単位で一致する。
+    ///
+    /// `options`の[`prioritize_first_packet`]が有効な場合は、先頭のアクセント句の音声を先に推論し、
+    /// 1つ目のチャンクとして返す。文全体の波形の推論はそれと並行して行い、先頭のアクセント句より後ろの
+    /// 部分を2つ目のチャンクとして返す。このとき:
+    ///
+    /// - 最初の音声が得られるまでの時間は先頭のアクセント句の長さにおおよそ比例し、文全体の長さには
+    ///   よらない。ただし音素長と音高の推論は文全体に対して行う必要があるため、その時間は短縮されない。
+    /// - 先頭のアクセント句と数フレームの文脈を余分に推論するため、全体の処理時間は長くなる。推論を
+    ///   並行して実行できないデバイスでは、2つ目のチャンクは既定の場合よりその分遅れて得られる。
+    /// - 連結したものの長さと2つ目のチャンクは[`synthesis`]の結果と一致する。1つ目のチャンクは後続の
+    ///   文脈を一部欠いて推論されるため、波形が[`synthesis`]の結果とわずかに異なりうる。
+    ///
+    /// アクセント句が1つ以下の場合は、[`prioritize_first_packet`]に関わらず1つのチャンクで返す。
+    ///
+    /// [`synthesis`]: Self::synthesis
+    /// [`prioritize_first_packet`]: SynthesisOptions::prioritize_first_packet
+    pub fn synthesis_stream<'a>(
+        &'a self,
+        audio_query: &AudioQueryModel,
+        style_id: StyleId,
+        options: &SynthesisOptions,
+    ) -> impl Stream<Item = Result<Vec<u8>>> + 'a {
+        self.pcm_stream(audio_query.clone(), style_id, options)
+    }
+
+    /// AudioQueryから音声合成を行い、WAVを`writer`に書き込む。
+    ///
+    /// ヘッダを書き込んだ後、[`synthesis_stream`]と同じチャンクを推論できた順に書き込むため、WAV全体を
+    /// メモリ上に保持しない。`data`チャンクの長さは推論を行わずにAudioQueryから求める。書き込まれる
+    /// WAVは[`synthesis`]の結果(`cue `チャンクを除く)と一致し、`prioritize_first_packet`が有効な場合の
+    /// 違いは[`synthesis_stream`]と同様である。`options`の`quality`、`emit_cue_points`、
+    /// `capture_tensors`は無視される。
+    ///
+    /// 書き込みの最後に`writer`をフラッシュする。
+    ///
+    /// # Errors
+    ///
+    /// 推論に失敗した場合はそのエラーを、書き込みに失敗した場合は[`Error::WriteOutput`]を返す。途中で
+    /// 失敗した場合、`writer`にはそこまでの内容が残る。その`data`チャンクはヘッダに記した長さに満たない
+    /// ため、不完全なWAVであることは[`wav::duration_secs`]が`None`を返すことなどで判別できる。
+    ///
+    /// [`synthesis_stream`]: Self::synthesis_stream
+    /// [`synthesis`]: Self::synthesis
+    /// [`wav::duration_secs`]: crate::wav::duration_secs
+    pub async fn synthesis_to_writer<W: AsyncWrite + Unpin + ?Sized>(
+        &self,
+        audio_query: &AudioQueryModel,
+        style_id: StyleId,
+        options: &SynthesisOptions,
+        writer: &mut W,
+    ) -> Result<()> {
+        let wave_len = SynthesisEngine::wave_len(
+            &options.voice_mode.apply(audio_query),
+            options.enable_interrogative_upspeak,
+        );
+        let pcm_len = SynthesisEngine::pcm_len(audio_query, wave_len);
+        let header = SynthesisEngine::wav_header(audio_query, pcm_len as u32, 0);
+        writer
+            .write_all(&header)
+            .await
+            .map_err(Error::WriteOutput)?;
+
+        let chunks = self.pcm_stream(audio_query.clone(), style_id, options);
+        futures::pin_mut!(chunks);
+        let mut written = 0;
+        while let Some(chunk) = chunks.try_next().await? {
+            writer.write_all(&chunk).await.map_err(Error::WriteOutput)?;
+            written += chunk.len();
+        }
+        debug_assert_eq!(pcm_len, written);
+
+        writer.flush().await.map_err(Error::WriteOutput)
+    }
+
+    /// テキスト音声合成を行い、[`synthesis_stream`]と同様にPCMをチャンクに分けて返す。
+    ///
+    /// `options`の[`prioritize_first_packet`]が有効な場合は、テキストの解析と音素長・音高の推論を文全体
+    /// に対して行った上で、先頭のアクセント句の音声を先に返す。チャンクと[`tts`]の結果の関係は
+    /// [`synthesis_stream`]と同様である。
+    ///
+    /// [`synthesis_stream`]: Self::synthesis_stream
+    /// [`prioritize_first_packet`]: TtsOptions::prioritize_first_packet
+    /// [`tts`]: Self::tts
+    pub fn tts_stream<'a>(
+        &'a self,
+        text: &'a str,
+        style_id: StyleId,
+        options: &'a TtsOptions,
+    ) -> impl Stream<Item = Result<Vec<u8>>> + 'a {
+        futures::stream::once(self.audio_query(text, style_id, &AudioQueryOptions::from(options)))
+            .map_ok(move |audio_query| {
+                self.pcm_stream(
+                    options.override_scales(audio_query),
+                    style_id,
+                    &SynthesisOptions::from(options),
+                )
+            })
+            .try_flatten()
+    }
+
+    fn pcm_stream(
+        &self,
+        audio_query: AudioQueryModel,
+        style_id: StyleId,
+        options: &SynthesisOptions,
+    ) -> impl Stream<Item = Result<Vec<u8>>> + '_ {
+        let enable_interrogative_upspeak = options.enable_interrogative_upspeak;
+        let output_gain_db = options.output_gain_db;
+        let prioritize_first_packet = options.prioritize_first_packet;
+        let eq = Arc::<[_]>::from(&*options.eq);
+        let audio_query = match options.voice_mode {
+            VoiceMode::Normal => audio_query,
+            VoiceMode::Whisper => audio_query.whispered(),
+        };
+        enum Step<'a> {
+            Head,
+            Tail {
+                head_len: usize,
+                wave: BoxFuture<'a, Result<Vec<f32>>>,
+            },
+            Done,
+        }
+
+        let audio_query = Arc::new(audio_query);
+        futures::stream::try_unfold(Step::Head, move |step| {
+            let audio_query = audio_query.clone();
+            let eq = eq.clone();
+            async move {
+                let engine = &self.synthesis_engine;
+                let apply_eq = |wave: &mut Vec<f32>| {
+                    equalize(&eq, SynthesisEngine::DEFAULT_SAMPLING_RATE, wave);
+                };
+                match step {
+                    Step::Head => {
+                        // 文全体の推論は先頭のアクセント句と並行して始めておき、1つ目のチャンクが
+                        // 消費されている間にも進める
+                        let wave = {
+                            let audio_query = audio_query.clone();
+                            async move {
+                                engine
+                                    .synthesis(&audio_query, style_id, enable_interrogative_upspeak)
+                                    .await
+                            }
+                            .boxed()
+                        };
+                        let head_end = if prioritize_first_packet {
+                            SynthesisEngine::head_end(&audio_query, enable_interrogative_upspeak)
+                        } else {
+                            None
+                        };
+                        let Some(head_end) = head_end else {
+                            let mut wave = wave.await?;
+                            apply_eq(&mut wave);
+                            return Ok(Some((
+                                SynthesisEngine::to_pcm(&audio_query, &wave, output_gain_db),
+                                Step::Done,
+                            )));
+                        };
+
+                        let (f0, flatten_phoneme) = SynthesisEngine::decode_inputs(
+                            &audio_query,
+                            enable_interrogative_upspeak,
+                        );
+                        let head = engine.decode_head(&f0, &flatten_phoneme, head_end, style_id);
+                        futures::pin_mut!(head);
+                        let (mut head, wave) = match futures::future::select(head, wave).await {
+                            Either::Left((head, wave)) => (head?, wave),
+                            Either::Right((wave, head)) => {
+                                (head.await?, futures::future::ready(wave).boxed())
+                            }
+                        };
+
+                        let head_len = head.len();
+                        apply_eq(&mut head);
+                        Ok(Some((
+                            SynthesisEngine::to_pcm(&audio_query, &head, output_gain_db),
+                            Step::Tail { head_len, wave },
+                        )))
+                    }
+                    Step::Tail { head_len, wave } => {
+                        // イコライザは過去のサンプルに依存するため、文全体に掛けてから切り出す
+                        let mut wave = wave.await?;
+                        apply_eq(&mut wave);
+                        Ok(Some((
+                            SynthesisEngine::to_pcm(
+                                &audio_query,
+                                &wave[head_len..],
+                                output_gain_db,
+                            ),
+                            Step::Done,
+                        )))
+                    }
+                    Step::Done => Ok(None),
+                }
+            }
+        })
+    }
+
+    #[doc(hidden)]
+    pub async fn predict_duration(
+        &self,
+        phoneme_vector: &[i64],
+        style_id: StyleId,
+    ) -> Result<Vec<f32>> {
+        self.synthesis_engine
             .inference_core()
             .predict_duration(phoneme_vector, style_id)
             .await
@@ -258,6 +1483,49 @@ impl Synthesizer {
             )
             .await
     }
+    /// [`predict_intonation`]の、[`ndarray`]の配列を受け取り、[`ndarray`]の配列を返す版。
+    ///
+    /// `length`は`vowel_phoneme_vector`の長さから求められる。所有権を持つ配列([`Array1`])を渡した場合は
+    /// 入力のコピーが起きない。ビュー([`ArrayView1`])を渡した場合は、推論を別スレッドで行うために一度
+    /// だけコピーされる。
+    ///
+    /// `ndarray`フィーチャが有効なときのみ使える。
+    ///
+    /// [`predict_intonation`]: Self::predict_intonation
+    /// [`ndarray`]: crate::ndarray
+    /// [`Array1`]: crate::ndarray::Array1
+    /// [`ArrayView1`]: crate::ndarray::ArrayView1
+    #[cfg(feature = "ndarray")]
+    #[allow(clippy::too_many_arguments)]
+    pub async fn predict_intonation_nd(
+        &self,
+        vowel_phoneme_vector: ndarray::ArrayBase<impl ndarray::Data<Elem = i64>, ndarray::Ix1>,
+        consonant_phoneme_vector: ndarray::ArrayBase<impl ndarray::Data<Elem = i64>, ndarray::Ix1>,
+        start_accent_vector: ndarray::ArrayBase<impl ndarray::Data<Elem = i64>, ndarray::Ix1>,
+        end_accent_vector: ndarray::ArrayBase<impl ndarray::Data<Elem = i64>, ndarray::Ix1>,
+        start_accent_phrase_vector: ndarray::ArrayBase<
+            impl ndarray::Data<Elem = i64>,
+            ndarray::Ix1,
+        >,
+        end_accent_phrase_vector: ndarray::ArrayBase<impl ndarray::Data<Elem = i64>, ndarray::Ix1>,
+        style_id: StyleId,
+    ) -> Result<ndarray::Array1<f32>> {
+        self.synthesis_engine
+            .inference_core()
+            .predict_intonation_nd(
+                vowel_phoneme_vector.len(),
+                vowel_phoneme_vector.into_owned(),
+                consonant_phoneme_vector.into_owned(),
+                start_accent_vector.into_owned(),
+                end_accent_vector.into_owned(),
+                start_accent_phrase_vector.into_owned(),
+                end_accent_phrase_vector.into_owned(),
+                style_id,
+            )
+            .await
+            .map(ndarray::Array1::from_vec)
+    }
+
     #[doc(hidden)]
     pub async fn decode(
         &self,
@@ -278,6 +1546,28 @@ impl Synthesizer {
     /// `text`は[`options.kana`]が有効化されているときにはAquesTalk風記法として、そうでないときには
     /// 日本語のテキストとして解釈される。
     ///
+    /// 日本語のテキストには、`会議{かいぎ:1}`のように単語の直後に`{読み:アクセント核の位置}`を置く
+    /// ことで、その単語の読みとアクセントをこの呼び出しに限り指定できる。読みはひらがなかカタカナで
+    /// 書き、アクセント核の位置は[`UserDictWord::accent_type`]と同じく数える。指定の対象は`{`の直前
+    /// にある漢字・カタカナ・英数字の並びである。単語の直後にあり、かつ直後が仮名である`{`はアクセント
+    /// 指定の始まりとみなされ、それ以外の`{`や`}`は普通の文字として扱われる。
+    ///
+    /// アクセント指定を含む呼び出しでは、設定済みのユーザー辞書に指定された単語を加えたものをその都度
+    /// コンパイルし、システム辞書と共に読み込み直す。そのため指定を含まない呼び出しよりも大幅に時間が
+    /// かかる。同じ単語を繰り返し指定する場合は、[`UserDict`]に登録して[`OpenJtalk::use_user_dict`]で
+    /// 設定する方がよい。
+    ///
+    /// [`OpenJtalk::use_user_dict`]で設定したユーザー辞書に[`ContextualWord`]がある場合は、その文脈に
+    /// 当てはまる箇所をその読みとアクセントで読む。
+    ///
+    /// # Errors
+    ///
+    /// - OpenJTalkの辞書が読み込まれていない場合、[`options.kana`]によらず
+    ///   [`Error::NotLoadedOpenjtalkDict`]を返す。これはテキストを解析する他のメソッド
+    ///   ([`audio_query`]、[`tts`]など)でも同様である。
+    /// - アクセント指定が不正な場合(`}`で閉じられていない、アクセント核の位置が読みのモーラ数を
+    ///   超えるなど)、その範囲を含む[`Error::InvalidAccentHint`]を返す。
+    ///
     /// # Examples
     ///
     #[cfg_attr(windows, doc = "```no_run")] // https://github.com/VOICEVOX/voicevox_core/issues/537
@@ -362,7 +1652,10 @@ impl Synthesizer {
     ///     .create_accent_phrases(
     ///         "コンニチワ'", // AquesTalk風記法
     ///         StyleId::new(302),
-    ///         &AccentPhrasesOptions { kana: true },
+    ///         &AccentPhrasesOptions {
+    ///             kana: true,
+    ///             ..Default::default()
+    ///         },
     ///     )
     ///     .await?;
     /// #
@@ -371,6 +1664,8 @@ impl Synthesizer {
     /// ```
     ///
     /// [`options.kana`]: crate::AccentPhrasesOptions::kana
+    /// [`audio_query`]: Self::audio_query
+    /// [`tts`]: Self::tts
     pub async fn create_accent_phrases(
         &self,
         text: &str,
@@ -380,15 +1675,37 @@ impl Synthesizer {
         if !self.synthesis_engine.is_openjtalk_dict_loaded() {
             return Err(Error::NotLoadedOpenjtalkDict);
         }
-        if options.kana {
+        let accent_phrases = if options.kana {
             self.synthesis_engine
                 .replace_mora_data(&parse_kana(text)?, style_id)
-                .await
+                .await?
+        } else if options.normalize_width {
+            self.synthesis_engine
+                .create_accent_phrases(&normalize_width(text), style_id)
+                .await?
         } else {
             self.synthesis_engine
                 .create_accent_phrases(text, style_id)
-                .await
-        }
+                .await?
+        };
+        Ok(options.phrase_segmentation.apply(accent_phrases))
+    }
+
+    /// AquesTalk風記法のテキストから、AccentPhrase (アクセント句)の配列を生成する。
+    ///
+    /// 推論は行わないため、音声モデルやスタイルを必要としない。生成されるAccentPhraseの音高・音素長
+    /// は全て`0`となる。記法の検証や正規化のみを行いたい場合に用いる。音高・音素長が必要な場合は、
+    /// [`replace_mora_data`]を用いるか、[`AccentPhrasesOptions::kana`]を有効にして
+    /// [`create_accent_phrases`]を用いる。
+    ///
+    /// # Errors
+    ///
+    /// `text`がAquesTalk風記法として不正な場合、[`Error::ParseKana`]を返す。
+    ///
+    /// [`replace_mora_data`]: Self::replace_mora_data
+    /// [`create_accent_phrases`]: Self::create_accent_phrases
+    pub fn accent_phrases_from_kana(text: &str) -> Result<Vec<AccentPhraseModel>> {
+        Ok(parse_kana(text)?)
     }
 
     /// AccentPhraseの配列の音高・音素長を、特定の声で生成しなおす。
@@ -402,6 +1719,35 @@ impl Synthesizer {
             .await
     }
 
+    /// AccentPhraseの配列のうち、`index`番目のAccentPhraseの音高・音素長のみを特定の声で生成しなおす。
+    ///
+    /// 推論は`index`番目のAccentPhrase単体に対してのみ行われ、それ以外のAccentPhraseはそのまま
+    /// 返される。そのため前後のAccentPhraseの影響は受けない。
+    ///
+    /// # Errors
+    ///
+    /// `index`が範囲外の場合、[`Error::InvalidAccentPhraseIndex`]を返す。
+    pub async fn replace_mora_data_at(
+        &self,
+        accent_phrases: &[AccentPhraseModel],
+        index: usize,
+        style_id: StyleId,
+    ) -> Result<Vec<AccentPhraseModel>> {
+        let accent_phrase = accent_phrases
+            .get(index)
+            .ok_or(Error::InvalidAccentPhraseIndex { index })?;
+        let [replaced] = <[_; 1]>::try_from(
+            self.synthesis_engine
+                .replace_mora_data(&[accent_phrase.clone()], style_id)
+                .await?,
+        )
+        .unwrap_or_else(|_| unreachable!("should be same length as the input"));
+
+        let mut accent_phrases = accent_phrases.to_owned();
+        accent_phrases[index] = replaced;
+        Ok(accent_phrases)
+    }
+
     /// AccentPhraseの配列の音素長を、特定の声で生成しなおす。
     pub async fn replace_phoneme_length(
         &self,
@@ -513,7 +1859,10 @@ impl Synthesizer {
     ///     .audio_query(
     ///         "コンニチワ'", // AquesTalk風記法
     ///         StyleId::new(302),
-    ///         &AudioQueryOptions { kana: true },
+    ///         &AudioQueryOptions {
+    ///             kana: true,
+    ///             ..Default::default()
+    ///         },
     ///     )
     ///     .await?;
     /// #
@@ -529,95 +1878,602 @@ impl Synthesizer {
         style_id: StyleId,
         options: &AudioQueryOptions,
     ) -> Result<AudioQueryModel> {
+        let len = text.chars().count();
+        if len > options.max_text_length {
+            return Err(Error::TextTooLong {
+                len,
+                max: options.max_text_length,
+            });
+        }
+
+        let width_normalized;
+        let text = if options.normalize_width && !options.kana {
+            width_normalized = normalize_width(text);
+            width_normalized.as_str()
+        } else {
+            text
+        };
+        let normalized;
+        let text = if options.normalize_text && !options.kana {
+            normalized = normalize(text);
+            normalized.as_str()
+        } else {
+            text
+        };
+        let transliterated;
+        let text = if options.transliterate_alphabet && !options.kana {
+            transliterated =
+                transliterate_alphabet(text, |word| self.synthesis_engine.is_user_dict_word(word));
+            transliterated.as_str()
+        } else {
+            text
+        };
         let accent_phrases = self
-            .create_accent_phrases(text, style_id, &AccentPhrasesOptions { kana: options.kana })
+            .create_accent_phrases(
+                text,
+                style_id,
+                &AccentPhrasesOptions {
+                    kana: options.kana,
+                    // 上で適用済み
+                    normalize_width: false,
+                    phrase_segmentation: options.phrase_segmentation,
+                },
+            )
             .await?;
         let kana = create_kana(&accent_phrases);
+        let default_scales = self.default_scales(style_id);
         Ok(AudioQueryModel::new(
             accent_phrases,
-            1.,
-            0.,
-            1.,
-            1.,
-            0.1,
-            0.1,
+            default_scales.speed_scale().unwrap_or(1.),
+            default_scales.pitch_scale().unwrap_or(0.),
+            default_scales.intonation_scale().unwrap_or(1.),
+            default_scales.volume_scale().unwrap_or(1.),
+            options
+                .pre_phoneme_length
+                .unwrap_or(AudioQueryOptions::DEFAULT_PHONEME_LENGTH),
+            options
+                .post_phoneme_length
+                .unwrap_or(AudioQueryOptions::DEFAULT_PHONEME_LENGTH),
             SynthesisEngine::DEFAULT_SAMPLING_RATE,
             false,
             Some(kana),
         ))
     }
 
+    /// [`audio_query`]に加えて、推論された音素長が[`options.duration_warning_thresholds`]を超えて
+    /// いるモーラを[`SynthesisWarning`]として返す。
+    ///
+    /// 警告の有無によらず、AudioQueryは[`audio_query`]と同じものとなる。
+    ///
+    /// [`audio_query`]: Self::audio_query
+    /// [`options.duration_warning_thresholds`]: AudioQueryOptions::duration_warning_thresholds
+    pub async fn audio_query_with_warnings(
+        &self,
+        text: &str,
+        style_id: StyleId,
+        options: &AudioQueryOptions,
+    ) -> Result<(AudioQueryModel, Vec<SynthesisWarning>)> {
+        let audio_query = self.audio_query(text, style_id, options).await?;
+        let warnings = options
+            .duration_warning_thresholds
+            .check(audio_query.accent_phrases());
+        Ok((audio_query, warnings))
+    }
+
+    /// スタイルのメタ情報に書かれたAudioQueryの既定値を取得する。
+    fn default_scales(&self, style_id: StyleId) -> StyleDefaultScales {
+        self.metas()
+            .iter()
+            .flat_map(SpeakerMeta::styles)
+            .find(|style| *style.id() == style_id)
+            .and_then(|style| *style.default_scales())
+            .unwrap_or_default()
+    }
+
     /// テキスト音声合成を行う。
     ///
     /// `text`は[`options.kana`]が有効化されているときにはAquesTalk風記法として、そうでないときには
     /// 日本語のテキストとして解釈される。
     ///
+    /// [`options.speed_scale`]などが設定されている場合、生成したAudioQueryの該当するパラメータを
+    /// 置き換えてから音声合成する。
+    ///
     /// [`options.kana`]: crate::TtsOptions::kana
+    /// [`options.speed_scale`]: crate::TtsOptions::speed_scale
     pub async fn tts(
         &self,
         text: &str,
         style_id: StyleId,
         options: &TtsOptions,
     ) -> Result<Vec<u8>> {
-        let audio_query = &self
-            .audio_query(text, style_id, &AudioQueryOptions::from(options))
-            .await?;
-        self.synthesis(audio_query, style_id, &SynthesisOptions::from(options))
+        self.tts_with_query_options(text, style_id, options, &AudioQueryOptions::from(options))
             .await
     }
-}
 
-#[cfg(windows)]
-fn list_windows_video_cards() {
-    use std::{ffi::OsString, os::windows::ffi::OsStringExt as _};
+    async fn tts_with_query_options(
+        &self,
+        text: &str,
+        style_id: StyleId,
+        options: &TtsOptions,
+        audio_query_options: &AudioQueryOptions,
+    ) -> Result<Vec<u8>> {
+        TensorCapture::scope(options.capture_tensors.as_ref(), async {
+            let audio_query = &options.override_scales(
+                self.audio_query(text, style_id, audio_query_options)
+                    .await?,
+            );
+            self.synthesis(audio_query, style_id, &SynthesisOptions::from(options))
+                .await
+        })
+        .await
+    }
 
-    use humansize::BINARY;
-    use tracing::{error, info};
-    use windows::Win32::Graphics::Dxgi::{
-        CreateDXGIFactory, IDXGIFactory, DXGI_ADAPTER_DESC, DXGI_ERROR_NOT_FOUND,
-    };
+    /// 話者名とスタイル名で指定したスタイルで、テキスト音声合成を行う。
+    ///
+    /// # Errors
+    ///
+    /// 該当するスタイルが読み込まれていない場合、[`Error::StyleNotFound`]を返す。
+    pub async fn tts_by_name(
+        &self,
+        text: &str,
+        speaker: &str,
+        style: &str,
+        options: &TtsOptions,
+    ) -> Result<Vec<u8>> {
+        let style_id = self
+            .resolve_style(speaker, style)
+            .ok_or_else(|| Error::StyleNotFound {
+                speaker: speaker.to_owned(),
+                style: style.to_owned(),
+                available: self
+                    .metas()
+                    .iter()
+                    .flat_map(|speaker| {
+                        speaker
+                            .styles()
+                            .iter()
+                            .map(move |style| format!("{} ({})", speaker.name(), style.name()))
+                    })
+                    .collect(),
+            })?;
+        self.tts(text, style_id, options).await
+    }
 
-    info!("検出されたGPU (DirectMLには1番目のGPUが使われます):");
-    match list_windows_video_cards() {
-        Ok(descs) => {
-            for desc in descs {
-                let description = OsString::from_wide(trim_nul(&desc.Description));
-                let vram = humansize::format_size(desc.DedicatedVideoMemory, BINARY);
-                info!("  - {description:?} ({vram})");
-            }
+    /// `style_id`のスタイルの試聴用の短い音声を、WAVデータとして返す。
+    ///
+    /// [`PREVIEW_TEXT`]を既定のオプションでテキスト音声合成したものである。結果はスタイルごとに
+    /// キャッシュされ、2回目以降は推論を行わずに同じデータを返す。キャッシュは音声モデルの読み込みを
+    /// 解除したときに破棄される。
+    ///
+    /// [`PREVIEW_TEXT`]: Self::PREVIEW_TEXT
+    pub async fn preview(&self, style_id: StyleId) -> Result<Vec<u8>> {
+        if let Some(wav) = self.previews.lock().unwrap().get(&style_id) {
+            return Ok(wav.clone());
         }
-        Err(err) => error!("{err}"),
+        let wav = self
+            .tts(Self::PREVIEW_TEXT, style_id, &Default::default())
+            .await?;
+        self.previews.lock().unwrap().insert(style_id, wav.clone());
+        Ok(wav)
     }
 
-    fn list_windows_video_cards() -> windows::core::Result<Vec<DXGI_ADAPTER_DESC>> {
-        #[allow(unsafe_code)]
-        unsafe {
-            let factory = CreateDXGIFactory::<IDXGIFactory>()?;
-            (0..)
-                .map(|i| factory.EnumAdapters(i)?.GetDesc())
-                .take_while(|r| !matches!(r, Err(e) if e.code() == DXGI_ERROR_NOT_FOUND))
-                .collect()
-        }
+    /// `style_id`のスタイルで一度テキスト音声合成を行い、結果を捨てる。
+    ///
+    /// 推論の初回は、実行環境の初期化などにより2回目以降よりも時間がかかる。サービスの開始前などに
+    /// 呼んでおくことで、最初のリクエストの遅延を抑えられる。合成するテキストは[`PREVIEW_TEXT`]で
+    /// ある。
+    ///
+    /// [`PREVIEW_TEXT`]: Self::PREVIEW_TEXT
+    pub async fn warm_up(&self, style_id: StyleId) -> Result<()> {
+        self.tts(Self::PREVIEW_TEXT, style_id, &Default::default())
+            .await?;
+        Ok(())
     }
 
-    fn trim_nul(s: &[u16]) -> &[u16] {
-        &s[..s.iter().position(|&c| c == 0x0000).unwrap_or(s.len())]
+    /// 読み込まれている音声モデルのメタ情報から、話者名とスタイル名に対応する[`StyleId`]を探す。
+    pub fn resolve_style(&self, speaker: &str, style: &str) -> Option<StyleId> {
+        self.metas()
+            .iter()
+            .filter(|meta| meta.name() == speaker)
+            .flat_map(|meta| meta.styles())
+            .find(|meta| meta.name() == style)
+            .map(|meta| *meta.id())
     }
-}
 
-#[cfg(test)]
-mod tests {
+    /// テキストを文ごとに分割し、一文ずつテキスト音声合成を行う。
+    ///
+    /// 文は「。」「！」「？」で区切られる。ただし括弧の内側では区切らず、文末記号の直後に続く閉じ括弧
+    /// や三点リーダーは直前の文に含まれる。
+    ///
+    /// 戻り値のストリームは`(文, WAVデータ)`を文の順に返す。各WAVデータはそれぞれ単独で再生可能で
+    /// あり、合成はストリームがポーリングされるたびに一文ずつ行われる。
+    ///
+    /// [`TtsOptions::pause_model`]が設定されている場合、各WAVデータの末尾の無音はその文の文末記号に
+    /// 応じた長さとなる。
+    ///
+    /// OpenJTalkの辞書が読み込まれていない場合は、`text`が空であっても
+    /// [`Error::NotLoadedOpenjtalkDict`]のみを返す。
+    pub fn tts_many<'a>(
+        &'a self,
+        text: &'a str,
+        style_id: StyleId,
+        options: &'a TtsOptions,
+    ) -> impl Stream<Item = Result<(String, Vec<u8>)>> + 'a {
+        // 文が1つも無い場合にも辞書の有無を報告するため、辞書が無ければ分割せずにエラーのみを返す
+        let dict_loaded = self.synthesis_engine.is_openjtalk_dict_loaded();
+        let not_loaded = (!dict_loaded).then_some(Err(Error::NotLoadedOpenjtalkDict));
+        let sentences = if dict_loaded {
+            split_sentences(text)
+        } else {
+            vec![]
+        };
+        let sentences = futures::stream::iter(sentences).then(move |sentence| async move {
+            let wav = self.tts_sentence(sentence, style_id, options).await?;
+            Ok((sentence.to_owned(), wav))
+        });
+        futures::stream::iter(not_loaded).chain(sentences)
+    }
 
-    use super::*;
-    use crate::{engine::MoraModel, macros::tests::assert_debug_fmt_eq};
-    use ::test_util::OPEN_JTALK_DIC_DIR;
+    /// [`tts_many`]で分割した一文を、[`TtsOptions::pause_model`]を反映してテキスト音声合成する。
+    ///
+    /// [`tts_many`]: Self::tts_many
+    pub(crate) async fn tts_sentence(
+        &self,
+        sentence: &str,
+        style_id: StyleId,
+        options: &TtsOptions,
+    ) -> Result<Vec<u8>> {
+        let audio_query_options = AudioQueryOptions {
+            post_phoneme_length: options
+                .pause_model
+                .map(|pause_model| pause_model.pause_after(sentence)),
+            ..AudioQueryOptions::from(options)
+        };
+        self.tts_with_query_options(sentence, style_id, options, &audio_query_options)
+            .await
+    }
 
-    #[rstest]
-    #[case(Ok(()))]
-    #[tokio::test]
-    async fn load_model_works(#[case] expected_result_at_initialized: Result<()>) {
-        let syntesizer = Synthesizer::new_with_initialize(
-            Arc::new(OpenJtalk::new_without_dic()),
+    /// テキスト音声合成を行ったときの音声の長さを、秒単位で見積もる。
+    ///
+    /// [`tts`]とは異なり、音素長と音高の推論までしか行わず波形の生成を行わないため、高速に
+    /// 求められる。
+    ///
+    /// [`tts`]: Self::tts
+    pub async fn estimate_duration(
+        &self,
+        text: &str,
+        style_id: StyleId,
+        options: &TtsOptions,
+    ) -> Result<f32> {
+        let audio_query = &options.override_scales(
+            self.audio_query(text, style_id, &AudioQueryOptions::from(options))
+                .await?,
+        );
+        Ok(self.estimate_duration_from_query(audio_query, &SynthesisOptions::from(options)))
+    }
+
+    /// AudioQueryから音声合成を行ったときの音声の長さを、秒単位で求める。
+    ///
+    /// 推論は行わない。AudioQueryの`speed_scale`や、`pre_phoneme_length`・`post_phoneme_length`、
+    /// 句読点による無音の長さが反映される。
+    pub fn estimate_duration_from_query(
+        &self,
+        audio_query: &AudioQueryModel,
+        options: &SynthesisOptions,
+    ) -> f32 {
+        SynthesisEngine::estimate_duration(audio_query, options.enable_interrogative_upspeak)
+    }
+
+    /// AudioQueryから[`synthesis`]を行ったときに返るWAVデータのバイト数を求める。
+    ///
+    /// 推論は行わない。`options`の`voice_mode`と`emit_cue_points`、AudioQueryの出力サンプリング
+    /// レートとステレオ出力の指定が反映される。呼び出し側でバッファを用意する場合などに使う。
+    ///
+    /// [`synthesis`]: Self::synthesis
+    pub fn wav_len_from_query(
+        &self,
+        audio_query: &AudioQueryModel,
+        options: &SynthesisOptions,
+    ) -> usize {
+        let audio_query = options.voice_mode.apply(audio_query);
+        let audio_query = &*audio_query;
+        let upspeak = options.enable_interrogative_upspeak;
+        let pcm_len =
+            SynthesisEngine::pcm_len(audio_query, SynthesisEngine::wave_len(audio_query, upspeak));
+        let num_cue_points = if options.emit_cue_points {
+            SynthesisEngine::accent_phrase_cue_points(audio_query, upspeak).len()
+        } else {
+            0
+        };
+        SynthesisEngine::wav_len(pcm_len, num_cue_points)
+    }
+
+    /// テキストのモーラ数やアクセント句の数、読み上げにかかる時間の見積もりを返す。
+    ///
+    /// テキストの解析と音素長の推論のみを行い、音声合成は行わない。`estimated_seconds`は
+    /// [`estimate_duration`]と同じ値となる。
+    ///
+    /// [`estimate_duration`]: Self::estimate_duration
+    pub async fn text_stats(
+        &self,
+        text: &str,
+        style_id: StyleId,
+        options: &TtsOptions,
+    ) -> Result<TextStats> {
+        let audio_query = &options.override_scales(
+            self.audio_query(text, style_id, &AudioQueryOptions::from(options))
+                .await?,
+        );
+        let accent_phrases = audio_query.accent_phrases();
+        Ok(TextStats {
+            mora_count: accent_phrases
+                .iter()
+                .map(|accent_phrase| accent_phrase.moras().len())
+                .sum(),
+            accent_phrase_count: accent_phrases.len(),
+            estimated_seconds: self
+                .estimate_duration_from_query(audio_query, &SynthesisOptions::from(options)),
+        })
+    }
+
+    /// AudioQueryから音声合成を行ったときに、疑問文の調整によって音声が変わるかどうかを返す。
+    ///
+    /// 推論は行わない。[`SynthesisOptions::enable_interrogative_upspeak`]が`true`であっても、語尾が
+    /// 疑問形のアクセント句が無い場合や、その最後のモーラが無声化している場合は`false`を返す。この
+    /// とき、`enable_interrogative_upspeak`の値に関わらず同じ音声が合成される。
+    pub fn is_upspeak_applied(
+        &self,
+        audio_query: &AudioQueryModel,
+        options: &SynthesisOptions,
+    ) -> bool {
+        SynthesisEngine::applies_interrogative_upspeak(
+            audio_query,
+            options.enable_interrogative_upspeak,
+        )
+    }
+
+    /// `sample_texts`を1つずつテキスト音声合成し、その性能を計測する。
+    ///
+    /// 計測の前に[`warm_up`]で推論を温めておく。この実行は計測の結果に含まれない。テキストは並列
+    /// ではなく1つずつ処理されるため、結果は単一のリクエストを処理するときの性能となる。
+    ///
+    /// `sample_texts`が空のときは何も行わず、全ての値が0の[`BenchmarkReport`]を返す。
+    ///
+    /// [`warm_up`]: Self::warm_up
+    pub async fn benchmark(
+        &self,
+        style_id: StyleId,
+        sample_texts: &[&str],
+    ) -> Result<BenchmarkReport> {
+        if sample_texts.is_empty() {
+            return Ok(BenchmarkReport::default());
+        }
+
+        self.warm_up(style_id).await?;
+
+        let options = &TtsOptions::default();
+
+        let mut latencies = Vec::with_capacity(sample_texts.len());
+        let mut audio_duration = 0.;
+        for text in sample_texts {
+            let start = Instant::now();
+            let audio_query = &self
+                .audio_query(text, style_id, &AudioQueryOptions::from(options))
+                .await?;
+            self.synthesis(audio_query, style_id, &SynthesisOptions::from(options))
+                .await?;
+            latencies.push(start.elapsed());
+            audio_duration += f64::from(
+                self.estimate_duration_from_query(audio_query, &SynthesisOptions::from(options)),
+            );
+        }
+        latencies.sort();
+
+        let num_samples = latencies.len();
+        let total = latencies.iter().sum::<Duration>();
+        let num_chars = sample_texts
+            .iter()
+            .map(|text| text.chars().count())
+            .sum::<usize>();
+        // nearest-rank法で求める
+        let percentile = |p: f64| latencies[((p * num_samples as f64).ceil() as usize).max(1) - 1];
+
+        Ok(BenchmarkReport {
+            num_samples,
+            mean_latency: total / num_samples as u32,
+            median_latency: percentile(0.5),
+            p99_latency: percentile(0.99),
+            chars_per_sec: num_chars as f64 / total.as_secs_f64(),
+            realtime_factor: total.as_secs_f64() / audio_duration,
+        })
+    }
+
+    /// `self`を、複数のタスクで共有するための[`SynthesizerHandle`]にする。
+    pub fn into_handle(self) -> SynthesizerHandle {
+        SynthesizerHandle(Arc::new(self))
+    }
+}
+
+/// 1つの[`Synthesizer`]を共有する、安価に複製できるハンドル。[`Synthesizer::into_handle`]で作る。
+///
+/// 内部では`Arc<Synthesizer>`を持ち、[`Deref`]によって[`Synthesizer`]の全てのメソッドを呼べる。
+/// [`clone`]しても`Synthesizer`は複製されないため、[`load_voice_model`]などによる状態の変更は全ての
+/// 複製に反映される。
+///
+/// ```
+/// # #[tokio::main]
+/// # async fn main() -> anyhow::Result<()> {
+/// #     use std::sync::Arc;
+/// #
+/// #     use test_util::OPEN_JTALK_DIC_DIR;
+/// #     use voicevox_core::{AccelerationMode, InitializeOptions, OpenJtalk, StyleId, Synthesizer};
+/// #
+/// let synthesizer = Synthesizer::new_with_initialize(
+///     Arc::new(OpenJtalk::new_with_initialize(OPEN_JTALK_DIC_DIR).unwrap()),
+///     &InitializeOptions {
+///         acceleration_mode: AccelerationMode::Cpu,
+///         ..Default::default()
+///     },
+/// )
+/// .await?
+/// .into_handle();
+///
+/// let task = tokio::spawn({
+///     let synthesizer = synthesizer.clone();
+///     async move { synthesizer.metas().len() }
+/// });
+/// assert_eq!(synthesizer.metas().len(), task.await?);
+/// #
+/// #     Ok(())
+/// # }
+/// ```
+///
+/// [`Deref`]: std::ops::Deref
+/// [`clone`]: Clone::clone
+/// [`load_voice_model`]: Synthesizer::load_voice_model
+#[derive(Clone)]
+pub struct SynthesizerHandle(Arc<Synthesizer>);
+
+impl SynthesizerHandle {
+    /// 共有している[`Synthesizer`]への`Arc`を返す。
+    pub fn as_arc(&self) -> &Arc<Synthesizer> {
+        &self.0
+    }
+}
+
+impl std::ops::Deref for SynthesizerHandle {
+    type Target = Synthesizer;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl From<Synthesizer> for SynthesizerHandle {
+    fn from(synthesizer: Synthesizer) -> Self {
+        synthesizer.into_handle()
+    }
+}
+
+impl From<Arc<Synthesizer>> for SynthesizerHandle {
+    fn from(synthesizer: Arc<Synthesizer>) -> Self {
+        Self(synthesizer)
+    }
+}
+
+/// [`Synthesizer::synthesis_samples`]が返す、音声のサンプルを少しずつ読み出すもの。
+pub struct SampleReader<'a> {
+    synthesis_engine: &'a SynthesisEngine,
+    style_id: StyleId,
+    volume_scale: f32,
+    output_gain_db: f32,
+    equalizer: Equalizer,
+    capture_tensors: Option<TensorCapture>,
+    f0: Vec<f32>,
+    flatten_phoneme: Vec<f32>,
+    frame_ends: std::vec::IntoIter<usize>,
+    frame_start: usize,
+    /// 推論済みのアクセント句1つ分の音声。
+    buffer: Vec<f32>,
+    /// `buffer`のうち、次に読み出す位置。
+    position: usize,
+}
+
+impl SampleReader<'_> {
+    /// 読み出される音声のサンプリングレート。
+    pub const SAMPLING_RATE: u32 = SynthesisEngine::DEFAULT_SAMPLING_RATE;
+
+    /// `buf`の先頭から音声のサンプルを書き込み、書き込んだサンプル数を返す。
+    ///
+    /// 推論済みの音声が残っていない場合は、次のアクセント句を推論する。`buf`が空でないにも関わらず
+    /// `0`を返した場合は、音声の終端に達している。
+    pub async fn read_samples(&mut self, buf: &mut [f32]) -> Result<usize> {
+        if buf.is_empty() {
+            return Ok(0);
+        }
+
+        while self.position == self.buffer.len() {
+            let Some(frame_end) = self.frame_ends.next() else {
+                return Ok(0);
+            };
+            let frames = self.frame_start..frame_end;
+            self.buffer = TensorCapture::scope(
+                self.capture_tensors.as_ref(),
+                self.synthesis_engine.decode_frames(
+                    &self.f0,
+                    &self.flatten_phoneme,
+                    frames,
+                    self.style_id,
+                ),
+            )
+            .await?;
+            self.equalizer.process(&mut self.buffer);
+            self.frame_start = frame_end;
+            self.position = 0;
+        }
+
+        let samples = &self.buffer[self.position..];
+        let len = buf.len().min(samples.len());
+        let output_gain = output_gain(self.output_gain_db);
+        for (dst, src) in buf.iter_mut().zip(&samples[..len]) {
+            *dst = output_gain(src * self.volume_scale);
+        }
+        self.position += len;
+        Ok(len)
+    }
+}
+
+#[cfg(windows)]
+fn list_windows_video_cards() {
+    use std::{ffi::OsString, os::windows::ffi::OsStringExt as _};
+
+    use humansize::BINARY;
+    use tracing::{error, info};
+    use windows::Win32::Graphics::Dxgi::{
+        CreateDXGIFactory, IDXGIFactory, DXGI_ADAPTER_DESC, DXGI_ERROR_NOT_FOUND,
+    };
+
+    info!("検出されたGPU (DirectMLには1番目のGPUが使われます):");
+    match list_windows_video_cards() {
+        Ok(descs) => {
+            for desc in descs {
+                let description = OsString::from_wide(trim_nul(&desc.Description));
+                let vram = humansize::format_size(desc.DedicatedVideoMemory, BINARY);
+                info!("  - {description:?} ({vram})");
+            }
+        }
+        Err(err) => error!("{err}"),
+    }
+
+    fn list_windows_video_cards() -> windows::core::Result<Vec<DXGI_ADAPTER_DESC>> {
+        #[allow(unsafe_code)]
+        unsafe {
+            let factory = CreateDXGIFactory::<IDXGIFactory>()?;
+            (0..)
+                .map(|i| factory.EnumAdapters(i)?.GetDesc())
+                .take_while(|r| !matches!(r, Err(e) if e.code() == DXGI_ERROR_NOT_FOUND))
+                .collect()
+        }
+    }
+
+    fn trim_nul(s: &[u16]) -> &[u16] {
+        &s[..s.iter().position(|&c| c == 0x0000).unwrap_or(s.len())]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::{engine::MoraModel, macros::tests::assert_debug_fmt_eq};
+    use ::test_util::OPEN_JTALK_DIC_DIR;
+
+    #[rstest]
+    #[case(Ok(()))]
+    #[tokio::test]
+    async fn load_model_works(#[case] expected_result_at_initialized: Result<()>) {
+        let syntesizer = Synthesizer::new_with_initialize(
+            Arc::new(OpenJtalk::new_without_dic()),
             &InitializeOptions {
                 acceleration_mode: AccelerationMode::Cpu,
                 ..Default::default()
@@ -639,24 +2495,54 @@ mod tests {
 
     #[rstest]
     #[tokio::test]
-    async fn is_use_gpu_works() {
+    async fn new_with_initialize_applies_user_dict() {
+        let mut user_dict = UserDict::new();
+        user_dict
+            .add_word(
+                UserDictWord::new(
+                    "this_word_should_not_exist_in_default_dictionary",
+                    "アイウエオ".to_owned(),
+                    0,
+                    UserDictWordType::ProperNoun,
+                    5,
+                )
+                .unwrap(),
+            )
+            .unwrap();
+
         let syntesizer = Synthesizer::new_with_initialize(
-            Arc::new(OpenJtalk::new_without_dic()),
+            Arc::new(OpenJtalk::new_with_initialize(OPEN_JTALK_DIC_DIR).unwrap()),
             &InitializeOptions {
                 acceleration_mode: AccelerationMode::Cpu,
+                load_all_models: true,
+                user_dict: Some(Arc::new(user_dict)),
                 ..Default::default()
             },
         )
         .await
         .unwrap();
-        assert!(!syntesizer.is_gpu_mode());
+
+        let query = syntesizer
+            .audio_query(
+                "this_word_should_not_exist_in_default_dictionary",
+                StyleId::new(0),
+                &Default::default(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(
+            Some("アイウエオ"),
+            query
+                .kana()
+                .as_deref()
+                .map(|kana| kana.trim_end_matches('\''))
+        );
     }
 
     #[rstest]
-    #[case(1, true)]
     #[tokio::test]
-    async fn is_loaded_model_by_style_id_works(#[case] style_id: u32, #[case] expected: bool) {
-        let style_id = StyleId::new(style_id);
+    async fn load_voice_model_with_progress_works() {
         let syntesizer = Synthesizer::new_with_initialize(
             Arc::new(OpenJtalk::new_without_dic()),
             &InitializeOptions {
@@ -666,26 +2552,21 @@ mod tests {
         )
         .await
         .unwrap();
-        assert!(
-            !syntesizer.is_loaded_model_by_style_id(style_id),
-            "expected is_model_loaded to return false, but got true",
-        );
+
+        let progress = std::sync::Mutex::new(vec![]);
         syntesizer
-            .load_voice_model(&open_default_vvm_file().await)
+            .load_voice_model_with_progress(&open_default_vvm_file().await, |loaded, total| {
+                progress.lock().unwrap().push((loaded, total));
+            })
             .await
             .unwrap();
 
-        assert_eq!(
-            syntesizer.is_loaded_model_by_style_id(style_id),
-            expected,
-            "expected is_model_loaded return value against style_id `{style_id}` is `{expected}`, but got `{}`",
-            !expected
-        );
+        assert_eq!([(1, 3), (2, 3), (3, 3)], *progress.into_inner().unwrap());
     }
 
     #[rstest]
     #[tokio::test]
-    async fn predict_duration_works() {
+    async fn loaded_session_count_is_stable_after_unload() {
         let syntesizer = Synthesizer::new_with_initialize(
             Arc::new(OpenJtalk::new_without_dic()),
             &InitializeOptions {
@@ -695,29 +2576,106 @@ mod tests {
         )
         .await
         .unwrap();
+        let model = open_default_vvm_file().await;
 
-        syntesizer
-            .load_voice_model(&open_default_vvm_file().await)
-            .await
-            .unwrap();
+        for _ in 0..100 {
+            syntesizer.load_voice_model(&model).await.unwrap();
+            assert_eq!(3, syntesizer.loaded_session_count());
+            syntesizer.unload_voice_model(model.id()).unwrap();
+            assert_eq!(0, syntesizer.loaded_session_count());
+        }
+    }
 
-        // 「こんにちは、音声合成の世界へようこそ」という文章を変換して得た phoneme_vector
-        let phoneme_vector = [
-            0, 23, 30, 4, 28, 21, 10, 21, 42, 7, 0, 30, 4, 35, 14, 14, 16, 30, 30, 35, 14, 14, 28,
-            30, 35, 14, 23, 7, 21, 14, 43, 30, 30, 23, 30, 35, 30, 0,
-        ];
+    #[rstest]
+    #[tokio::test]
+    async fn voice_models_can_be_reloaded_after_unload_all() {
+        let syntesizer = Synthesizer::new_with_initialize(
+            Arc::new(OpenJtalk::new_without_dic()),
+            &InitializeOptions {
+                acceleration_mode: AccelerationMode::Cpu,
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+        let model = open_default_vvm_file().await;
 
-        let result = syntesizer
-            .predict_duration(&phoneme_vector, StyleId::new(1))
-            .await;
+        syntesizer.load_voice_model(&model).await.unwrap();
+        syntesizer.unload_all_voice_models();
+        assert!(!syntesizer.is_loaded_voice_model(model.id()));
+        assert!(syntesizer.metas().is_empty());
+        assert_eq!(0, syntesizer.loaded_session_count());
 
-        assert!(result.is_ok(), "{result:?}");
-        assert_eq!(result.unwrap().len(), phoneme_vector.len());
+        syntesizer.load_voice_model(&model).await.unwrap();
+        assert!(syntesizer.is_loaded_voice_model(model.id()));
+        assert!(!syntesizer.metas().is_empty());
+        assert_eq!(3, syntesizer.loaded_session_count());
+    }
+
+    #[rstest]
+    #[case(AccelerationMode::Auto, r#""auto""#)]
+    #[case(AccelerationMode::Cpu, r#""cpu""#)]
+    #[case(AccelerationMode::Gpu, r#""gpu""#)]
+    fn acceleration_mode_serde_works(#[case] mode: AccelerationMode, #[case] json: &str) {
+        assert_eq!(json, serde_json::to_string(&mode).unwrap());
+        assert_eq!(
+            mode,
+            serde_json::from_str::<AccelerationMode>(json).unwrap()
+        );
+    }
+
+    #[rstest]
+    #[case(ExecutionProvider::Cpu, r#""cpu""#)]
+    #[case(ExecutionProvider::Cuda, r#""cuda""#)]
+    #[case(ExecutionProvider::DirectMl, r#""directml""#)]
+    fn execution_provider_serde_works(#[case] provider: ExecutionProvider, #[case] json: &str) {
+        assert_eq!(json, serde_json::to_string(&provider).unwrap());
+        assert_eq!(
+            provider,
+            serde_json::from_str::<ExecutionProvider>(json).unwrap()
+        );
     }
 
     #[rstest]
     #[tokio::test]
-    async fn predict_intonation_works() {
+    async fn new_with_initialize_rejects_unsupported_execution_provider() {
+        let unsupported = if cfg!(feature = "directml") {
+            ExecutionProvider::Cuda
+        } else {
+            ExecutionProvider::DirectMl
+        };
+        let result = Synthesizer::new_with_initialize(
+            Arc::new(OpenJtalk::new_without_dic()),
+            &InitializeOptions {
+                execution_provider: Some(unsupported),
+                ..Default::default()
+            },
+        )
+        .await;
+
+        assert!(matches!(
+            result,
+            Err(Error::GpuSupport(
+                GpuUnsupportedReason::NotBuiltWithExecutionProvider(provider)
+            )) if provider == unsupported,
+        ));
+    }
+
+    #[rstest]
+    fn tts_options_deserialize_fills_defaults() {
+        let options = serde_json::from_str::<TtsOptions>(r#"{"kana":true}"#).unwrap();
+        assert_eq!(
+            TtsOptions {
+                kana: true,
+                ..Default::default()
+            },
+            options
+        );
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn load_voice_model_with_timeout_works() {
         let syntesizer = Synthesizer::new_with_initialize(
             Arc::new(OpenJtalk::new_without_dic()),
             &InitializeOptions {
@@ -727,135 +2685,1805 @@ mod tests {
         )
         .await
         .unwrap();
+        let model = &open_default_vvm_file().await;
+
+        let result = syntesizer
+            .load_voice_model_with_timeout(model, Duration::ZERO)
+            .await;
+        assert!(
+            matches!(
+                &result,
+                Err(Error::LoadModel(err)) if matches!(err.context(), LoadModelErrorKind::Timeout)
+            ),
+            "got {result:?}",
+        );
+        assert!(!syntesizer.is_loaded_voice_model(model.id()));
+
         syntesizer
-            .load_voice_model(&open_default_vvm_file().await)
+            .load_voice_model_with_timeout(model, Duration::from_secs(60 * 60))
             .await
             .unwrap();
+        assert!(syntesizer.is_loaded_voice_model(model.id()));
+    }
 
-        // 「テスト」という文章に対応する入力
-        let vowel_phoneme_vector = [0, 14, 6, 30, 0];
-        let consonant_phoneme_vector = [-1, 37, 35, 37, -1];
-        let start_accent_vector = [0, 1, 0, 0, 0];
-        let end_accent_vector = [0, 1, 0, 0, 0];
-        let start_accent_phrase_vector = [0, 1, 0, 0, 0];
-        let end_accent_phrase_vector = [0, 0, 0, 1, 0];
-
+    #[rstest]
+    #[tokio::test]
+    async fn synthesis_proceeds_while_loading_another_model() {
+        let syntesizer = Synthesizer::new_with_initialize(
+            Arc::new(OpenJtalk::new_with_initialize(OPEN_JTALK_DIC_DIR).unwrap()),
+            &InitializeOptions {
+                acceleration_mode: AccelerationMode::Cpu,
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+        syntesizer
+            .load_voice_model(&open_default_vvm_file().await)
+            .await
+            .unwrap();
+
+        // スタイルIDを読み込み済みのものと重ならないようにした音声モデル。読み込むだけで、推論には
+        // 使わない
+        let (file, _) = crate::voice_model::tests::rewrite_sample_vvm(|entries, _| {
+            let mut metas =
+                serde_json::from_slice::<serde_json::Value>(&entries["metas.json"]).unwrap();
+            for speaker in metas.as_array_mut().unwrap() {
+                for style in speaker["styles"].as_array_mut().unwrap() {
+                    style["id"] = (style["id"].as_u64().unwrap() + 1000).into();
+                }
+            }
+            entries.insert("metas.json".to_owned(), serde_json::to_vec(&metas).unwrap());
+        })
+        .await;
+        let other = VoiceModel::from_path(file.path()).await.unwrap();
+
+        let load = syntesizer.load_voice_model(&other);
+        futures::pin_mut!(load);
+        assert!(
+            futures::poll!(&mut load).is_pending(),
+            "should be suspended in the middle of loading",
+        );
+
+        let audio_query = syntesizer
+            .audio_query("これはテストです", StyleId::new(0), &Default::default())
+            .await
+            .unwrap();
+        syntesizer
+            .synthesis(
+                &audio_query,
+                StyleId::new(0),
+                &SynthesisOptions::from(&TtsOptions::default()),
+            )
+            .await
+            .unwrap();
+        assert!(!syntesizer.is_loaded_voice_model(other.id()));
+
+        load.await.unwrap();
+        assert!(syntesizer.is_loaded_voice_model(other.id()));
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn is_use_gpu_works() {
+        let syntesizer = Synthesizer::new_with_initialize(
+            Arc::new(OpenJtalk::new_without_dic()),
+            &InitializeOptions {
+                acceleration_mode: AccelerationMode::Cpu,
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+        assert!(!syntesizer.is_gpu_mode());
+    }
+
+    #[rstest]
+    #[case(AccelerationMode::Cpu)]
+    #[case(AccelerationMode::Auto)]
+    #[tokio::test]
+    async fn effective_acceleration_mode_works(#[case] acceleration_mode: AccelerationMode) {
+        let syntesizer = Synthesizer::new_with_initialize(
+            Arc::new(OpenJtalk::new_without_dic()),
+            &InitializeOptions {
+                acceleration_mode,
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+        let (expected_mode, expected_provider) = if syntesizer.is_gpu_mode() {
+            (AccelerationMode::Gpu, ExecutionProvider::gpu().unwrap())
+        } else {
+            (AccelerationMode::Cpu, ExecutionProvider::Cpu)
+        };
+        assert_eq!(expected_mode, syntesizer.effective_acceleration_mode());
+        assert_eq!(expected_provider, syntesizer.execution_provider());
+    }
+
+    #[rstest]
+    #[case(0, 0, Some(0))]
+    #[case(1, 0, Some(1))]
+    #[case(2, 1, Some(303))]
+    #[case(2, 2, None)]
+    #[case(3, 0, None)]
+    #[tokio::test]
+    async fn style_id_from_indices_works(
+        #[case] speaker_index: usize,
+        #[case] style_index: usize,
+        #[case] expected: Option<u32>,
+    ) {
+        let synthesizer = Synthesizer::new_with_initialize(
+            Arc::new(OpenJtalk::new_without_dic()),
+            &InitializeOptions {
+                acceleration_mode: AccelerationMode::Cpu,
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+        assert_eq!(None, synthesizer.style_id_from_indices(0, 0));
+        synthesizer
+            .load_voice_model(&open_default_vvm_file().await)
+            .await
+            .unwrap();
+
+        let style_id = synthesizer.style_id_from_indices(speaker_index, style_index);
+        assert_eq!(expected.map(StyleId::new), style_id);
+        if let Some(style_id) = style_id {
+            assert_eq!(
+                Some((speaker_index, style_index)),
+                synthesizer.indices_from_style_id(style_id),
+            );
+        }
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn indices_from_style_id_returns_none_for_unknown_style() {
+        let synthesizer = Synthesizer::new_with_initialize(
+            Arc::new(OpenJtalk::new_without_dic()),
+            &InitializeOptions {
+                acceleration_mode: AccelerationMode::Cpu,
+                load_all_models: true,
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+        assert_eq!(None, synthesizer.indices_from_style_id(StyleId::new(1000)));
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn text_analysis_requires_openjtalk_dict() {
+        let synthesizer = Synthesizer::new_with_initialize(
+            Arc::new(OpenJtalk::new_without_dic()),
+            &InitializeOptions {
+                acceleration_mode: AccelerationMode::Cpu,
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+        synthesizer
+            .load_voice_model(&open_default_vvm_file().await)
+            .await
+            .unwrap();
+        let style_id = StyleId::new(1);
+        let kana = &TtsOptions {
+            kana: true,
+            ..Default::default()
+        };
+
+        let results = [
+            synthesizer
+                .create_accent_phrases("こんにちは", style_id, &Default::default())
+                .await
+                .map(|_| ()),
+            synthesizer
+                .create_accent_phrases(
+                    "コンニチワ'",
+                    style_id,
+                    &AccentPhrasesOptions {
+                        kana: true,
+                        ..Default::default()
+                    },
+                )
+                .await
+                .map(|_| ()),
+            synthesizer
+                .create_accent_phrases("", style_id, &Default::default())
+                .await
+                .map(|_| ()),
+            synthesizer
+                .audio_query("こんにちは", style_id, &Default::default())
+                .await
+                .map(|_| ()),
+            synthesizer
+                .tts("こんにちは", style_id, &Default::default())
+                .await
+                .map(|_| ()),
+            synthesizer
+                .tts("コンニチワ'", style_id, kana)
+                .await
+                .map(|_| ()),
+            synthesizer
+                .tts_by_name("こんにちは", "dummy2", "style2", &Default::default())
+                .await
+                .map(|_| ()),
+            synthesizer
+                .tts_stream("こんにちは", style_id, &Default::default())
+                .try_collect::<Vec<_>>()
+                .await
+                .map(|_| ()),
+            synthesizer
+                .tts_many("こんにちは。", style_id, &Default::default())
+                .try_collect::<Vec<_>>()
+                .await
+                .map(|_| ()),
+            synthesizer
+                .tts_many("", style_id, &Default::default())
+                .try_collect::<Vec<_>>()
+                .await
+                .map(|_| ()),
+            synthesizer
+                .estimate_duration("こんにちは", style_id, &Default::default())
+                .await
+                .map(|_| ()),
+            synthesizer
+                .text_stats("こんにちは", style_id, &Default::default())
+                .await
+                .map(|_| ()),
+            synthesizer.preview(style_id).await.map(|_| ()),
+        ];
+        for (i, result) in results.into_iter().enumerate() {
+            assert!(
+                matches!(result, Err(Error::NotLoadedOpenjtalkDict)),
+                "{i}: {result:?}",
+            );
+        }
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn synthesis_is_reproducible_with_seed() {
+        let synthesizer = Synthesizer::new_with_initialize(
+            Arc::new(OpenJtalk::new_with_initialize(OPEN_JTALK_DIC_DIR).unwrap()),
+            &InitializeOptions {
+                acceleration_mode: AccelerationMode::Cpu,
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+        synthesizer
+            .load_voice_model(&open_default_vvm_file().await)
+            .await
+            .unwrap();
+        let query = synthesizer
+            .audio_query(
+                "コンニチワ'",
+                StyleId::new(1),
+                &AudioQueryOptions {
+                    kana: true,
+                    ..Default::default()
+                },
+            )
+            .await
+            .unwrap();
+
+        let mut wavs = vec![];
+        for seed in [Some(0), Some(0), Some(1), None] {
+            let options = SynthesisOptions {
+                seed,
+                ..SynthesisOptions::from(&TtsOptions::default())
+            };
+            wavs.push(
+                synthesizer
+                    .synthesis(&query, StyleId::new(1), &options)
+                    .await
+                    .unwrap(),
+            );
+        }
+        assert!(wavs.windows(2).all(|w| w[0] == w[1]));
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn synthesis_applies_eq() {
+        let synthesizer = Synthesizer::new_with_initialize(
+            Arc::new(OpenJtalk::new_with_initialize(OPEN_JTALK_DIC_DIR).unwrap()),
+            &InitializeOptions {
+                acceleration_mode: AccelerationMode::Cpu,
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+        synthesizer
+            .load_voice_model(&open_default_vvm_file().await)
+            .await
+            .unwrap();
+        let query = synthesizer
+            .audio_query(
+                "コンニチワ'",
+                StyleId::new(1),
+                &AudioQueryOptions {
+                    kana: true,
+                    ..Default::default()
+                },
+            )
+            .await
+            .unwrap();
+
+        let options = SynthesisOptions::from(&TtsOptions::default());
+        let plain = synthesizer
+            .synthesis(&query, StyleId::new(1), &options)
+            .await
+            .unwrap();
+        let equalized = synthesizer
+            .synthesis(
+                &query,
+                StyleId::new(1),
+                &SynthesisOptions {
+                    eq: vec![BiquadBand {
+                        kind: BiquadKind::HighShelf,
+                        frequency: 4000.,
+                        gain_db: 6.,
+                        q: std::f32::consts::FRAC_1_SQRT_2,
+                    }],
+                    ..options
+                },
+            )
+            .await
+            .unwrap();
+        assert_eq!(plain.len(), equalized.len());
+        assert_eq!(plain[..44], equalized[..44]);
+        assert_ne!(plain, equalized);
+    }
+
+    #[rstest]
+    #[case(-1., -1., 6, 1)]
+    #[case(f32::INFINITY, -1., 0, 1)]
+    #[case(f32::INFINITY, f32::INFINITY, 0, 0)]
+    #[tokio::test]
+    async fn audio_query_with_warnings_works(
+        #[case] max_mora_length: f32,
+        #[case] max_pause_length: f32,
+        #[case] expected_mora_warnings: usize,
+        #[case] expected_pause_warnings: usize,
+    ) {
+        let synthesizer = Synthesizer::new_with_initialize(
+            Arc::new(OpenJtalk::new_with_initialize(OPEN_JTALK_DIC_DIR).unwrap()),
+            &InitializeOptions {
+                acceleration_mode: AccelerationMode::Cpu,
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+        synthesizer
+            .load_voice_model(&open_default_vvm_file().await)
+            .await
+            .unwrap();
+        let options = AudioQueryOptions {
+            kana: true,
+            duration_warning_thresholds: DurationWarningThresholds {
+                max_mora_length,
+                max_pause_length,
+            },
+            ..Default::default()
+        };
+
+        let (query, warnings) = synthesizer
+            .audio_query_with_warnings("コンニチワ'、ア'", StyleId::new(1), &options)
+            .await
+            .unwrap();
+
+        assert_eq!(
+            synthesizer
+                .audio_query("コンニチワ'、ア'", StyleId::new(1), &options)
+                .await
+                .unwrap(),
+            query,
+        );
+        let mora_warnings = warnings
+            .iter()
+            .filter(|warning| matches!(warning, SynthesisWarning::ExtremeMoraLength { .. }))
+            .count();
+        assert_eq!(expected_mora_warnings, mora_warnings);
+        assert_eq!(expected_pause_warnings, warnings.len() - mora_warnings,);
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn synthesizer_handle_shares_state() {
+        let synthesizer = Synthesizer::new_with_initialize(
+            Arc::new(OpenJtalk::new_without_dic()),
+            &InitializeOptions {
+                acceleration_mode: AccelerationMode::Cpu,
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap()
+        .into_handle();
+        let model = &open_default_vvm_file().await;
+
+        let clone = synthesizer.clone();
+        tokio::spawn({
+            let model = model.clone();
+            async move { clone.load_voice_model(&model).await }
+        })
+        .await
+        .unwrap()
+        .unwrap();
+        assert!(synthesizer.is_loaded_voice_model(model.id()));
+
+        synthesizer.clone().unload_voice_model(model.id()).unwrap();
+        assert!(!synthesizer.is_loaded_voice_model(model.id()));
+    }
+
+    #[rstest]
+    #[case(1, true)]
+    #[tokio::test]
+    async fn is_loaded_model_by_style_id_works(#[case] style_id: u32, #[case] expected: bool) {
+        let style_id = StyleId::new(style_id);
+        let syntesizer = Synthesizer::new_with_initialize(
+            Arc::new(OpenJtalk::new_without_dic()),
+            &InitializeOptions {
+                acceleration_mode: AccelerationMode::Cpu,
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+        assert!(
+            !syntesizer.is_loaded_model_by_style_id(style_id),
+            "expected is_model_loaded to return false, but got true",
+        );
+        syntesizer
+            .load_voice_model(&open_default_vvm_file().await)
+            .await
+            .unwrap();
+
+        assert_eq!(
+            syntesizer.is_loaded_model_by_style_id(style_id),
+            expected,
+            "expected is_model_loaded return value against style_id `{style_id}` is `{expected}`, but got `{}`",
+            !expected
+        );
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn predict_duration_works() {
+        let syntesizer = Synthesizer::new_with_initialize(
+            Arc::new(OpenJtalk::new_without_dic()),
+            &InitializeOptions {
+                acceleration_mode: AccelerationMode::Cpu,
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+
+        syntesizer
+            .load_voice_model(&open_default_vvm_file().await)
+            .await
+            .unwrap();
+
+        // 「こんにちは、音声合成の世界へようこそ」という文章を変換して得た phoneme_vector
+        let phoneme_vector = [
+            0, 23, 30, 4, 28, 21, 10, 21, 42, 7, 0, 30, 4, 35, 14, 14, 16, 30, 30, 35, 14, 14, 28,
+            30, 35, 14, 23, 7, 21, 14, 43, 30, 30, 23, 30, 35, 30, 0,
+        ];
+
+        let result = syntesizer
+            .predict_duration(&phoneme_vector, StyleId::new(1))
+            .await;
+
+        assert!(result.is_ok(), "{result:?}");
+        assert_eq!(result.unwrap().len(), phoneme_vector.len());
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn predict_intonation_works() {
+        let syntesizer = Synthesizer::new_with_initialize(
+            Arc::new(OpenJtalk::new_without_dic()),
+            &InitializeOptions {
+                acceleration_mode: AccelerationMode::Cpu,
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+        syntesizer
+            .load_voice_model(&open_default_vvm_file().await)
+            .await
+            .unwrap();
+
+        // 「テスト」という文章に対応する入力
+        let vowel_phoneme_vector = [0, 14, 6, 30, 0];
+        let consonant_phoneme_vector = [-1, 37, 35, 37, -1];
+        let start_accent_vector = [0, 1, 0, 0, 0];
+        let end_accent_vector = [0, 1, 0, 0, 0];
+        let start_accent_phrase_vector = [0, 1, 0, 0, 0];
+        let end_accent_phrase_vector = [0, 0, 0, 1, 0];
+
+        let result = syntesizer
+            .predict_intonation(
+                vowel_phoneme_vector.len(),
+                &vowel_phoneme_vector,
+                &consonant_phoneme_vector,
+                &start_accent_vector,
+                &end_accent_vector,
+                &start_accent_phrase_vector,
+                &end_accent_phrase_vector,
+                StyleId::new(1),
+            )
+            .await;
+
+        assert!(result.is_ok(), "{result:?}");
+        assert_eq!(result.unwrap().len(), vowel_phoneme_vector.len());
+    }
+
+    #[cfg(feature = "ndarray")]
+    #[rstest]
+    #[tokio::test]
+    async fn predict_intonation_nd_works() {
+        let syntesizer = Synthesizer::new_with_initialize(
+            Arc::new(OpenJtalk::new_without_dic()),
+            &InitializeOptions {
+                acceleration_mode: AccelerationMode::Cpu,
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+        syntesizer
+            .load_voice_model(&open_default_vvm_file().await)
+            .await
+            .unwrap();
+
+        // 「テスト」という文章に対応する入力
+        let vowel_phoneme_vector = [0, 14, 6, 30, 0];
+        let consonant_phoneme_vector = [-1, 37, 35, 37, -1];
+        let start_accent_vector = [0, 1, 0, 0, 0];
+        let end_accent_vector = [0, 1, 0, 0, 0];
+        let start_accent_phrase_vector = [0, 1, 0, 0, 0];
+        let end_accent_phrase_vector = [0, 0, 0, 1, 0];
+
+        let expected = syntesizer
+            .predict_intonation(
+                vowel_phoneme_vector.len(),
+                &vowel_phoneme_vector,
+                &consonant_phoneme_vector,
+                &start_accent_vector,
+                &end_accent_vector,
+                &start_accent_phrase_vector,
+                &end_accent_phrase_vector,
+                StyleId::new(1),
+            )
+            .await
+            .unwrap();
+        let result = syntesizer
+            .predict_intonation_nd(
+                ndarray::arr1(&vowel_phoneme_vector),
+                ndarray::aview1(&consonant_phoneme_vector),
+                ndarray::aview1(&start_accent_vector),
+                ndarray::aview1(&end_accent_vector),
+                ndarray::aview1(&start_accent_phrase_vector),
+                ndarray::aview1(&end_accent_phrase_vector),
+                StyleId::new(1),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(expected, result.to_vec());
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn decode_works() {
+        let syntesizer = Synthesizer::new_with_initialize(
+            Arc::new(OpenJtalk::new_without_dic()),
+            &InitializeOptions {
+                acceleration_mode: AccelerationMode::Cpu,
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+        syntesizer
+            .load_voice_model(&open_default_vvm_file().await)
+            .await
+            .unwrap();
+
+        // 「テスト」という文章に対応する入力
+        const F0_LENGTH: usize = 69;
+        let mut f0 = [0.; F0_LENGTH];
+        f0[9..24].fill(5.905218);
+        f0[37..60].fill(5.565851);
+
+        const PHONEME_SIZE: usize = 45;
+        let mut phoneme = [0.; PHONEME_SIZE * F0_LENGTH];
+        let mut set_one = |index, range| {
+            for i in range {
+                phoneme[i * PHONEME_SIZE + index] = 1.;
+            }
+        };
+        set_one(0, 0..9);
+        set_one(37, 9..13);
+        set_one(14, 13..24);
+        set_one(35, 24..30);
+        set_one(6, 30..37);
+        set_one(37, 37..45);
+        set_one(30, 45..60);
+        set_one(0, 60..69);
+
+        let result = syntesizer
+            .decode(F0_LENGTH, PHONEME_SIZE, &f0, &phoneme, StyleId::new(1))
+            .await;
+
+        assert!(result.is_ok(), "{result:?}");
+        assert_eq!(result.unwrap().len(), F0_LENGTH * 256);
+    }
+
+    type TextConsonantVowelData =
+        [(&'static [(&'static str, &'static str, &'static str)], usize)];
+
+    // [([(テキスト, 母音, 子音), ...], アクセントの位置), ...] の形式
+    const TEXT_CONSONANT_VOWEL_DATA1: &TextConsonantVowelData = &[
+        (&[("コ", "k", "o"), ("レ", "r", "e"), ("ワ", "w", "a")], 3),
+        (
+            &[
+                ("テ", "t", "e"),
+                ("ス", "s", "U"),
+                ("ト", "t", "o"),
+                ("デ", "d", "e"),
+                ("ス", "s", "U"),
+            ],
+            1,
+        ),
+    ];
+
+    const TEXT_CONSONANT_VOWEL_DATA2: &TextConsonantVowelData = &[
+        (&[("コ", "k", "o"), ("レ", "r", "e"), ("ワ", "w", "a")], 1),
+        (
+            &[
+                ("テ", "t", "e"),
+                ("ス", "s", "U"),
+                ("ト", "t", "o"),
+                ("デ", "d", "e"),
+                ("ス", "s", "U"),
+            ],
+            3,
+        ),
+    ];
+
+    #[rstest]
+    #[case(
+        "これはテストです",
+        false,
+        TEXT_CONSONANT_VOWEL_DATA1,
+        "コレワ'/テ'_ストデ_ス"
+    )]
+    #[case(
+        "コ'レワ/テ_スト'デ_ス",
+        true,
+        TEXT_CONSONANT_VOWEL_DATA2,
+        "コ'レワ/テ_スト'デ_ス"
+    )]
+    #[tokio::test]
+    async fn audio_query_works(
+        #[case] input_text: &str,
+        #[case] input_kana_option: bool,
+        #[case] expected_text_consonant_vowel_data: &TextConsonantVowelData,
+        #[case] expected_kana_text: &str,
+    ) {
+        let syntesizer = Synthesizer::new_with_initialize(
+            Arc::new(OpenJtalk::new_with_initialize(OPEN_JTALK_DIC_DIR).unwrap()),
+            &InitializeOptions {
+                acceleration_mode: AccelerationMode::Cpu,
+                load_all_models: true,
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+
+        let query = syntesizer
+            .audio_query(
+                input_text,
+                StyleId::new(0),
+                &AudioQueryOptions {
+                    kana: input_kana_option,
+                    ..Default::default()
+                },
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(
+            query.accent_phrases().len(),
+            expected_text_consonant_vowel_data.len()
+        );
+
+        for (accent_phrase, (text_consonant_vowel_slice, accent_pos)) in
+            std::iter::zip(query.accent_phrases(), expected_text_consonant_vowel_data)
+        {
+            assert_eq!(
+                accent_phrase.moras().len(),
+                text_consonant_vowel_slice.len()
+            );
+            assert_eq!(accent_phrase.accent(), accent_pos);
+
+            for (mora, (text, consonant, vowel)) in
+                std::iter::zip(accent_phrase.moras(), *text_consonant_vowel_slice)
+            {
+                assert_eq!(mora.text(), text);
+                // NOTE: 子音の長さが必ず非ゼロになるテストケースを想定している
+                assert_ne!(
+                    mora.consonant_length(),
+                    &Some(0.),
+                    "expected mora.consonant_length is not Some(0.0), but got Some(0.0)."
+                );
+                assert_eq!(mora.consonant(), &Some(consonant.to_string()));
+                assert_eq!(mora.vowel(), vowel);
+                // NOTE: 母音の長さが必ず非ゼロになるテストケースを想定している
+                assert_ne!(
+                    mora.vowel_length(),
+                    &0.,
+                    "expected mora.vowel_length is not 0.0, but got 0.0."
+                );
+            }
+        }
+
+        assert_eq!(query.kana().as_deref(), Some(expected_kana_text));
+    }
+
+    #[rstest]
+    #[case("これはテストです", false, TEXT_CONSONANT_VOWEL_DATA1)]
+    #[case("コ'レワ/テ_スト'デ_ス", true, TEXT_CONSONANT_VOWEL_DATA2)]
+    #[tokio::test]
+    async fn accent_phrases_works(
+        #[case] input_text: &str,
+        #[case] input_kana_option: bool,
+        #[case] expected_text_consonant_vowel_data: &TextConsonantVowelData,
+    ) {
+        let syntesizer = Synthesizer::new_with_initialize(
+            Arc::new(OpenJtalk::new_with_initialize(OPEN_JTALK_DIC_DIR).unwrap()),
+            &InitializeOptions {
+                acceleration_mode: AccelerationMode::Cpu,
+                load_all_models: true,
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+
+        let accent_phrases = syntesizer
+            .create_accent_phrases(
+                input_text,
+                StyleId::new(0),
+                &AccentPhrasesOptions {
+                    kana: input_kana_option,
+                    ..Default::default()
+                },
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(
+            accent_phrases.len(),
+            expected_text_consonant_vowel_data.len()
+        );
+
+        for (accent_phrase, (text_consonant_vowel_slice, accent_pos)) in
+            std::iter::zip(accent_phrases, expected_text_consonant_vowel_data)
+        {
+            assert_eq!(
+                accent_phrase.moras().len(),
+                text_consonant_vowel_slice.len()
+            );
+            assert_eq!(accent_phrase.accent(), accent_pos);
+
+            for (mora, (text, consonant, vowel)) in
+                std::iter::zip(accent_phrase.moras(), *text_consonant_vowel_slice)
+            {
+                assert_eq!(mora.text(), text);
+                // NOTE: 子音の長さが必ず非ゼロになるテストケースを想定している
+                assert_ne!(
+                    mora.consonant_length(),
+                    &Some(0.),
+                    "expected mora.consonant_length is not Some(0.0), but got Some(0.0)."
+                );
+                assert_eq!(mora.consonant(), &Some(consonant.to_string()));
+                assert_eq!(mora.vowel(), vowel);
+                // NOTE: 母音の長さが必ず非ゼロになるテストケースを想定している
+                assert_ne!(
+                    mora.vowel_length(),
+                    &0.,
+                    "expected mora.vowel_length is not 0.0, but got 0.0."
+                );
+            }
+        }
+    }
+
+    #[rstest]
+    fn accent_phrases_from_kana_works() {
+        let accent_phrases =
+            Synthesizer::accent_phrases_from_kana("コ'レワ/テ_スト'デ_ス").unwrap();
+
+        assert_eq!(accent_phrases.len(), TEXT_CONSONANT_VOWEL_DATA2.len());
+        for (accent_phrase, (text_consonant_vowel_slice, accent_pos)) in
+            std::iter::zip(accent_phrases, TEXT_CONSONANT_VOWEL_DATA2)
+        {
+            assert_eq!(accent_phrase.accent(), accent_pos);
+            assert_eq!(
+                accent_phrase.moras().len(),
+                text_consonant_vowel_slice.len()
+            );
+            for (mora, (text, consonant, vowel)) in
+                std::iter::zip(accent_phrase.moras(), *text_consonant_vowel_slice)
+            {
+                assert_eq!(mora.text(), text);
+                assert_eq!(mora.consonant(), &Some(consonant.to_string()));
+                assert_eq!(mora.vowel(), vowel);
+                assert_eq!(mora.pitch(), &0.);
+            }
+        }
+
+        assert!(matches!(
+            Synthesizer::accent_phrases_from_kana("コレワ"),
+            Err(Error::ParseKana(_)),
+        ));
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn mora_length_works() {
+        let syntesizer = Synthesizer::new_with_initialize(
+            Arc::new(OpenJtalk::new_with_initialize(OPEN_JTALK_DIC_DIR).unwrap()),
+            &InitializeOptions {
+                acceleration_mode: AccelerationMode::Cpu,
+                load_all_models: true,
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+
+        let accent_phrases = syntesizer
+            .create_accent_phrases("これはテストです", StyleId::new(0), &Default::default())
+            .await
+            .unwrap();
+
+        let modified_accent_phrases = syntesizer
+            .replace_phoneme_length(&accent_phrases, StyleId::new(1))
+            .await
+            .unwrap();
+
+        // NOTE: 一つでも母音の長さが変わっていれば、動作しているとみなす
+        assert!(
+            any_mora_param_changed(
+                &accent_phrases,
+                &modified_accent_phrases,
+                MoraModel::vowel_length
+            ),
+            "mora_length() does not work: mora.vowel_length() is not changed."
+        );
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn mora_pitch_works() {
+        let syntesizer = Synthesizer::new_with_initialize(
+            Arc::new(OpenJtalk::new_with_initialize(OPEN_JTALK_DIC_DIR).unwrap()),
+            &InitializeOptions {
+                acceleration_mode: AccelerationMode::Cpu,
+                load_all_models: true,
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+
+        let accent_phrases = syntesizer
+            .create_accent_phrases("これはテストです", StyleId::new(0), &Default::default())
+            .await
+            .unwrap();
+
+        let modified_accent_phrases = syntesizer
+            .replace_mora_pitch(&accent_phrases, StyleId::new(1))
+            .await
+            .unwrap();
+
+        // NOTE: 一つでも音高が変わっていれば、動作しているとみなす
+        assert!(
+            any_mora_param_changed(&accent_phrases, &modified_accent_phrases, MoraModel::pitch),
+            "mora_pitch() does not work: mora.pitch() is not changed."
+        );
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn mora_data_works() {
+        let syntesizer = Synthesizer::new_with_initialize(
+            Arc::new(OpenJtalk::new_with_initialize(OPEN_JTALK_DIC_DIR).unwrap()),
+            &InitializeOptions {
+                acceleration_mode: AccelerationMode::Cpu,
+                load_all_models: true,
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+
+        let accent_phrases = syntesizer
+            .create_accent_phrases("これはテストです", StyleId::new(0), &Default::default())
+            .await
+            .unwrap();
+
+        let modified_accent_phrases = syntesizer
+            .replace_mora_data(&accent_phrases, StyleId::new(1))
+            .await
+            .unwrap();
+
+        // NOTE: 一つでも音高が変わっていれば、動作しているとみなす
+        assert!(
+            any_mora_param_changed(&accent_phrases, &modified_accent_phrases, MoraModel::pitch),
+            "mora_data() does not work: mora.pitch() is not changed."
+        );
+        // NOTE: 一つでも母音の長さが変わっていれば、動作しているとみなす
+        assert!(
+            any_mora_param_changed(
+                &accent_phrases,
+                &modified_accent_phrases,
+                MoraModel::vowel_length
+            ),
+            "mora_data() does not work: mora.vowel_length() is not changed."
+        );
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn audio_query_applies_phrase_segmentation() {
+        let syntesizer = Synthesizer::new_with_initialize(
+            Arc::new(OpenJtalk::new_with_initialize(OPEN_JTALK_DIC_DIR).unwrap()),
+            &InitializeOptions {
+                acceleration_mode: AccelerationMode::Cpu,
+                load_all_models: true,
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+
+        let audio_query = |phrase_segmentation| {
+            syntesizer.audio_query(
+                "これはテストです",
+                StyleId::new(0),
+                &AudioQueryOptions {
+                    phrase_segmentation,
+                    ..Default::default()
+                },
+            )
+        };
+        let default = audio_query(PhraseSegmentation::Default).await.unwrap();
+        let per_mora = audio_query(PhraseSegmentation::PerMora).await.unwrap();
+        let merged = audio_query(PhraseSegmentation::MinMoras(100))
+            .await
+            .unwrap();
+
+        let mora_count = default
+            .accent_phrases()
+            .iter()
+            .map(|ap| ap.moras().len())
+            .sum::<usize>();
+        assert_eq!(mora_count, per_mora.accent_phrases().len());
+        assert_eq!(1, merged.accent_phrases().len());
+        for query in [&per_mora, &merged] {
+            assert_eq!(Some(query.to_kana()), *query.kana());
+        }
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn mora_data_at_works() {
+        let syntesizer = Synthesizer::new_with_initialize(
+            Arc::new(OpenJtalk::new_with_initialize(OPEN_JTALK_DIC_DIR).unwrap()),
+            &InitializeOptions {
+                acceleration_mode: AccelerationMode::Cpu,
+                load_all_models: true,
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+
+        let accent_phrases = syntesizer
+            .create_accent_phrases("これはテストです", StyleId::new(0), &Default::default())
+            .await
+            .unwrap();
+        assert!(accent_phrases.len() >= 2);
+
+        let modified_accent_phrases = syntesizer
+            .replace_mora_data_at(&accent_phrases, 1, StyleId::new(1))
+            .await
+            .unwrap();
+
+        let to_json =
+            |accent_phrase: &AccentPhraseModel| serde_json::to_string(accent_phrase).unwrap();
+        for (i, (before, after)) in
+            std::iter::zip(&accent_phrases, &modified_accent_phrases).enumerate()
+        {
+            if i != 1 {
+                assert_eq!(to_json(before), to_json(after), "{i}番目が変更されている");
+            }
+        }
+        assert!(any_mora_param_changed(
+            &accent_phrases[1..2],
+            &modified_accent_phrases[1..2],
+            MoraModel::vowel_length
+        ));
+
+        let result = syntesizer
+            .replace_mora_data_at(&accent_phrases, accent_phrases.len(), StyleId::new(1))
+            .await;
+        assert!(matches!(
+            result,
+            Err(Error::InvalidAccentPhraseIndex { .. })
+        ));
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn tts_many_works() {
+        let syntesizer = Synthesizer::new_with_initialize(
+            Arc::new(OpenJtalk::new_with_initialize(OPEN_JTALK_DIC_DIR).unwrap()),
+            &InitializeOptions {
+                acceleration_mode: AccelerationMode::Cpu,
+                load_all_models: true,
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+
+        let results = syntesizer
+            .tts_many(
+                "これはテストです。「はい。」と言った！",
+                StyleId::new(0),
+                &Default::default(),
+            )
+            .collect::<Vec<_>>()
+            .await;
+
+        let sentences = results
+            .into_iter()
+            .map(|result| {
+                let (sentence, wav) = result.unwrap();
+                assert_eq!(b"RIFF", &wav[..4]);
+                sentence
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(["これはテストです。", "「はい。」と言った！"], *sentences);
+    }
+
+    #[rstest]
+    #[case("これはテストです。", 0.5)]
+    #[case("「はい。」", 0.5)]
+    #[case("本当？", 0.4)]
+    #[case("やった！", 0.4)]
+    #[case("以上", AudioQueryOptions::DEFAULT_PHONEME_LENGTH)]
+    fn pause_model_pause_after_works(#[case] sentence: &str, #[case] expected: f32) {
+        assert_eq!(expected, PauseModel::default().pause_after(sentence));
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn tts_many_applies_pause_model() {
+        let syntesizer = Synthesizer::new_with_initialize(
+            Arc::new(OpenJtalk::new_with_initialize(OPEN_JTALK_DIC_DIR).unwrap()),
+            &InitializeOptions {
+                acceleration_mode: AccelerationMode::Cpu,
+                load_all_models: true,
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+
+        let mut wav_lengths = vec![];
+        for pause_model in [
+            None,
+            Some(PauseModel {
+                period: 1.,
+                ..Default::default()
+            }),
+        ] {
+            let options = &TtsOptions {
+                pause_model,
+                ..Default::default()
+            };
+            let wavs = syntesizer
+                .tts_many("これはテストです。", StyleId::new(0), options)
+                .map_ok(|(_, wav)| wav.len())
+                .try_collect::<Vec<_>>()
+                .await
+                .unwrap();
+            wav_lengths.push(wavs[0]);
+        }
+
+        assert!(wav_lengths[1] > wav_lengths[0], "{wav_lengths:?}");
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn preview_is_cached_until_unload() {
+        let syntesizer = Synthesizer::new_with_initialize(
+            Arc::new(OpenJtalk::new_with_initialize(OPEN_JTALK_DIC_DIR).unwrap()),
+            &InitializeOptions {
+                acceleration_mode: AccelerationMode::Cpu,
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+        let model = &open_default_vvm_file().await;
+        syntesizer.load_voice_model(model).await.unwrap();
+
+        let preview = syntesizer.preview(StyleId::new(0)).await.unwrap();
+        assert_eq!(b"RIFF", &preview[..4]);
+        assert_eq!(1, syntesizer.previews.lock().unwrap().len());
+        assert_eq!(preview, syntesizer.preview(StyleId::new(0)).await.unwrap());
+
+        syntesizer.unload_voice_model(model.id()).unwrap();
+        assert!(syntesizer.previews.lock().unwrap().is_empty());
+        assert!(matches!(
+            syntesizer.preview(StyleId::new(0)).await,
+            Err(Error::InvalidStyleId { .. })
+        ));
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn tts_stream_works() {
+        let syntesizer = Synthesizer::new_with_initialize(
+            Arc::new(OpenJtalk::new_with_initialize(OPEN_JTALK_DIC_DIR).unwrap()),
+            &InitializeOptions {
+                acceleration_mode: AccelerationMode::Cpu,
+                load_all_models: true,
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+
+        let text = "これはテストです、よろしくお願いします。";
+        let wav = syntesizer
+            .tts(text, StyleId::new(0), &Default::default())
+            .await
+            .unwrap();
+        let data = &wav[44..];
+
+        let chunks = syntesizer
+            .tts_stream(text, StyleId::new(0), &Default::default())
+            .try_collect::<Vec<_>>()
+            .await
+            .unwrap();
+        assert_eq!(vec![data], chunks);
+
+        let chunks = syntesizer
+            .tts_stream(
+                text,
+                StyleId::new(0),
+                &TtsOptions {
+                    prioritize_first_packet: true,
+                    ..Default::default()
+                },
+            )
+            .try_collect::<Vec<_>>()
+            .await
+            .unwrap();
+        assert_eq!(2, chunks.len());
+        assert_eq!(data.len(), chunks.concat().len());
+        assert_eq!(data[chunks[0].len()..], chunks[1]);
+    }
+
+    #[rstest]
+    #[case(VoiceMode::Normal, vec![])]
+    #[case(
+        VoiceMode::Whisper,
+        vec![BiquadBand {
+            kind: BiquadKind::Peaking,
+            frequency: 1000.,
+            gain_db: 6.,
+            q: 1.,
+        }]
+    )]
+    #[tokio::test]
+    async fn synthesis_stream_matches_synthesis(
+        #[case] voice_mode: VoiceMode,
+        #[case] eq: Vec<BiquadBand>,
+    ) {
+        let syntesizer = Synthesizer::new_with_initialize(
+            Arc::new(OpenJtalk::new_with_initialize(OPEN_JTALK_DIC_DIR).unwrap()),
+            &InitializeOptions {
+                acceleration_mode: AccelerationMode::Cpu,
+                load_all_models: true,
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+
+        let query = syntesizer
+            .audio_query(
+                "これはテストです、よろしくお願いします。",
+                StyleId::new(0),
+                &Default::default(),
+            )
+            .await
+            .unwrap();
+        let options = SynthesisOptions {
+            voice_mode,
+            eq,
+            output_gain_db: -3.,
+            ..SynthesisOptions::from(&TtsOptions::default())
+        };
+        let wav = syntesizer
+            .synthesis(&query, StyleId::new(0), &options)
+            .await
+            .unwrap();
+        let data = &wav[44..];
+
+        let chunks = syntesizer
+            .synthesis_stream(&query, StyleId::new(0), &options)
+            .try_collect::<Vec<_>>()
+            .await
+            .unwrap();
+        assert_eq!(vec![data], chunks);
+
+        let chunks = syntesizer
+            .synthesis_stream(
+                &query,
+                StyleId::new(0),
+                &SynthesisOptions {
+                    prioritize_first_packet: true,
+                    ..options
+                },
+            )
+            .try_collect::<Vec<_>>()
+            .await
+            .unwrap();
+        assert_eq!(2, chunks.len());
+        let (head, tail) = data.split_at(chunks[0].len());
+        assert_eq!(data.len(), chunks.concat().len());
+        assert_eq!(tail, chunks[1]);
+
+        // 先頭のアクセント句は後続の文脈を一部欠いて推論されるため、わずかな違いのみを許す
+        let samples = |pcm: &[u8]| {
+            pcm.chunks_exact(2)
+                .map(|b| f32::from(i16::from_le_bytes([b[0], b[1]])) / f32::from(i16::MAX))
+                .collect::<Vec<_>>()
+        };
+        let (expected, actual) = (samples(head), samples(&chunks[0]));
+        let rms = (expected
+            .iter()
+            .zip(&actual)
+            .map(|(a, b)| (a - b).powi(2))
+            .sum::<f32>()
+            / expected.len() as f32)
+            .sqrt();
+        assert!(rms < 1e-2, "{rms}");
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn synthesis_to_writer_works() {
+        let syntesizer = Synthesizer::new_with_initialize(
+            Arc::new(OpenJtalk::new_with_initialize(OPEN_JTALK_DIC_DIR).unwrap()),
+            &InitializeOptions {
+                acceleration_mode: AccelerationMode::Cpu,
+                load_all_models: true,
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+
+        let query = syntesizer
+            .audio_query(
+                "これはテストです、よろしくお願いします。",
+                StyleId::new(0),
+                &Default::default(),
+            )
+            .await
+            .unwrap();
+        let options = SynthesisOptions::from(&TtsOptions::default());
+        let wav = syntesizer
+            .synthesis(&query, StyleId::new(0), &options)
+            .await
+            .unwrap();
+
+        let mut written = vec![];
+        syntesizer
+            .synthesis_to_writer(&query, StyleId::new(0), &options, &mut written)
+            .await
+            .unwrap();
+        assert_eq!(wav.len(), written.len());
+        assert_eq!(wav[..44], written[..44]);
+        assert_eq!(
+            crate::wav::duration_secs(&wav),
+            crate::wav::duration_secs(&written),
+        );
+
+        let (mut closed, reader) = tokio::io::duplex(64);
+        drop(reader);
+        let result = syntesizer
+            .synthesis_to_writer(&query, StyleId::new(0), &options, &mut closed)
+            .await;
+        assert!(matches!(result, Err(Error::WriteOutput(_))), "{result:?}");
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn tts_captures_tensors() {
+        let syntesizer = Synthesizer::new_with_initialize(
+            Arc::new(OpenJtalk::new_with_initialize(OPEN_JTALK_DIC_DIR).unwrap()),
+            &InitializeOptions {
+                acceleration_mode: AccelerationMode::Cpu,
+                load_all_models: true,
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+
+        let capture = TensorCapture::new();
+        syntesizer
+            .tts(
+                "これはテストです",
+                StyleId::new(0),
+                &TtsOptions {
+                    capture_tensors: Some(capture.clone()),
+                    ..Default::default()
+                },
+            )
+            .await
+            .unwrap();
+
+        let captured = capture.take();
+        assert!(!captured.predict_duration.is_empty());
+        assert!(!captured.predict_intonation.is_empty());
+        // アクセント句が2つ以上ある場合は、先頭とそれ以降に分けて推論される
+        assert!(!captured.decode.is_empty());
+        for decode in &captured.decode {
+            assert_eq!(decode.length, decode.f0.len());
+            assert_eq!(decode.length * decode.phoneme_size, decode.phoneme.len());
+        }
+        assert_eq!(CapturedTensors::default(), capture.take());
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn tts_applies_scale_overrides() {
+        let syntesizer = Synthesizer::new_with_initialize(
+            Arc::new(OpenJtalk::new_with_initialize(OPEN_JTALK_DIC_DIR).unwrap()),
+            &InitializeOptions {
+                acceleration_mode: AccelerationMode::Cpu,
+                load_all_models: true,
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+        let syntesizer = &syntesizer;
+        let tts = move |options| async move {
+            syntesizer
+                .tts("これはテストです", StyleId::new(0), &options)
+                .await
+                .unwrap()
+        };
+
+        let default = tts(TtsOptions::default()).await;
+        let fast = tts(TtsOptions {
+            speed_scale: Some(2.),
+            ..Default::default()
+        })
+        .await;
+        assert!(fast.len() < default.len());
+
+        let silent = tts(TtsOptions {
+            volume_scale: Some(0.),
+            ..Default::default()
+        })
+        .await;
+        assert_eq!(default.len(), silent.len());
+        assert!(silent[44..].iter().all(|&b| b == 0));
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn synthesis_preview_has_same_length() {
+        let syntesizer = Synthesizer::new_with_initialize(
+            Arc::new(OpenJtalk::new_with_initialize(OPEN_JTALK_DIC_DIR).unwrap()),
+            &InitializeOptions {
+                acceleration_mode: AccelerationMode::Cpu,
+                load_all_models: true,
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+
+        let audio_query = &syntesizer
+            .audio_query("これはテストです", StyleId::new(0), &Default::default())
+            .await
+            .unwrap();
+        let syntesizer = &syntesizer;
+        let synthesis = |quality| async move {
+            syntesizer
+                .synthesis(
+                    audio_query,
+                    StyleId::new(0),
+                    &SynthesisOptions {
+                        quality,
+                        ..SynthesisOptions::from(&TtsOptions::default())
+                    },
+                )
+                .await
+        };
+        let full = synthesis(SynthesisQuality::Full).await.unwrap();
+        let preview = synthesis(SynthesisQuality::Preview).await.unwrap();
+
+        assert_eq!(full.len(), preview.len());
+        assert_ne!(full, preview);
+    }
+
+    #[rstest]
+    #[case("")]
+    #[case("　")]
+    #[case(" \n")]
+    #[case("、。")]
+    #[case("「……」！？")]
+    #[tokio::test]
+    async fn silent_text_yields_silent_wav(#[case] text: &str) {
+        let syntesizer = Synthesizer::new_with_initialize(
+            Arc::new(OpenJtalk::new_with_initialize(OPEN_JTALK_DIC_DIR).unwrap()),
+            &InitializeOptions {
+                acceleration_mode: AccelerationMode::Cpu,
+                load_all_models: true,
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+
+        let query = syntesizer
+            .audio_query(text, StyleId::new(0), &Default::default())
+            .await
+            .unwrap();
+        assert!(query.accent_phrases().is_empty());
+
+        let wav = syntesizer
+            .tts(text, StyleId::new(0), &Default::default())
+            .await
+            .unwrap();
+        assert!(wav.len() > 44);
+        assert!(wav[44..].iter().all(|&b| b == 0));
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn synthesis_from_accent_phrases_works() {
+        let syntesizer = Synthesizer::new_with_initialize(
+            Arc::new(OpenJtalk::new_with_initialize(OPEN_JTALK_DIC_DIR).unwrap()),
+            &InitializeOptions {
+                acceleration_mode: AccelerationMode::Cpu,
+                load_all_models: true,
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+
+        let audio_query = syntesizer
+            .audio_query("これはテストです", StyleId::new(0), &Default::default())
+            .await
+            .unwrap();
+        let prosody = ProsodyParams {
+            speed_scale: *audio_query.speed_scale(),
+            pitch_scale: *audio_query.pitch_scale(),
+            intonation_scale: *audio_query.intonation_scale(),
+            volume_scale: *audio_query.volume_scale(),
+            pre_phoneme_length: *audio_query.pre_phoneme_length(),
+            post_phoneme_length: *audio_query.post_phoneme_length(),
+            output_sampling_rate: *audio_query.output_sampling_rate(),
+            output_stereo: *audio_query.output_stereo(),
+        };
+        let options = SynthesisOptions::from(&TtsOptions::default());
+
+        let expected = syntesizer
+            .synthesis(&audio_query, StyleId::new(0), &options)
+            .await
+            .unwrap();
+        let wav = syntesizer
+            .synthesis_from_accent_phrases(
+                audio_query.accent_phrases(),
+                StyleId::new(0),
+                &prosody,
+                &options,
+            )
+            .await
+            .unwrap();
+        assert_eq!(expected, wav);
+    }
+
+    #[rstest]
+    #[case(24000, false)]
+    #[case(48000, true)]
+    #[tokio::test]
+    async fn synthesis_described_works(
+        #[case] output_sampling_rate: u32,
+        #[case] output_stereo: bool,
+    ) {
+        let syntesizer = Synthesizer::new_with_initialize(
+            Arc::new(OpenJtalk::new_with_initialize(OPEN_JTALK_DIC_DIR).unwrap()),
+            &InitializeOptions {
+                acceleration_mode: AccelerationMode::Cpu,
+                load_all_models: true,
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+
+        let audio_query = syntesizer
+            .audio_query("これはテストです", StyleId::new(0), &Default::default())
+            .await
+            .unwrap();
+        let audio_query = AudioQueryModel::from_accent_phrases(
+            audio_query.accent_phrases().clone(),
+            &ProsodyParams {
+                output_sampling_rate,
+                output_stereo,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        let options = SynthesisOptions::from(&TtsOptions::default());
+
+        let expected = syntesizer
+            .synthesis(&audio_query, StyleId::new(0), &options)
+            .await
+            .unwrap();
+        let clip = syntesizer
+            .synthesis_described(&audio_query, StyleId::new(0), &options)
+            .await
+            .unwrap();
+        assert_eq!(expected, clip.bytes);
+        assert_eq!(output_sampling_rate, clip.sampling_rate);
+        assert_eq!(if output_stereo { 2 } else { 1 }, clip.channels);
+        assert_eq!(16, clip.bit_depth);
+        assert_eq!(
+            crate::wav::duration_secs(&expected),
+            Some(clip.duration_secs)
+        );
+    }
+
+    #[rstest]
+    #[case(0)]
+    #[case(44100)]
+    #[tokio::test]
+    async fn synthesis_described_rejects_unsupported_sampling_rate(
+        #[case] output_sampling_rate: u32,
+    ) {
+        let syntesizer = Synthesizer::new_with_initialize(
+            Arc::new(OpenJtalk::new_without_dic()),
+            &InitializeOptions {
+                acceleration_mode: AccelerationMode::Cpu,
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+
+        let audio_query = AudioQueryModel::from_accent_phrases(
+            vec![],
+            &ProsodyParams {
+                output_sampling_rate,
+                ..Default::default()
+            },
+        )
+        .unwrap();
         let result = syntesizer
-            .predict_intonation(
-                vowel_phoneme_vector.len(),
-                &vowel_phoneme_vector,
-                &consonant_phoneme_vector,
-                &start_accent_vector,
-                &end_accent_vector,
-                &start_accent_phrase_vector,
-                &end_accent_phrase_vector,
-                StyleId::new(1),
+            .synthesis_described(
+                &audio_query,
+                StyleId::new(0),
+                &SynthesisOptions::from(&TtsOptions::default()),
+            )
+            .await;
+        assert!(
+            matches!(
+                result,
+                Err(Error::UnsupportedSamplingRate { sampling_rate }) if sampling_rate == output_sampling_rate
+            ),
+            "{result:?}",
+        );
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn synthesis_samples_has_same_length() {
+        let syntesizer = Synthesizer::new_with_initialize(
+            Arc::new(OpenJtalk::new_with_initialize(OPEN_JTALK_DIC_DIR).unwrap()),
+            &InitializeOptions {
+                acceleration_mode: AccelerationMode::Cpu,
+                load_all_models: true,
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+
+        let audio_query = syntesizer
+            .audio_query(
+                "こんにちは、これはテストです",
+                StyleId::new(0),
+                &Default::default(),
+            )
+            .await
+            .unwrap();
+        let options = SynthesisOptions::from(&TtsOptions::default());
+
+        let wav = syntesizer
+            .synthesis(&audio_query, StyleId::new(0), &options)
+            .await
+            .unwrap();
+
+        let mut reader = syntesizer.synthesis_samples(&audio_query, StyleId::new(0), &options);
+        let mut buf = [0.; 1000];
+        let mut num_samples = 0;
+        loop {
+            let len = reader.read_samples(&mut buf).await.unwrap();
+            if len == 0 {
+                break;
+            }
+            num_samples += len;
+        }
+        assert_eq!((wav.len() - 44) / 2, num_samples);
+    }
+
+    #[rstest]
+    #[case(None, None, 0.1, 0.1)]
+    #[case(Some(0.), None, 0., 0.1)]
+    #[case(Some(0.05), Some(0.3), 0.05, 0.3)]
+    #[tokio::test]
+    async fn audio_query_applies_phoneme_length_overrides(
+        #[case] pre_phoneme_length: Option<f32>,
+        #[case] post_phoneme_length: Option<f32>,
+        #[case] expected_pre: f32,
+        #[case] expected_post: f32,
+    ) {
+        let syntesizer = Synthesizer::new_with_initialize(
+            Arc::new(OpenJtalk::new_with_initialize(OPEN_JTALK_DIC_DIR).unwrap()),
+            &InitializeOptions {
+                acceleration_mode: AccelerationMode::Cpu,
+                load_all_models: true,
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+
+        let query = syntesizer
+            .audio_query(
+                "こんにちは",
+                StyleId::new(0),
+                &AudioQueryOptions {
+                    pre_phoneme_length,
+                    post_phoneme_length,
+                    ..Default::default()
+                },
+            )
+            .await
+            .unwrap();
+        assert_eq!(expected_pre, *query.pre_phoneme_length());
+        assert_eq!(expected_post, *query.post_phoneme_length());
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn audio_query_rejects_too_long_text() {
+        let syntesizer = Synthesizer::new_with_initialize(
+            Arc::new(OpenJtalk::new_with_initialize(OPEN_JTALK_DIC_DIR).unwrap()),
+            &InitializeOptions {
+                acceleration_mode: AccelerationMode::Cpu,
+                load_all_models: true,
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+
+        let options = AudioQueryOptions {
+            max_text_length: 5,
+            ..Default::default()
+        };
+        let audio_query = |text| syntesizer.audio_query(text, StyleId::new(0), &options);
+
+        assert!(audio_query("こんにちは").await.is_ok());
+        assert!(matches!(
+            audio_query("こんにちは。").await,
+            Err(Error::TextTooLong { len: 6, max: 5 }),
+        ));
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn benchmark_works() {
+        let syntesizer = Synthesizer::new_with_initialize(
+            Arc::new(OpenJtalk::new_with_initialize(OPEN_JTALK_DIC_DIR).unwrap()),
+            &InitializeOptions {
+                acceleration_mode: AccelerationMode::Cpu,
+                load_all_models: true,
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+
+        let report = syntesizer
+            .benchmark(
+                StyleId::new(0),
+                &["こんにちは", "これはテストです", "よろしく"],
             )
-            .await;
+            .await
+            .unwrap();
 
-        assert!(result.is_ok(), "{result:?}");
-        assert_eq!(result.unwrap().len(), vowel_phoneme_vector.len());
+        assert_eq!(3, report.num_samples);
+        assert!(report.median_latency <= report.p99_latency);
+        assert!(report.chars_per_sec > 0.);
+        assert!(report.realtime_factor > 0.);
+
+        let report = syntesizer.benchmark(StyleId::new(0), &[]).await.unwrap();
+        assert_eq!(BenchmarkReport::default(), report);
     }
 
     #[rstest]
+    #[case("dummy1", "style1", Some(StyleId::new(0)))]
+    #[case("dummy3", "style3-2", Some(StyleId::new(303)))]
+    #[case("dummy1", "style3-2", None)]
+    #[case("unknown", "style1", None)]
     #[tokio::test]
-    async fn decode_works() {
+    async fn resolve_style_works(
+        #[case] speaker: &str,
+        #[case] style: &str,
+        #[case] expected: Option<StyleId>,
+    ) {
         let syntesizer = Synthesizer::new_with_initialize(
             Arc::new(OpenJtalk::new_without_dic()),
             &InitializeOptions {
                 acceleration_mode: AccelerationMode::Cpu,
+                load_all_models: true,
                 ..Default::default()
             },
         )
         .await
         .unwrap();
-        syntesizer
-            .load_voice_model(&open_default_vvm_file().await)
-            .await
-            .unwrap();
-
-        // 「テスト」という文章に対応する入力
-        const F0_LENGTH: usize = 69;
-        let mut f0 = [0.; F0_LENGTH];
-        f0[9..24].fill(5.905218);
-        f0[37..60].fill(5.565851);
-
-        const PHONEME_SIZE: usize = 45;
-        let mut phoneme = [0.; PHONEME_SIZE * F0_LENGTH];
-        let mut set_one = |index, range| {
-            for i in range {
-                phoneme[i * PHONEME_SIZE + index] = 1.;
-            }
-        };
-        set_one(0, 0..9);
-        set_one(37, 9..13);
-        set_one(14, 13..24);
-        set_one(35, 24..30);
-        set_one(6, 30..37);
-        set_one(37, 37..45);
-        set_one(30, 45..60);
-        set_one(0, 60..69);
-
-        let result = syntesizer
-            .decode(F0_LENGTH, PHONEME_SIZE, &f0, &phoneme, StyleId::new(1))
-            .await;
 
-        assert!(result.is_ok(), "{result:?}");
-        assert_eq!(result.unwrap().len(), F0_LENGTH * 256);
+        assert_eq!(expected, syntesizer.resolve_style(speaker, style));
     }
 
-    type TextConsonantVowelData =
-        [(&'static [(&'static str, &'static str, &'static str)], usize)];
-
-    // [([(テキスト, 母音, 子音), ...], アクセントの位置), ...] の形式
-    const TEXT_CONSONANT_VOWEL_DATA1: &TextConsonantVowelData = &[
-        (&[("コ", "k", "o"), ("レ", "r", "e"), ("ワ", "w", "a")], 3),
-        (
-            &[
-                ("テ", "t", "e"),
-                ("ス", "s", "U"),
-                ("ト", "t", "o"),
-                ("デ", "d", "e"),
-                ("ス", "s", "U"),
-            ],
-            1,
-        ),
-    ];
-
-    const TEXT_CONSONANT_VOWEL_DATA2: &TextConsonantVowelData = &[
-        (&[("コ", "k", "o"), ("レ", "r", "e"), ("ワ", "w", "a")], 1),
-        (
-            &[
-                ("テ", "t", "e"),
-                ("ス", "s", "U"),
-                ("ト", "t", "o"),
-                ("デ", "d", "e"),
-                ("ス", "s", "U"),
-            ],
-            3,
-        ),
-    ];
-
     #[rstest]
-    #[case(
-        "これはテストです",
-        false,
-        TEXT_CONSONANT_VOWEL_DATA1,
-        "コレワ'/テ'_ストデ_ス"
-    )]
-    #[case(
-        "コ'レワ/テ_スト'デ_ス",
-        true,
-        TEXT_CONSONANT_VOWEL_DATA2,
-        "コ'レワ/テ_スト'デ_ス"
-    )]
     #[tokio::test]
-    async fn audio_query_works(
-        #[case] input_text: &str,
-        #[case] input_kana_option: bool,
-        #[case] expected_text_consonant_vowel_data: &TextConsonantVowelData,
-        #[case] expected_kana_text: &str,
-    ) {
+    async fn tts_by_name_lists_available_styles_on_error() {
         let syntesizer = Synthesizer::new_with_initialize(
             Arc::new(OpenJtalk::new_with_initialize(OPEN_JTALK_DIC_DIR).unwrap()),
             &InitializeOptions {
@@ -867,64 +4495,21 @@ mod tests {
         .await
         .unwrap();
 
-        let query = syntesizer
-            .audio_query(
-                input_text,
-                StyleId::new(0),
-                &AudioQueryOptions {
-                    kana: input_kana_option,
-                },
-            )
+        let err = syntesizer
+            .tts_by_name("こんにちは", "dummy1", "unknown", &Default::default())
             .await
-            .unwrap();
-
-        assert_eq!(
-            query.accent_phrases().len(),
-            expected_text_consonant_vowel_data.len()
-        );
-
-        for (accent_phrase, (text_consonant_vowel_slice, accent_pos)) in
-            std::iter::zip(query.accent_phrases(), expected_text_consonant_vowel_data)
-        {
-            assert_eq!(
-                accent_phrase.moras().len(),
-                text_consonant_vowel_slice.len()
-            );
-            assert_eq!(accent_phrase.accent(), accent_pos);
-
-            for (mora, (text, consonant, vowel)) in
-                std::iter::zip(accent_phrase.moras(), *text_consonant_vowel_slice)
-            {
-                assert_eq!(mora.text(), text);
-                // NOTE: 子音の長さが必ず非ゼロになるテストケースを想定している
-                assert_ne!(
-                    mora.consonant_length(),
-                    &Some(0.),
-                    "expected mora.consonant_length is not Some(0.0), but got Some(0.0)."
-                );
-                assert_eq!(mora.consonant(), &Some(consonant.to_string()));
-                assert_eq!(mora.vowel(), vowel);
-                // NOTE: 母音の長さが必ず非ゼロになるテストケースを想定している
-                assert_ne!(
-                    mora.vowel_length(),
-                    &0.,
-                    "expected mora.vowel_length is not 0.0, but got 0.0."
-                );
-            }
-        }
+            .unwrap_err();
 
-        assert_eq!(query.kana().as_deref(), Some(expected_kana_text));
+        let Error::StyleNotFound { available, .. } = err else {
+            panic!("unexpected error: {err:?}");
+        };
+        assert!(available.contains(&"dummy1 (style1)".to_owned()));
+        assert!(available.contains(&"dummy3 (style3-2)".to_owned()));
     }
 
     #[rstest]
-    #[case("これはテストです", false, TEXT_CONSONANT_VOWEL_DATA1)]
-    #[case("コ'レワ/テ_スト'デ_ス", true, TEXT_CONSONANT_VOWEL_DATA2)]
     #[tokio::test]
-    async fn accent_phrases_works(
-        #[case] input_text: &str,
-        #[case] input_kana_option: bool,
-        #[case] expected_text_consonant_vowel_data: &TextConsonantVowelData,
-    ) {
+    async fn can_synthesize_works() {
         let syntesizer = Synthesizer::new_with_initialize(
             Arc::new(OpenJtalk::new_with_initialize(OPEN_JTALK_DIC_DIR).unwrap()),
             &InitializeOptions {
@@ -936,56 +4521,39 @@ mod tests {
         .await
         .unwrap();
 
-        let accent_phrases = syntesizer
-            .create_accent_phrases(
-                input_text,
-                StyleId::new(0),
-                &AccentPhrasesOptions {
-                    kana: input_kana_option,
-                },
-            )
+        let audio_query = &syntesizer
+            .audio_query("これはテストです", StyleId::new(0), &Default::default())
             .await
             .unwrap();
-
-        assert_eq!(
-            accent_phrases.len(),
-            expected_text_consonant_vowel_data.len()
+        let inconsistent_query = &AudioQueryModel::new(
+            vec![AccentPhraseModel::new(vec![], 1, None, false)],
+            1.,
+            0.,
+            1.,
+            1.,
+            0.1,
+            0.1,
+            24000,
+            false,
+            None,
         );
 
-        for (accent_phrase, (text_consonant_vowel_slice, accent_pos)) in
-            std::iter::zip(accent_phrases, expected_text_consonant_vowel_data)
-        {
-            assert_eq!(
-                accent_phrase.moras().len(),
-                text_consonant_vowel_slice.len()
-            );
-            assert_eq!(accent_phrase.accent(), accent_pos);
-
-            for (mora, (text, consonant, vowel)) in
-                std::iter::zip(accent_phrase.moras(), *text_consonant_vowel_slice)
-            {
-                assert_eq!(mora.text(), text);
-                // NOTE: 子音の長さが必ず非ゼロになるテストケースを想定している
-                assert_ne!(
-                    mora.consonant_length(),
-                    &Some(0.),
-                    "expected mora.consonant_length is not Some(0.0), but got Some(0.0)."
-                );
-                assert_eq!(mora.consonant(), &Some(consonant.to_string()));
-                assert_eq!(mora.vowel(), vowel);
-                // NOTE: 母音の長さが必ず非ゼロになるテストケースを想定している
-                assert_ne!(
-                    mora.vowel_length(),
-                    &0.,
-                    "expected mora.vowel_length is not 0.0, but got 0.0."
-                );
-            }
-        }
+        assert!(syntesizer
+            .can_synthesize(audio_query, StyleId::new(0))
+            .is_ok());
+        assert!(matches!(
+            syntesizer.can_synthesize(audio_query, StyleId::new(9999)),
+            Err(Error::InvalidStyleId { .. }),
+        ));
+        assert!(matches!(
+            syntesizer.can_synthesize(inconsistent_query, StyleId::new(0)),
+            Err(Error::InconsistentAccentPhrase { index: 0, .. }),
+        ));
     }
 
     #[rstest]
     #[tokio::test]
-    async fn mora_length_works() {
+    async fn estimate_duration_works() {
         let syntesizer = Synthesizer::new_with_initialize(
             Arc::new(OpenJtalk::new_with_initialize(OPEN_JTALK_DIC_DIR).unwrap()),
             &InitializeOptions {
@@ -997,34 +4565,34 @@ mod tests {
         .await
         .unwrap();
 
-        let accent_phrases = syntesizer
-            .create_accent_phrases(
-                "これはテストです",
-                StyleId::new(0),
-                &AccentPhrasesOptions { kana: false },
-            )
+        let text = "これはテストです、よろしく。";
+        let duration = syntesizer
+            .estimate_duration(text, StyleId::new(0), &Default::default())
             .await
             .unwrap();
-
-        let modified_accent_phrases = syntesizer
-            .replace_phoneme_length(&accent_phrases, StyleId::new(1))
+        let wav = syntesizer
+            .tts(text, StyleId::new(0), &Default::default())
             .await
             .unwrap();
 
-        // NOTE: 一つでも母音の長さが変わっていれば、動作しているとみなす
-        assert!(
-            any_mora_param_changed(
-                &accent_phrases,
-                &modified_accent_phrases,
-                MoraModel::vowel_length
-            ),
-            "mora_length() does not work: mora.vowel_length() is not changed."
+        // 16bitモノラルのWAVで、ヘッダは44バイト
+        let num_samples = (wav.len() - 44) / 2;
+        assert_eq!(
+            num_samples,
+            (duration * Synthesizer::DEFAULT_SAMPLING_RATE as f32).round() as usize
         );
     }
 
     #[rstest]
+    #[case(false, 24000, false)]
+    #[case(true, 24000, false)]
+    #[case(true, 48000, true)]
     #[tokio::test]
-    async fn mora_pitch_works() {
+    async fn wav_len_from_query_works(
+        #[case] emit_cue_points: bool,
+        #[case] output_sampling_rate: u32,
+        #[case] output_stereo: bool,
+    ) {
         let syntesizer = Synthesizer::new_with_initialize(
             Arc::new(OpenJtalk::new_with_initialize(OPEN_JTALK_DIC_DIR).unwrap()),
             &InitializeOptions {
@@ -1036,30 +4604,41 @@ mod tests {
         .await
         .unwrap();
 
-        let accent_phrases = syntesizer
-            .create_accent_phrases(
-                "これはテストです",
+        let audio_query = syntesizer
+            .audio_query(
+                "これはテストです、よろしく。",
                 StyleId::new(0),
-                &AccentPhrasesOptions { kana: false },
+                &Default::default(),
             )
             .await
             .unwrap();
+        let audio_query = AudioQueryModel::from_accent_phrases(
+            audio_query.accent_phrases().clone(),
+            &ProsodyParams {
+                output_sampling_rate,
+                output_stereo,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        let options = SynthesisOptions {
+            emit_cue_points,
+            ..SynthesisOptions::from(&TtsOptions::default())
+        };
 
-        let modified_accent_phrases = syntesizer
-            .replace_mora_pitch(&accent_phrases, StyleId::new(1))
+        let wav = syntesizer
+            .synthesis(&audio_query, StyleId::new(0), &options)
             .await
             .unwrap();
-
-        // NOTE: 一つでも音高が変わっていれば、動作しているとみなす
-        assert!(
-            any_mora_param_changed(&accent_phrases, &modified_accent_phrases, MoraModel::pitch),
-            "mora_pitch() does not work: mora.pitch() is not changed."
+        assert_eq!(
+            wav.len(),
+            syntesizer.wav_len_from_query(&audio_query, &options)
         );
     }
 
     #[rstest]
     #[tokio::test]
-    async fn mora_data_works() {
+    async fn text_stats_works() {
         let syntesizer = Synthesizer::new_with_initialize(
             Arc::new(OpenJtalk::new_with_initialize(OPEN_JTALK_DIC_DIR).unwrap()),
             &InitializeOptions {
@@ -1071,34 +4650,27 @@ mod tests {
         .await
         .unwrap();
 
-        let accent_phrases = syntesizer
-            .create_accent_phrases(
-                "これはテストです",
-                StyleId::new(0),
-                &AccentPhrasesOptions { kana: false },
-            )
+        let text = "これはテストです、よろしく。";
+        let stats = syntesizer
+            .text_stats(text, StyleId::new(0), &Default::default())
             .await
             .unwrap();
-
-        let modified_accent_phrases = syntesizer
-            .replace_mora_data(&accent_phrases, StyleId::new(1))
+        let audio_query = syntesizer
+            .audio_query(text, StyleId::new(0), &Default::default())
+            .await
+            .unwrap();
+        let duration = syntesizer
+            .estimate_duration(text, StyleId::new(0), &Default::default())
             .await
             .unwrap();
 
-        // NOTE: 一つでも音高が変わっていれば、動作しているとみなす
-        assert!(
-            any_mora_param_changed(&accent_phrases, &modified_accent_phrases, MoraModel::pitch),
-            "mora_data() does not work: mora.pitch() is not changed."
-        );
-        // NOTE: 一つでも母音の長さが変わっていれば、動作しているとみなす
-        assert!(
-            any_mora_param_changed(
-                &accent_phrases,
-                &modified_accent_phrases,
-                MoraModel::vowel_length
-            ),
-            "mora_data() does not work: mora.vowel_length() is not changed."
+        // コ/レ/ワ/テ/ス/ト/デ/ス/ヨ/ロ/シ/ク
+        assert_eq!(12, stats.mora_count);
+        assert_eq!(
+            audio_query.accent_phrases().len(),
+            stats.accent_phrase_count
         );
+        assert_eq!(duration, stats.estimated_seconds);
     }
 
     fn any_mora_param_changed<T: PartialEq>(
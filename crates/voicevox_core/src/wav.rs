@@ -0,0 +1,140 @@
+//! [`Synthesizer::synthesis`]などが返すWAVデータを扱うためのユーティリティ。
+//!
+//! [`Synthesizer::synthesis`]: crate::Synthesizer::synthesis
+
+/// WAVデータのヘッダから、音声の長さ(秒)を求める。
+///
+/// `fmt `チャンクの1秒あたりのバイト数と、`data`チャンクの大きさから計算する。`cue `などの他の
+/// チャンクは読み飛ばす。RIFF/WAVEとして解釈できない場合や、`data`チャンクが途中で切れている場合は
+/// `None`を返す。
+///
+/// # Example
+///
+/// ```
+/// # #[tokio::main]
+/// # async fn main() -> anyhow::Result<()> {
+/// #     use std::sync::Arc;
+/// #
+/// #     use test_util::OPEN_JTALK_DIC_DIR;
+/// #     use voicevox_core::{
+/// #         AccelerationMode, InitializeOptions, OpenJtalk, StyleId, Synthesizer, VoiceModel,
+/// #     };
+/// #
+/// #     let syntesizer = Synthesizer::new_with_initialize(
+/// #         Arc::new(OpenJtalk::new_with_initialize(OPEN_JTALK_DIC_DIR).unwrap()),
+/// #         &InitializeOptions {
+/// #             acceleration_mode: AccelerationMode::Cpu,
+/// #             ..Default::default()
+/// #         },
+/// #     )
+/// #     .await?;
+/// #
+/// #     let model = &VoiceModel::from_path(concat!(
+/// #         env!("CARGO_MANIFEST_DIR"),
+/// #         "/../../model/sample.vvm",
+/// #     ))
+/// #     .await?;
+/// #     syntesizer.load_voice_model(model).await?;
+/// #
+/// let wav = syntesizer
+///     .tts("こんにちは", StyleId::new(302), &Default::default())
+///     .await?;
+/// let duration = voicevox_core::wav::duration_secs(&wav).unwrap();
+/// #
+/// #     Ok(())
+/// # }
+/// ```
+pub fn duration_secs(wav: &[u8]) -> Option<f32> {
+    let (riff, _) = split_chunk(wav)?;
+    if riff.id != *b"RIFF" || riff.body.get(..4)? != b"WAVE" {
+        return None;
+    }
+
+    let mut byte_rate = None;
+    let mut chunks = &riff.body[4..];
+    while !chunks.is_empty() {
+        let (chunk, rest) = split_chunk(chunks)?;
+        match &chunk.id {
+            b"fmt " => {
+                let rate = u32::from_le_bytes(chunk.body.get(8..12)?.try_into().unwrap());
+                byte_rate = Some(rate).filter(|&rate| rate > 0);
+            }
+            b"data" => return Some(chunk.body.len() as f32 / byte_rate? as f32),
+            _ => {}
+        }
+        chunks = rest;
+    }
+    None
+}
+
+struct Chunk<'a> {
+    id: [u8; 4],
+    body: &'a [u8],
+}
+
+/// 先頭のチャンクと、その後ろ(パディングを除く)に分ける。
+fn split_chunk(bytes: &[u8]) -> Option<(Chunk<'_>, &[u8])> {
+    let id = bytes.get(..4)?.try_into().unwrap();
+    let size = u32::from_le_bytes(bytes.get(4..8)?.try_into().unwrap()) as usize;
+    let body = bytes.get(8..8usize.checked_add(size)?)?;
+    // チャンクは偶数バイトに揃えられる
+    let rest = bytes.get(8 + size + size % 2..).unwrap_or_default();
+    Some((Chunk { id, body }, rest))
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::duration_secs;
+    use crate::{engine::SynthesisEngine, AudioQueryModel};
+
+    fn wav(output_sampling_rate: u32, output_stereo: bool, cue_points: &[u32]) -> Vec<u8> {
+        let query = AudioQueryModel::new(
+            vec![],
+            1.,
+            0.,
+            1.,
+            1.,
+            0.,
+            0.,
+            output_sampling_rate,
+            output_stereo,
+            None,
+        );
+        let wave = vec![0.; SynthesisEngine::DEFAULT_SAMPLING_RATE as usize * 3 / 2];
+        SynthesisEngine::to_wav_with_metrics(&query, &wave, 0., cue_points).0
+    }
+
+    #[rstest]
+    #[case(24000, false, &[])]
+    #[case(48000, true, &[])]
+    #[case(24000, false, &[1, 3])]
+    fn duration_secs_works(
+        #[case] output_sampling_rate: u32,
+        #[case] output_stereo: bool,
+        #[case] cue_points: &[u32],
+    ) {
+        let wav = wav(output_sampling_rate, output_stereo, cue_points);
+        assert_eq!(Some(1.5), duration_secs(&wav));
+    }
+
+    #[rstest]
+    #[case::empty(|_| vec![])]
+    #[case::truncated(|wav| wav[..wav.len() - 1].to_owned())]
+    #[case::not_riff(|mut wav| {
+        wav[..4].copy_from_slice(b"RIFX");
+        wav
+    })]
+    #[case::no_fmt(|mut wav| {
+        wav[12..16].copy_from_slice(b"junk");
+        wav
+    })]
+    #[case::zero_byte_rate(|mut wav| {
+        wav[28..32].fill(0);
+        wav
+    })]
+    fn duration_secs_returns_none_for_malformed_input(#[case] corrupt: fn(Vec<u8>) -> Vec<u8>) {
+        assert_eq!(None, duration_secs(&corrupt(wav(24000, false, &[]))));
+    }
+}
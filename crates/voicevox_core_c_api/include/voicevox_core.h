@@ -78,6 +78,39 @@ enum VoicevoxAccelerationMode
 typedef int32_t VoicevoxAccelerationMode;
 #endif // __cplusplus
 
+/**
+ * ログの重要度。
+ */
+enum VoicevoxLogLevel
+#ifdef __cplusplus
+  : int32_t
+#endif // __cplusplus
+ {
+  /**
+   * エラー
+   */
+  VOICEVOX_LOG_LEVEL_ERROR = 1,
+  /**
+   * 警告
+   */
+  VOICEVOX_LOG_LEVEL_WARN = 2,
+  /**
+   * 情報
+   */
+  VOICEVOX_LOG_LEVEL_INFO = 3,
+  /**
+   * デバッグ情報
+   */
+  VOICEVOX_LOG_LEVEL_DEBUG = 4,
+  /**
+   * より詳細なデバッグ情報
+   */
+  VOICEVOX_LOG_LEVEL_TRACE = 5,
+};
+#ifndef __cplusplus
+typedef int32_t VoicevoxLogLevel;
+#endif // __cplusplus
+
 /**
  * 処理結果を示す結果コード。
  */
@@ -182,6 +215,50 @@ enum VoicevoxResultCode
    * UUIDの変換に失敗した
    */
   VOICEVOX_RESULT_INVALID_UUID_ERROR = 25,
+  /**
+   * 制限時間内に音声モデルの読み込みが完了しなかった
+   */
+  VOICEVOX_RESULT_LOAD_MODEL_TIMEOUT_ERROR = 28,
+  /**
+   * アクセント句やモーラの位置が範囲外だった
+   */
+  VOICEVOX_RESULT_INVALID_ACCENT_PHRASE_POSITION_ERROR = 29,
+  /**
+   * 入力テキストが長すぎた
+   */
+  VOICEVOX_RESULT_TEXT_TOO_LONG_ERROR = 30,
+  /**
+   * 出力先のバッファが小さすぎた
+   */
+  VOICEVOX_RESULT_BUFFER_TOO_SMALL_ERROR = 31,
+  /**
+   * 入力テキスト中のアクセント指定が不正だった
+   */
+  VOICEVOX_RESULT_INVALID_ACCENT_HINT_ERROR = 32,
+  /**
+   * 対応していないバージョンの音声モデルだった
+   */
+  VOICEVOX_RESULT_UNSUPPORTED_MODEL_VERSION_ERROR = 33,
+  /**
+   * 音声モデルが処理に必要なネットワークを含んでいなかった
+   */
+  VOICEVOX_RESULT_MISSING_NETWORK_ERROR = 34,
+  /**
+   * バイナリ形式のデータが不正だった
+   */
+  VOICEVOX_RESULT_INVALID_BINARY_DATA_ERROR = 35,
+  /**
+   * 出力先への書き込みに失敗した
+   */
+  VOICEVOX_RESULT_WRITE_OUTPUT_ERROR = 36,
+  /**
+   * 音声合成に使えないアクセント句が含まれていた
+   */
+  VOICEVOX_RESULT_INCONSISTENT_ACCENT_PHRASE_ERROR = 37,
+  /**
+   * 出力のサンプリングレートに対応していなかった
+   */
+  VOICEVOX_RESULT_UNSUPPORTED_SAMPLING_RATE_ERROR = 38,
 };
 #ifndef __cplusplus
 typedef int32_t VoicevoxResultCode;
@@ -360,6 +437,11 @@ typedef struct VoicevoxUserDictWord {
   uint32_t priority;
 } VoicevoxUserDictWord;
 
+/**
+ * ::voicevox_set_log_callback で設定する、ログを受け取るコールバック。
+ */
+typedef void (*VoicevoxLogCallback)(VoicevoxLogLevel level, const char *message, void *user_data);
+
 #ifdef __cplusplus
 extern "C" {
 #endif // __cplusplus
@@ -644,6 +726,26 @@ __declspec(dllimport)
 #endif
 char *voicevox_synthesizer_create_metas_json(const struct VoicevoxSynthesizer *synthesizer);
 
+/**
+ * 今読み込んでいる音声モデルに含まれるスタイルの一覧を、JSONで取得する。
+ *
+ * JSONは`{"style_id": 0, "is_loaded": true}`のような、スタイルIDとそのスタイルが読み込まれているかどうかの組の配列である。`is_loaded`が`true`のスタイルIDは、音声合成に使うことができる。
+ *
+ * JSONの解放は ::voicevox_json_free で行う。
+ *
+ * @param [in] synthesizer 音声シンセサイザ
+ *
+ * @return スタイルの一覧のJSON文字列
+ *
+ * \safety{
+ * - `synthesizer`は ::voicevox_synthesizer_new_with_initialize で得たものでなければならず、また ::voicevox_synthesizer_delete で解放されていてはいけない。
+ * }
+ */
+#ifdef _WIN32
+__declspec(dllimport)
+#endif
+char *voicevox_synthesizer_get_loaded_styles_json(const struct VoicevoxSynthesizer *synthesizer);
+
 /**
  * このライブラリで利用可能なデバイスの情報を、JSONで取得する。
  *
@@ -904,6 +1006,83 @@ VoicevoxResultCode voicevox_synthesizer_synthesis(const struct VoicevoxSynthesiz
                                                   uintptr_t *output_wav_length,
                                                   uint8_t **output_wav);
 
+/**
+ * AudioQueryから音声合成を行い、呼び出し側が用意したバッファにWAVデータを書き込む。
+ *
+ * ::voicevox_synthesizer_synthesis と異なり、WAVデータの解放は不要である。
+ *
+ * `output_written`には、成功した場合は書き込んだバイト長が、`capacity`が足りなかった場合は必要なバイト長が書き込まれる。
+ * 後者の場合、`buffer`には何も書き込まれず ::VOICEVOX_RESULT_BUFFER_TOO_SMALL_ERROR が返る。
+ *
+ * 必要なバイト長は推論を行う前にAudioQueryから求めるため、`capacity`に0を渡せば音声合成を行わずに必要なバイト長だけを得られる。
+ *
+ * @param [in] synthesizer 音声シンセサイザ
+ * @param [in] audio_query_json AudioQueryのJSON文字列
+ * @param [in] style_id スタイルID
+ * @param [in] options オプション
+ * @param [out] buffer 出力先のバッファ
+ * @param [in] capacity `buffer`のバイト長
+ * @param [out] output_written 書き込んだ、または必要なバイト長
+ *
+ * @returns 結果コード
+ *
+ * \safety{
+ * - `synthesizer`は ::voicevox_synthesizer_new_with_initialize で得たものでなければならず、また ::voicevox_synthesizer_delete で解放されていてはいけない。
+ * - `audio_query_json`はヌル終端文字列を指し、かつ<a href="#voicevox-core-safety">読み込みについて有効</a>でなければならない。
+ * - `capacity`が0でなければ、`buffer`は`capacity`バイトの範囲で<a href="#voicevox-core-safety">書き込みについて有効</a>でなければならない。
+ * - `output_written`は<a href="#voicevox-core-safety">書き込みについて有効</a>でなければならない。
+ * }
+ */
+#ifdef _WIN32
+__declspec(dllimport)
+#endif
+VoicevoxResultCode voicevox_synthesizer_synthesis_into(const struct VoicevoxSynthesizer *synthesizer,
+                                                       const char *audio_query_json,
+                                                       VoicevoxStyleId style_id,
+                                                       struct VoicevoxSynthesisOptions options,
+                                                       uint8_t *buffer,
+                                                       uintptr_t capacity,
+                                                       uintptr_t *output_written);
+
+/**
+ * AudioQueryから音声合成を行い、音声を少しずつコールバックに渡す。
+ *
+ * `chunk_callback`には、ヘッダの無い16bitのリニアPCMのチャンクが順に渡される。先頭のアクセント句の音声が先に渡されるため、
+ * 文全体の合成を待たずに再生を始めることができる。その代わりに全体の処理時間は長くなる。
+ *
+ * 全てのチャンクを連結したものの長さは ::voicevox_synthesizer_synthesis が返すWAVのdataチャンクと一致し、先頭のアクセント句より
+ * 後ろの部分はその内容とも一致する。先頭のアクセント句は後続の文脈を一部欠いて合成されるため、波形がわずかに異なりうる。
+ *
+ * チャンクは`chunk_callback`から戻った後に解放されるため、必要であればコールバック内で複製すること。
+ * 途中で合成に失敗した場合、それ以降`chunk_callback`は呼ばれず、そのエラーの結果コードが返る。
+ *
+ * @param [in] synthesizer 音声シンセサイザ
+ * @param [in] audio_query_json AudioQueryのJSON文字列
+ * @param [in] style_id スタイルID
+ * @param [in] options オプション
+ * @param [in] chunk_callback チャンクを受け取るコールバック。引数はチャンクの先頭、チャンクのバイト長、`user_data`
+ * @param [in] user_data `chunk_callback`にそのまま渡される値
+ *
+ * @returns 結果コード
+ *
+ * \safety{
+ * - `synthesizer`は ::voicevox_synthesizer_new_with_initialize で得たものでなければならず、また ::voicevox_synthesizer_delete で解放されていてはいけない。
+ * - `audio_query_json`はヌル終端文字列を指し、かつ<a href="#voicevox-core-safety">読み込みについて有効</a>でなければならない。
+ * - `chunk_callback`は、与えられた`user_data`に対して安全に呼び出せる関数でなければならない。
+ * }
+ */
+#ifdef _WIN32
+__declspec(dllimport)
+#endif
+VoicevoxResultCode voicevox_synthesizer_synthesis_stream(const struct VoicevoxSynthesizer *synthesizer,
+                                                         const char *audio_query_json,
+                                                         VoicevoxStyleId style_id,
+                                                         struct VoicevoxSynthesisOptions options,
+                                                         void (*chunk_callback)(const uint8_t *chunk,
+                                                                                uintptr_t chunk_length,
+                                                                                void *user_data),
+                                                         void *user_data);
+
 /**
  * デフォルトのテキスト音声合成オプションを生成する
  * @return テキスト音声合成オプション
@@ -944,6 +1123,114 @@ VoicevoxResultCode voicevox_synthesizer_tts(const struct VoicevoxSynthesizer *sy
                                             uintptr_t *output_wav_length,
                                             uint8_t **output_wav);
 
+/**
+ * 音素ごとの長さを推論する。
+ *
+ * 生成した配列を解放するには ::voicevox_float_array_free を使う。
+ *
+ * @param [in] synthesizer 音声シンセサイザ
+ * @param [in] length 音素列の長さ
+ * @param [in] phoneme_vector 音素ID列
+ * @param [in] style_id スタイルID
+ * @param [out] output_predict_duration_length 出力の要素数
+ * @param [out] output_predict_duration 出力先。音素ごとの長さ(秒)の配列
+ *
+ * @returns 結果コード
+ *
+ * \safety{
+ * - `synthesizer`は ::voicevox_synthesizer_new_with_initialize で得たものでなければならず、また ::voicevox_synthesizer_delete で解放されていてはいけない。
+ * - `length`が0でなければ、`phoneme_vector`は`length`要素の範囲で<a href="#voicevox-core-safety">読み込みについて有効</a>でなければならない。
+ * - `output_predict_duration_length`は<a href="#voicevox-core-safety">書き込みについて有効</a>でなければならない。
+ * - `output_predict_duration`は<a href="#voicevox-core-safety">書き込みについて有効</a>でなければならない。
+ * }
+ */
+#ifdef _WIN32
+__declspec(dllimport)
+#endif
+VoicevoxResultCode voicevox_synthesizer_predict_duration(const struct VoicevoxSynthesizer *synthesizer,
+                                                         uintptr_t length,
+                                                         const int64_t *phoneme_vector,
+                                                         VoicevoxStyleId style_id,
+                                                         uintptr_t *output_predict_duration_length,
+                                                         float **output_predict_duration);
+
+/**
+ * モーラごとの音高を推論する。
+ *
+ * 生成した配列を解放するには ::voicevox_float_array_free を使う。
+ *
+ * @param [in] synthesizer 音声シンセサイザ
+ * @param [in] length モーラ列の長さ
+ * @param [in] vowel_phoneme_vector 母音の音素ID列
+ * @param [in] consonant_phoneme_vector 子音の音素ID列。子音が無いモーラは-1
+ * @param [in] start_accent_vector アクセントの開始位置であるかどうか(0か1)の列
+ * @param [in] end_accent_vector アクセントの終了位置であるかどうか(0か1)の列
+ * @param [in] start_accent_phrase_vector アクセント句の開始位置であるかどうか(0か1)の列
+ * @param [in] end_accent_phrase_vector アクセント句の終了位置であるかどうか(0か1)の列
+ * @param [in] style_id スタイルID
+ * @param [out] output_predict_intonation_length 出力の要素数
+ * @param [out] output_predict_intonation 出力先。モーラごとの音高の配列
+ *
+ * @returns 結果コード
+ *
+ * \safety{
+ * - `synthesizer`は ::voicevox_synthesizer_new_with_initialize で得たものでなければならず、また ::voicevox_synthesizer_delete で解放されていてはいけない。
+ * - `vowel_phoneme_vector`、`consonant_phoneme_vector`、`start_accent_vector`、`end_accent_vector`、`start_accent_phrase_vector`、`end_accent_phrase_vector`は、`length`が0でなければそれぞれ`length`要素の範囲で<a href="#voicevox-core-safety">読み込みについて有効</a>でなければならない。
+ * - `output_predict_intonation_length`は<a href="#voicevox-core-safety">書き込みについて有効</a>でなければならない。
+ * - `output_predict_intonation`は<a href="#voicevox-core-safety">書き込みについて有効</a>でなければならない。
+ * }
+ */
+#ifdef _WIN32
+__declspec(dllimport)
+#endif
+VoicevoxResultCode voicevox_synthesizer_predict_intonation(const struct VoicevoxSynthesizer *synthesizer,
+                                                           uintptr_t length,
+                                                           const int64_t *vowel_phoneme_vector,
+                                                           const int64_t *consonant_phoneme_vector,
+                                                           const int64_t *start_accent_vector,
+                                                           const int64_t *end_accent_vector,
+                                                           const int64_t *start_accent_phrase_vector,
+                                                           const int64_t *end_accent_phrase_vector,
+                                                           VoicevoxStyleId style_id,
+                                                           uintptr_t *output_predict_intonation_length,
+                                                           float **output_predict_intonation);
+
+/**
+ * 音高と音素の列から波形を推論する。
+ *
+ * 生成した配列を解放するには ::voicevox_float_array_free を使う。
+ *
+ * @param [in] synthesizer 音声シンセサイザ
+ * @param [in] length フレーム数
+ * @param [in] phoneme_size 音素の種類数
+ * @param [in] f0 フレームごとの音高
+ * @param [in] phoneme_vector フレームごとの音素のone-hotベクトルを並べたもの
+ * @param [in] style_id スタイルID
+ * @param [out] output_decode_length 出力の要素数
+ * @param [out] output_decode 出力先。波形のサンプルの配列
+ *
+ * @returns 結果コード
+ *
+ * \safety{
+ * - `synthesizer`は ::voicevox_synthesizer_new_with_initialize で得たものでなければならず、また ::voicevox_synthesizer_delete で解放されていてはいけない。
+ * - `length`が0でなければ、`f0`は`length`要素の範囲で<a href="#voicevox-core-safety">読み込みについて有効</a>でなければならない。
+ * - `length * phoneme_size`が0でなければ、`phoneme_vector`は`length * phoneme_size`要素の範囲で<a href="#voicevox-core-safety">読み込みについて有効</a>でなければならない。
+ * - `output_decode_length`は<a href="#voicevox-core-safety">書き込みについて有効</a>でなければならない。
+ * - `output_decode`は<a href="#voicevox-core-safety">書き込みについて有効</a>でなければならない。
+ * }
+ */
+#ifdef _WIN32
+__declspec(dllimport)
+#endif
+VoicevoxResultCode voicevox_synthesizer_decode(const struct VoicevoxSynthesizer *synthesizer,
+                                               uintptr_t length,
+                                               uintptr_t phoneme_size,
+                                               const float *f0,
+                                               const float *phoneme_vector,
+                                               VoicevoxStyleId style_id,
+                                               uintptr_t *output_decode_length,
+                                               float **output_decode);
+
 /**
  * JSON文字列を解放する。
  *
@@ -953,6 +1240,7 @@ VoicevoxResultCode voicevox_synthesizer_tts(const struct VoicevoxSynthesizer *sy
  * - `json`は以下のAPIで得られたポインタでなくてはいけない。
  *     - ::voicevox_create_supported_devices_json
  *     - ::voicevox_synthesizer_create_metas_json
+ *     - ::voicevox_synthesizer_get_loaded_styles_json
  *     - ::voicevox_synthesizer_create_audio_query
  *     - ::voicevox_synthesizer_create_accent_phrases
  *     - ::voicevox_synthesizer_replace_mora_data
@@ -987,6 +1275,25 @@ __declspec(dllimport)
 #endif
 void voicevox_wav_free(uint8_t *wav);
 
+/**
+ * 推論結果の配列を解放する。
+ *
+ * @param [in] array 解放する配列
+ *
+ * \safety{
+ * - `array`は以下のAPIで得られたポインタでなくてはいけない。
+ *     - ::voicevox_synthesizer_predict_duration
+ *     - ::voicevox_synthesizer_predict_intonation
+ *     - ::voicevox_synthesizer_decode
+ * - `array`は<a href="#voicevox-core-safety">読み込みと書き込みについて有効</a>でなければならない。
+ * - `array`は以後<b>ダングリングポインタ</b>(_dangling pointer_)として扱われなくてはならない。
+ * }
+ */
+#ifdef _WIN32
+__declspec(dllimport)
+#endif
+void voicevox_float_array_free(float *array);
+
 /**
  * 結果コードに対応したメッセージ文字列を取得する。
  *
@@ -1014,6 +1321,52 @@ __declspec(dllimport)
 #endif
 const char *voicevox_error_result_to_message(VoicevoxResultCode result_code);
 
+/**
+ * このスレッドで最後に失敗した呼び出しの、エラーの詳細を表すメッセージ文字列を取得する。
+ *
+ * ::voicevox_error_result_to_message が結果コードごとに固定のメッセージを返すのに対し、こちらは無効だったスタイルIDなど、
+ * エラーの原因を含んだメッセージを返す。エラーが一度も起きていない場合は空文字列を返す。
+ *
+ * 成功した呼び出しではメッセージは更新されない。
+ *
+ * @returns エラーの詳細を表すメッセージ文字列
+ *
+ * \safety{
+ * - 戻り値は、同じスレッドで次にエラーが起きるまでの間のみ有効である。また解放してはいけない。
+ * }
+ */
+#ifdef _WIN32
+__declspec(dllimport)
+#endif
+const char *voicevox_get_last_error_message(void);
+
+/**
+ * ログの出力先となるコールバックを設定する。
+ *
+ * 設定されている間、このライブラリのログとエラーの詳細は標準エラー出力ではなく`log_callback`に渡される。`log_callback`に
+ * ヌルポインタを渡すと設定が解除され、標準エラー出力に戻る。
+ *
+ * `log_callback`には`level`以上の重要度のログのみが、ログの重要度、ヌル終端のメッセージ文字列、`user_data`の順で渡される。
+ * メッセージ文字列は`log_callback`から戻った後に解放される。
+ *
+ * `log_callback`は音声合成などの途中に、任意のスレッドから呼ばれうる。
+ *
+ * @param [in] log_callback ログを受け取るコールバック
+ * @param [in] user_data `log_callback`にそのまま渡される値
+ * @param [in] level 受け取るログの重要度の下限
+ *
+ * \safety{
+ * - `log_callback`は、与えられた`user_data`に対して、任意のスレッドから同時に呼び出しても安全な関数でなければならない。
+ * - `log_callback`の中から ::voicevox_set_log_callback を呼んではならない。
+ * }
+ */
+#ifdef _WIN32
+__declspec(dllimport)
+#endif
+void voicevox_set_log_callback(VoicevoxLogCallback log_callback,
+                               void *user_data,
+                               VoicevoxLogLevel level);
+
 /**
  * ::VoicevoxUserDictWord を最低限のパラメータで作成する。
  *
@@ -1,6 +1,9 @@
 use std::{ffi::CString, path::Path, sync::Arc};
 
-use voicevox_core::{InitializeOptions, OpenJtalk, Result, Synthesizer, VoiceModel, VoiceModelId};
+use voicevox_core::{
+    InitializeOptions, OpenJtalk, Result, SpeakerMeta, StyleMeta, Synthesizer, VoiceModel,
+    VoiceModelId,
+};
 
 use crate::{CApiResult, OpenJtalkRc, VoicevoxSynthesizer, VoicevoxVoiceModel};
 
@@ -36,6 +39,23 @@ impl VoicevoxSynthesizer {
         let metas = &self.synthesizer.metas();
         CString::new(serde_json::to_string(metas).unwrap()).unwrap()
     }
+
+    pub(crate) fn loaded_styles(&self) -> CString {
+        let styles = self
+            .synthesizer
+            .metas()
+            .iter()
+            .flat_map(SpeakerMeta::styles)
+            .map(StyleMeta::id)
+            .map(|&style_id| {
+                serde_json::json!({
+                    "style_id": style_id,
+                    "is_loaded": self.synthesizer.is_loaded_model_by_style_id(style_id),
+                })
+            })
+            .collect::<Vec<_>>();
+        CString::new(serde_json::to_string(&styles).unwrap()).unwrap()
+    }
 }
 
 impl VoicevoxVoiceModel {
@@ -78,6 +78,7 @@ pub extern "C" fn initialize(use_gpu: bool, cpu_num_threads: c_int, load_all_mod
             },
             cpu_num_threads: cpu_num_threads as u16,
             load_all_models,
+            ..Default::default()
         },
     ));
     match result {
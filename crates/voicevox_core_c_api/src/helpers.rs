@@ -1,3 +1,4 @@
+use std::cell::RefCell;
 use std::fmt::Debug;
 use voicevox_core::UserDictWord;
 
@@ -6,55 +7,62 @@ use thiserror::Error;
 use super::*;
 use voicevox_core::AccentPhraseModel;
 
+thread_local! {
+    /// このスレッドで最後に失敗した呼び出しのエラーの詳細。
+    static LAST_ERROR_MESSAGE: RefCell<CString> = RefCell::default();
+}
+
 pub(crate) fn into_result_code_with_error(result: CApiResult<()>) -> VoicevoxResultCode {
     if let Err(err) = &result {
         display_error(err);
+        set_last_error_message(err);
     }
     return into_result_code(result);
 
     fn display_error(err: &CApiError) {
+        // バッファの不足は、必要な長さを得るために意図して起こされうるため、エラーとしては扱わない
+        if let CApiError::BufferTooSmall { .. } = err {
+            log_callback::send(tracing::Level::DEBUG, &format!("{err}"));
+            return;
+        }
+        if log_callback::send(tracing::Level::ERROR, &format!("{err}")) {
+            return;
+        }
         eprintln!("Error(Display): {err}");
         eprintln!("Error(Debug): {err:#?}");
     }
 
+    fn set_last_error_message(err: &CApiError) {
+        let message =
+            CString::new(err.to_string().replace('\0', "\\0")).expect("should not contain NUL");
+        LAST_ERROR_MESSAGE.with(|last| *last.borrow_mut() = message);
+    }
+
     fn into_result_code(result: CApiResult<()>) -> VoicevoxResultCode {
-        use voicevox_core::{Error::*, LoadModelErrorKind::*};
         use CApiError::*;
         use VoicevoxResultCode::*;
 
         match result {
             Ok(()) => VOICEVOX_RESULT_OK,
-            Err(RustApi(NotLoadedOpenjtalkDict)) => VOICEVOX_RESULT_NOT_LOADED_OPENJTALK_DICT_ERROR,
-            Err(RustApi(GpuSupport)) => VOICEVOX_RESULT_GPU_SUPPORT_ERROR,
-            Err(RustApi(LoadModel(err))) => match err.context() {
-                OpenZipFile => VOICEVOX_RESULT_OPEN_ZIP_FILE_ERROR,
-                ReadZipEntry { .. } => VOICEVOX_RESULT_READ_ZIP_ENTRY_ERROR,
-                ModelAlreadyLoaded { .. } => VOICEVOX_RESULT_MODEL_ALREADY_LOADED_ERROR,
-                StyleAlreadyLoaded { .. } => VOICEVOX_RESULT_STYLE_ALREADY_LOADED_ERROR,
-                InvalidModelData => VOICEVOX_RESULT_INVALID_MODEL_DATA_ERROR,
-            },
-            Err(RustApi(GetSupportedDevices(_))) => VOICEVOX_RESULT_GET_SUPPORTED_DEVICES_ERROR,
-            Err(RustApi(InvalidStyleId { .. })) => VOICEVOX_RESULT_INVALID_STYLE_ID_ERROR,
-            Err(RustApi(InvalidModelId { .. })) => VOICEVOX_RESULT_INVALID_MODEL_ID_ERROR,
-            Err(RustApi(InferenceFailed)) => VOICEVOX_RESULT_INFERENCE_ERROR,
-            Err(RustApi(ExtractFullContextLabel(_))) => {
-                VOICEVOX_RESULT_EXTRACT_FULL_CONTEXT_LABEL_ERROR
+            // `Error::code`の値が全て`VoicevoxResultCode`にあることはテストで確かめている。万一無い
+            // 場合も、FFI境界でパニックさせないために推論の失敗として扱う
+            Err(RustApi(err)) => {
+                VoicevoxResultCode::from_code(err.code()).unwrap_or(VOICEVOX_RESULT_INFERENCE_ERROR)
             }
-            Err(RustApi(UnloadedModel { .. })) => VOICEVOX_RESULT_UNLOADED_MODEL_ERROR,
-            Err(RustApi(ParseKana(_))) => VOICEVOX_RESULT_PARSE_KANA_ERROR,
-            Err(RustApi(LoadUserDict(_))) => VOICEVOX_RESULT_LOAD_USER_DICT_ERROR,
-            Err(RustApi(SaveUserDict(_))) => VOICEVOX_RESULT_SAVE_USER_DICT_ERROR,
-            Err(RustApi(UnknownWord(_))) => VOICEVOX_RESULT_UNKNOWN_USER_DICT_WORD_ERROR,
-            Err(RustApi(UseUserDict(_))) => VOICEVOX_RESULT_USE_USER_DICT_ERROR,
-            Err(RustApi(InvalidWord(_))) => VOICEVOX_RESULT_INVALID_USER_DICT_WORD_ERROR,
             Err(InvalidUtf8Input) => VOICEVOX_RESULT_INVALID_UTF8_INPUT_ERROR,
             Err(InvalidAudioQuery(_)) => VOICEVOX_RESULT_INVALID_AUDIO_QUERY_ERROR,
             Err(InvalidAccentPhrase(_)) => VOICEVOX_RESULT_INVALID_ACCENT_PHRASE_ERROR,
             Err(InvalidUuid(_)) => VOICEVOX_RESULT_INVALID_UUID_ERROR,
+            Err(BufferTooSmall { .. }) => VOICEVOX_RESULT_BUFFER_TOO_SMALL_ERROR,
         }
     }
 }
 
+/// このスレッドで最後に失敗した呼び出しのエラーの詳細を、[`CStr`]として`f`に渡す。
+pub(crate) fn with_last_error_message<R>(f: impl FnOnce(&CStr) -> R) -> R {
+    LAST_ERROR_MESSAGE.with(|last| f(&last.borrow()))
+}
+
 pub(crate) type CApiResult<T> = std::result::Result<T, CApiError>;
 
 #[derive(Error, Debug)]
@@ -69,6 +77,8 @@ pub enum CApiError {
     InvalidAccentPhrase(serde_json::Error),
     #[error("無効なUUIDです: {0}")]
     InvalidUuid(uuid::Error),
+    #[error("出力先のバッファが小さすぎます({required}バイト必要ですが、{capacity}バイトしかありません)")]
+    BufferTooSmall { required: usize, capacity: usize },
 }
 
 pub(crate) fn audio_query_model_to_json(audio_query_model: &AudioQueryModel) -> String {
@@ -83,6 +93,21 @@ pub(crate) fn ensure_utf8(s: &CStr) -> CApiResult<&str> {
     s.to_str().map_err(|_| CApiError::InvalidUtf8Input)
 }
 
+/// `ptr`から`len`要素のスライスを作る。
+///
+/// [`std::slice::from_raw_parts`]とは異なり、`len`が0の場合は`ptr`を参照せずに空のスライスを返す。
+/// そのため`len`が0であれば、`ptr`はヌルであってもよい。
+///
+/// # Safety
+///
+/// `len`が0でなければ、`ptr`は`len`要素の範囲で読み込みについて有効でなければならない。
+pub(crate) unsafe fn slice_from_raw_parts<'a, T>(ptr: *const T, len: usize) -> &'a [T] {
+    if len == 0 {
+        return &[];
+    }
+    std::slice::from_raw_parts(ptr, len)
+}
+
 impl From<voicevox_core::AudioQueryOptions> for VoicevoxAudioQueryOptions {
     fn from(options: voicevox_core::AudioQueryOptions) -> Self {
         Self { kana: options.kana }
@@ -90,7 +115,10 @@ impl From<voicevox_core::AudioQueryOptions> for VoicevoxAudioQueryOptions {
 }
 impl From<VoicevoxAudioQueryOptions> for voicevox_core::AudioQueryOptions {
     fn from(options: VoicevoxAudioQueryOptions) -> Self {
-        Self { kana: options.kana }
+        Self {
+            kana: options.kana,
+            ..Default::default()
+        }
     }
 }
 
@@ -101,7 +129,10 @@ impl From<voicevox_core::AccentPhrasesOptions> for VoicevoxAccentPhrasesOptions
 }
 impl From<VoicevoxAccentPhrasesOptions> for voicevox_core::AccentPhrasesOptions {
     fn from(options: VoicevoxAccentPhrasesOptions) -> Self {
-        Self { kana: options.kana }
+        Self {
+            kana: options.kana,
+            ..Default::default()
+        }
     }
 }
 
@@ -109,6 +140,14 @@ impl From<VoicevoxSynthesisOptions> for voicevox_core::SynthesisOptions {
     fn from(options: VoicevoxSynthesisOptions) -> Self {
         Self {
             enable_interrogative_upspeak: options.enable_interrogative_upspeak,
+            capture_tensors: None,
+            quality: Default::default(),
+            output_gain_db: 0.,
+            emit_cue_points: false,
+            voice_mode: Default::default(),
+            seed: None,
+            eq: vec![],
+            prioritize_first_packet: false,
         }
     }
 }
@@ -135,6 +174,18 @@ impl From<VoicevoxAccelerationMode> for voicevox_core::AccelerationMode {
     }
 }
 
+impl From<tracing::Level> for VoicevoxLogLevel {
+    fn from(level: tracing::Level) -> Self {
+        match level {
+            tracing::Level::ERROR => Self::VOICEVOX_LOG_LEVEL_ERROR,
+            tracing::Level::WARN => Self::VOICEVOX_LOG_LEVEL_WARN,
+            tracing::Level::INFO => Self::VOICEVOX_LOG_LEVEL_INFO,
+            tracing::Level::DEBUG => Self::VOICEVOX_LOG_LEVEL_DEBUG,
+            _ => Self::VOICEVOX_LOG_LEVEL_TRACE,
+        }
+    }
+}
+
 impl Default for VoicevoxInitializeOptions {
     fn default() -> Self {
         let options = voicevox_core::InitializeOptions::default();
@@ -152,6 +203,7 @@ impl From<VoicevoxInitializeOptions> for voicevox_core::InitializeOptions {
             acceleration_mode: value.acceleration_mode.into(),
             cpu_num_threads: value.cpu_num_threads,
             load_all_models: value.load_all_models,
+            ..Default::default()
         }
     }
 }
@@ -170,6 +222,7 @@ impl From<VoicevoxTtsOptions> for voicevox_core::TtsOptions {
         Self {
             kana: options.kana,
             enable_interrogative_upspeak: options.enable_interrogative_upspeak,
+            ..Default::default()
         }
     }
 }
@@ -7,25 +7,31 @@ mod c_impls;
 mod compatible_engine;
 mod drop_check;
 mod helpers;
+mod log_callback;
 mod result_code;
 mod slice_owner;
 use self::drop_check::C_STRING_DROP_CHECKER;
 use self::helpers::*;
 use self::result_code::VoicevoxResultCode;
-use self::slice_owner::U8_SLICE_OWNER;
+use self::slice_owner::{F32_SLICE_OWNER, U8_SLICE_OWNER};
 use chrono::SecondsFormat;
 use derive_getters::Getters;
+use futures::TryStreamExt as _;
 use once_cell::sync::Lazy;
 use std::env;
-use std::ffi::{CStr, CString};
+use std::ffi::{c_void, CStr, CString};
 use std::fmt;
 use std::io::{self, IsTerminal, Write};
 use std::os::raw::c_char;
 use std::ptr::NonNull;
 use std::sync::{Arc, Mutex, MutexGuard};
 use tokio::runtime::Runtime;
+use tracing_subscriber::filter::filter_fn;
 use tracing_subscriber::fmt::format::Writer;
-use tracing_subscriber::EnvFilter;
+use tracing_subscriber::fmt::writer::MakeWriterExt as _;
+use tracing_subscriber::layer::SubscriberExt as _;
+use tracing_subscriber::util::SubscriberInitExt as _;
+use tracing_subscriber::{EnvFilter, Layer as _};
 use uuid::Uuid;
 use voicevox_core::{
     AccentPhraseModel, AudioQueryModel, AudioQueryOptions, OpenJtalk, TtsOptions, UserDictWord,
@@ -38,19 +44,33 @@ use rstest::*;
 
 static RUNTIME: Lazy<Runtime> = Lazy::new(|| {
     let _ = init_logger();
+    Runtime::new().unwrap()
+});
 
-    fn init_logger() -> std::result::Result<(), impl Sized> {
-        tracing_subscriber::fmt()
-            .with_env_filter(if env::var_os(EnvFilter::DEFAULT_ENV).is_some() {
-                EnvFilter::from_default_env()
-            } else {
-                "error,voicevox_core=info,voicevox_core_c_api=info,onnxruntime=info".into()
-            })
-            .with_timer(local_time as fn(&mut Writer<'_>) -> _)
-            .with_ansi(out().is_terminal() && env_allows_ansi())
-            .with_writer(out)
-            .try_init()
-    }
+/// ロガーを初期化する。二度目以降の呼び出しは何もせずに`Err`を返す。
+fn init_logger() -> std::result::Result<(), impl Sized> {
+    return tracing_subscriber::registry()
+        .with(
+            tracing_subscriber::fmt::layer()
+                .with_timer(local_time as fn(&mut Writer<'_>) -> _)
+                .with_ansi(out().is_terminal() && env_allows_ansi())
+                // コールバックが設定されている間は、そちらにのみ出力する
+                .with_writer(out.with_filter(|_| !log_callback::is_set()))
+                .with_filter(if env::var_os(EnvFilter::DEFAULT_ENV).is_some() {
+                    EnvFilter::from_default_env()
+                } else {
+                    "error,voicevox_core=info,voicevox_core_c_api=info,onnxruntime=info".into()
+                }),
+        )
+        .with(
+            tracing_subscriber::fmt::layer()
+                .without_time()
+                .with_level(false)
+                .with_ansi(false)
+                .with_writer(log_callback::MakeCallbackWriter)
+                .with_filter(filter_fn(|meta| log_callback::enabled(meta.level()))),
+        )
+        .try_init();
 
     fn local_time(wtr: &mut Writer<'_>) -> fmt::Result {
         // ローカル時刻で表示はするが、そのフォーマットはtracing-subscriber本来のものに近いようにする。
@@ -72,8 +92,7 @@ static RUNTIME: Lazy<Runtime> = Lazy::new(|| {
             |term| term != "dumb",
         ) && env::var_os("NO_COLOR").is_none()
     }
-    Runtime::new().unwrap()
-});
+}
 
 /*
  * Cの関数として公開するための型や関数を定義するこれらの実装はvoicevox_core/publish.rsに定義してある対応する関数にある
@@ -192,6 +211,23 @@ pub enum VoicevoxAccelerationMode {
     VOICEVOX_ACCELERATION_MODE_GPU = 2,
 }
 
+/// ログの重要度。
+#[repr(i32)]
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
+#[allow(non_camel_case_types)]
+pub enum VoicevoxLogLevel {
+    /// エラー
+    VOICEVOX_LOG_LEVEL_ERROR = 1,
+    /// 警告
+    VOICEVOX_LOG_LEVEL_WARN = 2,
+    /// 情報
+    VOICEVOX_LOG_LEVEL_INFO = 3,
+    /// デバッグ情報
+    VOICEVOX_LOG_LEVEL_DEBUG = 4,
+    /// より詳細なデバッグ情報
+    VOICEVOX_LOG_LEVEL_TRACE = 5,
+}
+
 /// ::voicevox_synthesizer_new_with_initialize のオプション。
 #[repr(C)]
 pub struct VoicevoxInitializeOptions {
@@ -460,6 +496,27 @@ pub extern "C" fn voicevox_synthesizer_create_metas_json(
     C_STRING_DROP_CHECKER.whitelist(metas).into_raw()
 }
 
+/// 今読み込んでいる音声モデルに含まれるスタイルの一覧を、JSONで取得する。
+///
+/// JSONは`{"style_id": 0, "is_loaded": true}`のような、スタイルIDとそのスタイルが読み込まれているかどうかの組の配列である。`is_loaded`が`true`のスタイルIDは、音声合成に使うことができる。
+///
+/// JSONの解放は ::voicevox_json_free で行う。
+///
+/// @param [in] synthesizer 音声シンセサイザ
+///
+/// @return スタイルの一覧のJSON文字列
+///
+/// \safety{
+/// - `synthesizer`は ::voicevox_synthesizer_new_with_initialize で得たものでなければならず、また ::voicevox_synthesizer_delete で解放されていてはいけない。
+/// }
+#[no_mangle]
+pub extern "C" fn voicevox_synthesizer_get_loaded_styles_json(
+    synthesizer: &VoicevoxSynthesizer,
+) -> *mut c_char {
+    let styles = synthesizer.loaded_styles();
+    C_STRING_DROP_CHECKER.whitelist(styles).into_raw()
+}
+
 /// このライブラリで利用可能なデバイスの情報を、JSONで取得する。
 ///
 /// JSONの解放は ::voicevox_json_free で行う。
@@ -829,6 +886,128 @@ pub unsafe extern "C" fn voicevox_synthesizer_synthesis(
     })())
 }
 
+/// AudioQueryから音声合成を行い、呼び出し側が用意したバッファにWAVデータを書き込む。
+///
+/// ::voicevox_synthesizer_synthesis と異なり、WAVデータの解放は不要である。
+///
+/// `output_written`には、成功した場合は書き込んだバイト長が、`capacity`が足りなかった場合は必要なバイト長が書き込まれる。
+/// 後者の場合、`buffer`には何も書き込まれず ::VOICEVOX_RESULT_BUFFER_TOO_SMALL_ERROR が返る。
+///
+/// 必要なバイト長は推論を行う前にAudioQueryから求めるため、`capacity`に0を渡せば音声合成を行わずに必要なバイト長だけを得られる。
+///
+/// @param [in] synthesizer 音声シンセサイザ
+/// @param [in] audio_query_json AudioQueryのJSON文字列
+/// @param [in] style_id スタイルID
+/// @param [in] options オプション
+/// @param [out] buffer 出力先のバッファ
+/// @param [in] capacity `buffer`のバイト長
+/// @param [out] output_written 書き込んだ、または必要なバイト長
+///
+/// @returns 結果コード
+///
+/// \safety{
+/// - `synthesizer`は ::voicevox_synthesizer_new_with_initialize で得たものでなければならず、また ::voicevox_synthesizer_delete で解放されていてはいけない。
+/// - `audio_query_json`はヌル終端文字列を指し、かつ<a href="#voicevox-core-safety">読み込みについて有効</a>でなければならない。
+/// - `capacity`が0でなければ、`buffer`は`capacity`バイトの範囲で<a href="#voicevox-core-safety">書き込みについて有効</a>でなければならない。
+/// - `output_written`は<a href="#voicevox-core-safety">書き込みについて有効</a>でなければならない。
+/// }
+#[no_mangle]
+pub unsafe extern "C" fn voicevox_synthesizer_synthesis_into(
+    synthesizer: &VoicevoxSynthesizer,
+    audio_query_json: *const c_char,
+    style_id: VoicevoxStyleId,
+    options: VoicevoxSynthesisOptions,
+    buffer: *mut u8,
+    capacity: usize,
+    output_written: NonNull<usize>,
+) -> VoicevoxResultCode {
+    into_result_code_with_error((|| {
+        let audio_query_json = CStr::from_ptr(audio_query_json)
+            .to_str()
+            .map_err(|_| CApiError::InvalidUtf8Input)?;
+        let audio_query: AudioQueryModel =
+            serde_json::from_str(audio_query_json).map_err(CApiError::InvalidAudioQuery)?;
+        let options = &SynthesisOptions::from(options);
+        let required = synthesizer
+            .synthesizer()
+            .wav_len_from_query(&audio_query, options);
+        output_written.as_ptr().write_unaligned(required);
+        if required > capacity {
+            return Err(CApiError::BufferTooSmall { required, capacity });
+        }
+        let wav = RUNTIME.block_on(synthesizer.synthesizer().synthesis(
+            &audio_query,
+            StyleId::new(style_id),
+            options,
+        ))?;
+        debug_assert_eq!(required, wav.len());
+        std::ptr::copy_nonoverlapping(wav.as_ptr(), buffer, wav.len());
+        Ok(())
+    })())
+}
+
+/// AudioQueryから音声合成を行い、音声を少しずつコールバックに渡す。
+///
+/// `chunk_callback`には、ヘッダの無い16bitのリニアPCMのチャンクが順に渡される。先頭のアクセント句の音声が先に渡されるため、
+/// 文全体の合成を待たずに再生を始めることができる。その代わりに全体の処理時間は長くなる。
+///
+/// 全てのチャンクを連結したものの長さは ::voicevox_synthesizer_synthesis が返すWAVのdataチャンクと一致し、先頭のアクセント句より
+/// 後ろの部分はその内容とも一致する。先頭のアクセント句は後続の文脈を一部欠いて合成されるため、波形がわずかに異なりうる。
+///
+/// チャンクは`chunk_callback`から戻った後に解放されるため、必要であればコールバック内で複製すること。
+/// 途中で合成に失敗した場合、それ以降`chunk_callback`は呼ばれず、そのエラーの結果コードが返る。
+///
+/// @param [in] synthesizer 音声シンセサイザ
+/// @param [in] audio_query_json AudioQueryのJSON文字列
+/// @param [in] style_id スタイルID
+/// @param [in] options オプション
+/// @param [in] chunk_callback チャンクを受け取るコールバック。引数はチャンクの先頭、チャンクのバイト長、`user_data`
+/// @param [in] user_data `chunk_callback`にそのまま渡される値
+///
+/// @returns 結果コード
+///
+/// \safety{
+/// - `synthesizer`は ::voicevox_synthesizer_new_with_initialize で得たものでなければならず、また ::voicevox_synthesizer_delete で解放されていてはいけない。
+/// - `audio_query_json`はヌル終端文字列を指し、かつ<a href="#voicevox-core-safety">読み込みについて有効</a>でなければならない。
+/// - `chunk_callback`は、与えられた`user_data`に対して安全に呼び出せる関数でなければならない。
+/// }
+#[no_mangle]
+pub unsafe extern "C" fn voicevox_synthesizer_synthesis_stream(
+    synthesizer: &VoicevoxSynthesizer,
+    audio_query_json: *const c_char,
+    style_id: VoicevoxStyleId,
+    options: VoicevoxSynthesisOptions,
+    chunk_callback: unsafe extern "C" fn(
+        chunk: *const u8,
+        chunk_length: usize,
+        user_data: *mut c_void,
+    ),
+    user_data: *mut c_void,
+) -> VoicevoxResultCode {
+    into_result_code_with_error((|| {
+        let audio_query_json = CStr::from_ptr(audio_query_json)
+            .to_str()
+            .map_err(|_| CApiError::InvalidUtf8Input)?;
+        let audio_query: AudioQueryModel =
+            serde_json::from_str(audio_query_json).map_err(CApiError::InvalidAudioQuery)?;
+        RUNTIME.block_on(async {
+            let chunks = synthesizer.synthesizer().synthesis_stream(
+                &audio_query,
+                StyleId::new(style_id),
+                &SynthesisOptions {
+                    prioritize_first_packet: true,
+                    ..options.into()
+                },
+            );
+            futures::pin_mut!(chunks);
+            while let Some(chunk) = chunks.try_next().await? {
+                chunk_callback(chunk.as_ptr(), chunk.len(), user_data);
+            }
+            Ok::<_, CApiError>(())
+        })
+    })())
+}
+
 /// ::voicevox_synthesizer_tts のオプション。
 #[repr(C)]
 pub struct VoicevoxTtsOptions {
@@ -885,6 +1064,153 @@ pub unsafe extern "C" fn voicevox_synthesizer_tts(
     })())
 }
 
+/// 音素ごとの長さを推論する。
+///
+/// 生成した配列を解放するには ::voicevox_float_array_free を使う。
+///
+/// @param [in] synthesizer 音声シンセサイザ
+/// @param [in] length 音素列の長さ
+/// @param [in] phoneme_vector 音素ID列
+/// @param [in] style_id スタイルID
+/// @param [out] output_predict_duration_length 出力の要素数
+/// @param [out] output_predict_duration 出力先。音素ごとの長さ(秒)の配列
+///
+/// @returns 結果コード
+///
+/// \safety{
+/// - `synthesizer`は ::voicevox_synthesizer_new_with_initialize で得たものでなければならず、また ::voicevox_synthesizer_delete で解放されていてはいけない。
+/// - `length`が0でなければ、`phoneme_vector`は`length`要素の範囲で<a href="#voicevox-core-safety">読み込みについて有効</a>でなければならない。
+/// - `output_predict_duration_length`は<a href="#voicevox-core-safety">書き込みについて有効</a>でなければならない。
+/// - `output_predict_duration`は<a href="#voicevox-core-safety">書き込みについて有効</a>でなければならない。
+/// }
+#[no_mangle]
+pub unsafe extern "C" fn voicevox_synthesizer_predict_duration(
+    synthesizer: &VoicevoxSynthesizer,
+    length: usize,
+    phoneme_vector: *const i64,
+    style_id: VoicevoxStyleId,
+    output_predict_duration_length: NonNull<usize>,
+    output_predict_duration: NonNull<*mut f32>,
+) -> VoicevoxResultCode {
+    into_result_code_with_error((|| {
+        let output = RUNTIME.block_on(synthesizer.synthesizer().predict_duration(
+            slice_from_raw_parts(phoneme_vector, length),
+            StyleId::new(style_id),
+        ))?;
+        F32_SLICE_OWNER.own_and_lend(
+            output,
+            output_predict_duration,
+            output_predict_duration_length,
+        );
+        Ok(())
+    })())
+}
+
+/// モーラごとの音高を推論する。
+///
+/// 生成した配列を解放するには ::voicevox_float_array_free を使う。
+///
+/// @param [in] synthesizer 音声シンセサイザ
+/// @param [in] length モーラ列の長さ
+/// @param [in] vowel_phoneme_vector 母音の音素ID列
+/// @param [in] consonant_phoneme_vector 子音の音素ID列。子音が無いモーラは-1
+/// @param [in] start_accent_vector アクセントの開始位置であるかどうか(0か1)の列
+/// @param [in] end_accent_vector アクセントの終了位置であるかどうか(0か1)の列
+/// @param [in] start_accent_phrase_vector アクセント句の開始位置であるかどうか(0か1)の列
+/// @param [in] end_accent_phrase_vector アクセント句の終了位置であるかどうか(0か1)の列
+/// @param [in] style_id スタイルID
+/// @param [out] output_predict_intonation_length 出力の要素数
+/// @param [out] output_predict_intonation 出力先。モーラごとの音高の配列
+///
+/// @returns 結果コード
+///
+/// \safety{
+/// - `synthesizer`は ::voicevox_synthesizer_new_with_initialize で得たものでなければならず、また ::voicevox_synthesizer_delete で解放されていてはいけない。
+/// - `vowel_phoneme_vector`、`consonant_phoneme_vector`、`start_accent_vector`、`end_accent_vector`、`start_accent_phrase_vector`、`end_accent_phrase_vector`は、`length`が0でなければそれぞれ`length`要素の範囲で<a href="#voicevox-core-safety">読み込みについて有効</a>でなければならない。
+/// - `output_predict_intonation_length`は<a href="#voicevox-core-safety">書き込みについて有効</a>でなければならない。
+/// - `output_predict_intonation`は<a href="#voicevox-core-safety">書き込みについて有効</a>でなければならない。
+/// }
+#[no_mangle]
+#[allow(clippy::too_many_arguments)]
+pub unsafe extern "C" fn voicevox_synthesizer_predict_intonation(
+    synthesizer: &VoicevoxSynthesizer,
+    length: usize,
+    vowel_phoneme_vector: *const i64,
+    consonant_phoneme_vector: *const i64,
+    start_accent_vector: *const i64,
+    end_accent_vector: *const i64,
+    start_accent_phrase_vector: *const i64,
+    end_accent_phrase_vector: *const i64,
+    style_id: VoicevoxStyleId,
+    output_predict_intonation_length: NonNull<usize>,
+    output_predict_intonation: NonNull<*mut f32>,
+) -> VoicevoxResultCode {
+    into_result_code_with_error((|| {
+        let output = RUNTIME.block_on(synthesizer.synthesizer().predict_intonation(
+            length,
+            slice_from_raw_parts(vowel_phoneme_vector, length),
+            slice_from_raw_parts(consonant_phoneme_vector, length),
+            slice_from_raw_parts(start_accent_vector, length),
+            slice_from_raw_parts(end_accent_vector, length),
+            slice_from_raw_parts(start_accent_phrase_vector, length),
+            slice_from_raw_parts(end_accent_phrase_vector, length),
+            StyleId::new(style_id),
+        ))?;
+        F32_SLICE_OWNER.own_and_lend(
+            output,
+            output_predict_intonation,
+            output_predict_intonation_length,
+        );
+        Ok(())
+    })())
+}
+
+/// 音高と音素の列から波形を推論する。
+///
+/// 生成した配列を解放するには ::voicevox_float_array_free を使う。
+///
+/// @param [in] synthesizer 音声シンセサイザ
+/// @param [in] length フレーム数
+/// @param [in] phoneme_size 音素の種類数
+/// @param [in] f0 フレームごとの音高
+/// @param [in] phoneme_vector フレームごとの音素のone-hotベクトルを並べたもの
+/// @param [in] style_id スタイルID
+/// @param [out] output_decode_length 出力の要素数
+/// @param [out] output_decode 出力先。波形のサンプルの配列
+///
+/// @returns 結果コード
+///
+/// \safety{
+/// - `synthesizer`は ::voicevox_synthesizer_new_with_initialize で得たものでなければならず、また ::voicevox_synthesizer_delete で解放されていてはいけない。
+/// - `length`が0でなければ、`f0`は`length`要素の範囲で<a href="#voicevox-core-safety">読み込みについて有効</a>でなければならない。
+/// - `length * phoneme_size`が0でなければ、`phoneme_vector`は`length * phoneme_size`要素の範囲で<a href="#voicevox-core-safety">読み込みについて有効</a>でなければならない。
+/// - `output_decode_length`は<a href="#voicevox-core-safety">書き込みについて有効</a>でなければならない。
+/// - `output_decode`は<a href="#voicevox-core-safety">書き込みについて有効</a>でなければならない。
+/// }
+#[no_mangle]
+pub unsafe extern "C" fn voicevox_synthesizer_decode(
+    synthesizer: &VoicevoxSynthesizer,
+    length: usize,
+    phoneme_size: usize,
+    f0: *const f32,
+    phoneme_vector: *const f32,
+    style_id: VoicevoxStyleId,
+    output_decode_length: NonNull<usize>,
+    output_decode: NonNull<*mut f32>,
+) -> VoicevoxResultCode {
+    into_result_code_with_error((|| {
+        let output = RUNTIME.block_on(synthesizer.synthesizer().decode(
+            length,
+            phoneme_size,
+            slice_from_raw_parts(f0, length),
+            slice_from_raw_parts(phoneme_vector, length * phoneme_size),
+            StyleId::new(style_id),
+        ))?;
+        F32_SLICE_OWNER.own_and_lend(output, output_decode, output_decode_length);
+        Ok(())
+    })())
+}
+
 /// JSON文字列を解放する。
 ///
 /// @param [in] json 解放するJSON文字列
@@ -893,6 +1219,7 @@ pub unsafe extern "C" fn voicevox_synthesizer_tts(
 /// - `json`は以下のAPIで得られたポインタでなくてはいけない。
 ///     - ::voicevox_create_supported_devices_json
 ///     - ::voicevox_synthesizer_create_metas_json
+///     - ::voicevox_synthesizer_get_loaded_styles_json
 ///     - ::voicevox_synthesizer_create_audio_query
 ///     - ::voicevox_synthesizer_create_accent_phrases
 ///     - ::voicevox_synthesizer_replace_mora_data
@@ -924,6 +1251,23 @@ pub extern "C" fn voicevox_wav_free(wav: *mut u8) {
     U8_SLICE_OWNER.drop_for(wav);
 }
 
+/// 推論結果の配列を解放する。
+///
+/// @param [in] array 解放する配列
+///
+/// \safety{
+/// - `array`は以下のAPIで得られたポインタでなくてはいけない。
+///     - ::voicevox_synthesizer_predict_duration
+///     - ::voicevox_synthesizer_predict_intonation
+///     - ::voicevox_synthesizer_decode
+/// - `array`は<a href="#voicevox-core-safety">読み込みと書き込みについて有効</a>でなければならない。
+/// - `array`は以後<b>ダングリングポインタ</b>(_dangling pointer_)として扱われなくてはならない。
+/// }
+#[no_mangle]
+pub extern "C" fn voicevox_float_array_free(array: *mut f32) {
+    F32_SLICE_OWNER.drop_for(array);
+}
+
 /// 結果コードに対応したメッセージ文字列を取得する。
 ///
 /// @param [in] result_code 結果コード
@@ -952,6 +1296,51 @@ pub extern "C" fn voicevox_error_result_to_message(
     C_STRING_DROP_CHECKER.blacklist(message).as_ptr()
 }
 
+/// このスレッドで最後に失敗した呼び出しの、エラーの詳細を表すメッセージ文字列を取得する。
+///
+/// ::voicevox_error_result_to_message が結果コードごとに固定のメッセージを返すのに対し、こちらは無効だったスタイルIDなど、
+/// エラーの原因を含んだメッセージを返す。エラーが一度も起きていない場合は空文字列を返す。
+///
+/// 成功した呼び出しではメッセージは更新されない。
+///
+/// @returns エラーの詳細を表すメッセージ文字列
+///
+/// \safety{
+/// - 戻り値は、同じスレッドで次にエラーが起きるまでの間のみ有効である。また解放してはいけない。
+/// }
+#[no_mangle]
+pub extern "C" fn voicevox_get_last_error_message() -> *const c_char {
+    with_last_error_message(|message| message.as_ptr())
+}
+
+/// ログの出力先となるコールバックを設定する。
+///
+/// 設定されている間、このライブラリのログとエラーの詳細は標準エラー出力ではなく`log_callback`に渡される。`log_callback`に
+/// ヌルポインタを渡すと設定が解除され、標準エラー出力に戻る。
+///
+/// `log_callback`には`level`以上の重要度のログのみが、ログの重要度、ヌル終端のメッセージ文字列、`user_data`の順で渡される。
+/// メッセージ文字列は`log_callback`から戻った後に解放される。
+///
+/// `log_callback`は音声合成などの途中に、任意のスレッドから呼ばれうる。
+///
+/// @param [in] log_callback ログを受け取るコールバック
+/// @param [in] user_data `log_callback`にそのまま渡される値
+/// @param [in] level 受け取るログの重要度の下限
+///
+/// \safety{
+/// - `log_callback`は、与えられた`user_data`に対して、任意のスレッドから同時に呼び出しても安全な関数でなければならない。
+/// - `log_callback`の中から ::voicevox_set_log_callback を呼んではならない。
+/// }
+#[no_mangle]
+pub unsafe extern "C" fn voicevox_set_log_callback(
+    log_callback: Option<log_callback::VoicevoxLogCallback>,
+    user_data: *mut c_void,
+    level: VoicevoxLogLevel,
+) {
+    let _ = init_logger();
+    log_callback::set(log_callback, user_data, level);
+}
+
 /// ユーザー辞書。
 #[derive(Default)]
 pub struct VoicevoxUserDict {
@@ -1223,6 +1612,7 @@ mod tests {
     use super::*;
     use anyhow::anyhow;
     use pretty_assertions::assert_eq;
+    use voicevox_core::error_code;
     use voicevox_core::Error;
     use voicevox_core::Result;
 
@@ -1236,6 +1626,14 @@ mod tests {
         Err(Error::GetSupportedDevices(anyhow!("some get supported devices error"))),
         VoicevoxResultCode::VOICEVOX_RESULT_GET_SUPPORTED_DEVICES_ERROR
     )]
+    #[case(
+        Err(Error::InvalidMoraOffset { mora_offset: 0 }),
+        VoicevoxResultCode::VOICEVOX_RESULT_INVALID_ACCENT_PHRASE_POSITION_ERROR
+    )]
+    #[case(
+        Err(Error::TextTooLong { len: 2, max: 1 }),
+        VoicevoxResultCode::VOICEVOX_RESULT_TEXT_TOO_LONG_ERROR
+    )]
     fn into_result_code_with_error_works(
         #[case] result: Result<()>,
         #[case] expected: VoicevoxResultCode,
@@ -1243,4 +1641,185 @@ mod tests {
         let actual = into_result_code_with_error(result.map_err(Into::into));
         assert_eq!(expected, actual);
     }
+
+    #[rstest]
+    #[case(Error::NotLoadedOpenjtalkDict)]
+    #[case(Error::GpuSupport(voicevox_core::GpuUnsupportedReason::NotBuiltWithGpuSupport))]
+    #[case(Error::UnloadedModel {
+        model_id: VoiceModelId::new("model".to_owned()),
+    })]
+    #[case(Error::GetSupportedDevices(anyhow!("some get supported devices error")))]
+    #[case(Error::InvalidStyleId {
+        style_id: StyleId::new(0),
+    })]
+    #[case(Error::StyleNotFound {
+        speaker: "speaker".to_owned(),
+        style: "style".to_owned(),
+        available: vec![],
+    })]
+    #[case(Error::InvalidModelId {
+        model_id: VoiceModelId::new("model".to_owned()),
+    })]
+    #[case(Error::InferenceFailed)]
+    #[case(Error::LoadUserDict("some load user dict error".to_owned()))]
+    #[case(Error::SaveUserDict("some save user dict error".to_owned()))]
+    #[case(Error::UnknownWord(Uuid::nil()))]
+    #[case(Error::UseUserDict("some use user dict error".to_owned()))]
+    #[case(Error::InvalidWord(voicevox_core::InvalidWordError::InvalidPriority(11)))]
+    #[case(Error::InvalidAccentPhraseIndex { index: 0 })]
+    #[case(Error::InvalidMoraOffset { mora_offset: 0 })]
+    #[case(Error::InconsistentAccentPhrase {
+        index: 0,
+        reason: "some reason".to_owned(),
+    })]
+    #[case(Error::TextTooLong { len: 2, max: 1 })]
+    #[case(Error::InvalidAccentHint {
+        span: 0..1,
+        reason: "some reason".to_owned(),
+    })]
+    #[case(Error::MissingNetwork {
+        model_id: VoiceModelId::new("model".to_owned()),
+        network: voicevox_core::Network::Decode,
+    })]
+    #[case(Error::InvalidBinaryData {
+        reason: "some reason".to_owned(),
+    })]
+    #[case(Error::WriteOutput(std::io::ErrorKind::Other.into()))]
+    #[case(Error::UnsupportedSamplingRate { sampling_rate: 0 })]
+    fn into_result_code_with_error_agrees_with_error_code(#[case] err: Error) {
+        let expected = err.code();
+        let actual = into_result_code_with_error(Err(err.into()));
+        assert_eq!(expected, actual as u32);
+    }
+
+    #[rstest]
+    #[case(
+        error_code::NOT_LOADED_OPENJTALK_DICT,
+        VoicevoxResultCode::VOICEVOX_RESULT_NOT_LOADED_OPENJTALK_DICT_ERROR
+    )]
+    #[case(
+        error_code::GET_SUPPORTED_DEVICES,
+        VoicevoxResultCode::VOICEVOX_RESULT_GET_SUPPORTED_DEVICES_ERROR
+    )]
+    #[case(
+        error_code::GPU_SUPPORT,
+        VoicevoxResultCode::VOICEVOX_RESULT_GPU_SUPPORT_ERROR
+    )]
+    #[case(
+        error_code::INVALID_STYLE_ID,
+        VoicevoxResultCode::VOICEVOX_RESULT_INVALID_STYLE_ID_ERROR
+    )]
+    #[case(
+        error_code::INVALID_MODEL_ID,
+        VoicevoxResultCode::VOICEVOX_RESULT_INVALID_MODEL_ID_ERROR
+    )]
+    #[case(
+        error_code::INFERENCE,
+        VoicevoxResultCode::VOICEVOX_RESULT_INFERENCE_ERROR
+    )]
+    #[case(
+        error_code::EXTRACT_FULL_CONTEXT_LABEL,
+        VoicevoxResultCode::VOICEVOX_RESULT_EXTRACT_FULL_CONTEXT_LABEL_ERROR
+    )]
+    #[case(
+        error_code::PARSE_KANA,
+        VoicevoxResultCode::VOICEVOX_RESULT_PARSE_KANA_ERROR
+    )]
+    #[case(
+        error_code::OPEN_ZIP_FILE,
+        VoicevoxResultCode::VOICEVOX_RESULT_OPEN_ZIP_FILE_ERROR
+    )]
+    #[case(
+        error_code::READ_ZIP_ENTRY,
+        VoicevoxResultCode::VOICEVOX_RESULT_READ_ZIP_ENTRY_ERROR
+    )]
+    #[case(
+        error_code::MODEL_ALREADY_LOADED,
+        VoicevoxResultCode::VOICEVOX_RESULT_MODEL_ALREADY_LOADED_ERROR
+    )]
+    #[case(
+        error_code::UNLOADED_MODEL,
+        VoicevoxResultCode::VOICEVOX_RESULT_UNLOADED_MODEL_ERROR
+    )]
+    #[case(
+        error_code::LOAD_USER_DICT,
+        VoicevoxResultCode::VOICEVOX_RESULT_LOAD_USER_DICT_ERROR
+    )]
+    #[case(
+        error_code::SAVE_USER_DICT,
+        VoicevoxResultCode::VOICEVOX_RESULT_SAVE_USER_DICT_ERROR
+    )]
+    #[case(
+        error_code::UNKNOWN_USER_DICT_WORD,
+        VoicevoxResultCode::VOICEVOX_RESULT_UNKNOWN_USER_DICT_WORD_ERROR
+    )]
+    #[case(
+        error_code::USE_USER_DICT,
+        VoicevoxResultCode::VOICEVOX_RESULT_USE_USER_DICT_ERROR
+    )]
+    #[case(
+        error_code::INVALID_USER_DICT_WORD,
+        VoicevoxResultCode::VOICEVOX_RESULT_INVALID_USER_DICT_WORD_ERROR
+    )]
+    #[case(
+        error_code::STYLE_ALREADY_LOADED,
+        VoicevoxResultCode::VOICEVOX_RESULT_STYLE_ALREADY_LOADED_ERROR
+    )]
+    #[case(
+        error_code::INVALID_MODEL_DATA,
+        VoicevoxResultCode::VOICEVOX_RESULT_INVALID_MODEL_DATA_ERROR
+    )]
+    #[case(
+        error_code::LOAD_MODEL_TIMEOUT,
+        VoicevoxResultCode::VOICEVOX_RESULT_LOAD_MODEL_TIMEOUT_ERROR
+    )]
+    #[case(
+        error_code::INVALID_ACCENT_PHRASE_POSITION,
+        VoicevoxResultCode::VOICEVOX_RESULT_INVALID_ACCENT_PHRASE_POSITION_ERROR
+    )]
+    #[case(
+        error_code::TEXT_TOO_LONG,
+        VoicevoxResultCode::VOICEVOX_RESULT_TEXT_TOO_LONG_ERROR
+    )]
+    #[case(
+        error_code::INVALID_ACCENT_HINT,
+        VoicevoxResultCode::VOICEVOX_RESULT_INVALID_ACCENT_HINT_ERROR
+    )]
+    #[case(
+        error_code::UNSUPPORTED_MODEL_VERSION,
+        VoicevoxResultCode::VOICEVOX_RESULT_UNSUPPORTED_MODEL_VERSION_ERROR
+    )]
+    #[case(
+        error_code::MISSING_NETWORK,
+        VoicevoxResultCode::VOICEVOX_RESULT_MISSING_NETWORK_ERROR
+    )]
+    #[case(
+        error_code::INVALID_BINARY_DATA,
+        VoicevoxResultCode::VOICEVOX_RESULT_INVALID_BINARY_DATA_ERROR
+    )]
+    #[case(
+        error_code::WRITE_OUTPUT,
+        VoicevoxResultCode::VOICEVOX_RESULT_WRITE_OUTPUT_ERROR
+    )]
+    #[case(
+        error_code::INCONSISTENT_ACCENT_PHRASE,
+        VoicevoxResultCode::VOICEVOX_RESULT_INCONSISTENT_ACCENT_PHRASE_ERROR
+    )]
+    #[case(
+        error_code::UNSUPPORTED_SAMPLING_RATE,
+        VoicevoxResultCode::VOICEVOX_RESULT_UNSUPPORTED_SAMPLING_RATE_ERROR
+    )]
+    fn error_codes_have_result_codes(#[case] code: u32, #[case] expected: VoicevoxResultCode) {
+        assert_eq!(Some(expected), VoicevoxResultCode::from_code(code));
+    }
+
+    #[rstest]
+    fn slice_from_raw_parts_accepts_null_for_empty() {
+        let slice = unsafe { slice_from_raw_parts(std::ptr::null::<i64>(), 0) };
+        assert!(slice.is_empty());
+
+        let vec = vec![1_i64, 2, 3];
+        let slice = unsafe { slice_from_raw_parts(vec.as_ptr(), vec.len()) };
+        assert_eq!(&*vec, slice);
+    }
 }
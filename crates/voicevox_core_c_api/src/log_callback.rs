@@ -0,0 +1,124 @@
+use std::{
+    ffi::{c_char, c_void, CString},
+    io,
+    sync::RwLock,
+};
+
+use tracing::{Level, Metadata};
+use tracing_subscriber::fmt::MakeWriter;
+
+use crate::VoicevoxLogLevel;
+
+/// ::voicevox_set_log_callback で設定されたコールバック。
+///
+/// 設定されている間、ログは標準エラー出力ではなくこちらに送られる。
+static LOG_CALLBACK: RwLock<Option<LogCallback>> = RwLock::new(None);
+
+/// ::voicevox_set_log_callback で設定する、ログを受け取るコールバック。
+pub type VoicevoxLogCallback =
+    unsafe extern "C" fn(level: VoicevoxLogLevel, message: *const c_char, user_data: *mut c_void);
+
+struct LogCallback {
+    callback: VoicevoxLogCallback,
+    user_data: UserData,
+    level: VoicevoxLogLevel,
+}
+
+struct UserData(*mut c_void);
+
+// SAFETY: `user_data`をどのスレッドから使ってもよいことは、`voicevox_set_log_callback`の安全性要件
+// としている。
+unsafe impl Send for UserData {}
+unsafe impl Sync for UserData {}
+
+pub(crate) fn set(
+    callback: Option<VoicevoxLogCallback>,
+    user_data: *mut c_void,
+    level: VoicevoxLogLevel,
+) {
+    *LOG_CALLBACK.write().unwrap() = callback.map(|callback| LogCallback {
+        callback,
+        user_data: UserData(user_data),
+        level,
+    });
+}
+
+pub(crate) fn is_set() -> bool {
+    LOG_CALLBACK.read().unwrap().is_some()
+}
+
+/// コールバックが設定されており、かつ`level`のログを受け取るかどうか。
+pub(crate) fn enabled(level: &Level) -> bool {
+    LOG_CALLBACK
+        .read()
+        .unwrap()
+        .as_ref()
+        .map_or(false, |callback| {
+            VoicevoxLogLevel::from(*level) <= callback.level
+        })
+}
+
+/// コールバックが設定されていれば`message`を渡し、`true`を返す。
+///
+/// `level`がコールバックの受け取るレベルより詳細な場合は、何もせずに`true`を返す。
+pub(crate) fn send(level: Level, message: &str) -> bool {
+    let callback = LOG_CALLBACK.read().unwrap();
+    let Some(callback) = &*callback else {
+        return false;
+    };
+    let level = VoicevoxLogLevel::from(level);
+    if level <= callback.level {
+        let message = CString::new(message.replace('\0', "\\0")).expect("should not contain NUL");
+        // SAFETY: `voicevox_set_log_callback`の安全性要件による。
+        unsafe {
+            (callback.callback)(level, message.as_ptr(), callback.user_data.0);
+        }
+    }
+    true
+}
+
+/// `tracing-subscriber`の出力先として、ログをコールバックに送る。
+pub(crate) struct MakeCallbackWriter;
+
+impl<'a> MakeWriter<'a> for MakeCallbackWriter {
+    type Writer = CallbackWriter;
+
+    fn make_writer(&'a self) -> Self::Writer {
+        CallbackWriter::new(Level::INFO)
+    }
+
+    fn make_writer_for(&'a self, meta: &Metadata<'_>) -> Self::Writer {
+        CallbackWriter::new(*meta.level())
+    }
+}
+
+/// イベント1つ分の出力を溜め、破棄されるときにまとめてコールバックに送る。
+pub(crate) struct CallbackWriter {
+    level: Level,
+    buf: Vec<u8>,
+}
+
+impl CallbackWriter {
+    fn new(level: Level) -> Self {
+        Self { level, buf: vec![] }
+    }
+}
+
+impl io::Write for CallbackWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.buf.extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+impl Drop for CallbackWriter {
+    fn drop(&mut self) {
+        if !self.buf.is_empty() {
+            send(self.level, String::from_utf8_lossy(&self.buf).trim_end());
+        }
+    }
+}
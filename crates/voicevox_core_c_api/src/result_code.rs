@@ -1,10 +1,11 @@
 use std::ffi::CStr;
 
 use cstr::cstr;
+use strum::{EnumIter, IntoEnumIterator as _};
 
 /// 処理結果を示す結果コード。
 #[repr(i32)]
-#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+#[derive(Debug, PartialEq, Eq, Clone, Copy, EnumIter)]
 #[allow(non_camel_case_types)]
 pub enum VoicevoxResultCode {
     // C でのenum定義に合わせて大文字で定義している
@@ -57,6 +58,35 @@ pub enum VoicevoxResultCode {
     VOICEVOX_RESULT_INVALID_USER_DICT_WORD_ERROR = 24,
     /// UUIDの変換に失敗した
     VOICEVOX_RESULT_INVALID_UUID_ERROR = 25,
+    /// 制限時間内に音声モデルの読み込みが完了しなかった
+    VOICEVOX_RESULT_LOAD_MODEL_TIMEOUT_ERROR = 28,
+    /// アクセント句やモーラの位置が範囲外だった
+    VOICEVOX_RESULT_INVALID_ACCENT_PHRASE_POSITION_ERROR = 29,
+    /// 入力テキストが長すぎた
+    VOICEVOX_RESULT_TEXT_TOO_LONG_ERROR = 30,
+    /// 出力先のバッファが小さすぎた
+    VOICEVOX_RESULT_BUFFER_TOO_SMALL_ERROR = 31,
+    /// 入力テキスト中のアクセント指定が不正だった
+    VOICEVOX_RESULT_INVALID_ACCENT_HINT_ERROR = 32,
+    /// 対応していないバージョンの音声モデルだった
+    VOICEVOX_RESULT_UNSUPPORTED_MODEL_VERSION_ERROR = 33,
+    /// 音声モデルが処理に必要なネットワークを含んでいなかった
+    VOICEVOX_RESULT_MISSING_NETWORK_ERROR = 34,
+    /// バイナリ形式のデータが不正だった
+    VOICEVOX_RESULT_INVALID_BINARY_DATA_ERROR = 35,
+    /// 出力先への書き込みに失敗した
+    VOICEVOX_RESULT_WRITE_OUTPUT_ERROR = 36,
+    /// 音声合成に使えないアクセント句が含まれていた
+    VOICEVOX_RESULT_INCONSISTENT_ACCENT_PHRASE_ERROR = 37,
+    /// 出力のサンプリングレートに対応していなかった
+    VOICEVOX_RESULT_UNSUPPORTED_SAMPLING_RATE_ERROR = 38,
+}
+
+impl VoicevoxResultCode {
+    /// [`voicevox_core::Error::code`]の値に対応する結果コード。対応するものが無ければ`None`を返す。
+    pub(crate) fn from_code(code: u32) -> Option<Self> {
+        Self::iter().find(|&result_code| result_code as u32 == code)
+    }
 }
 
 pub(crate) const fn error_result_to_message(result_code: VoicevoxResultCode) -> &'static CStr {
@@ -104,5 +134,30 @@ pub(crate) const fn error_result_to_message(result_code: VoicevoxResultCode) ->
             cstr!("ユーザー辞書の単語のバリデーションに失敗しました")
         }
         VOICEVOX_RESULT_INVALID_UUID_ERROR => cstr!("UUIDの変換に失敗しました"),
+        VOICEVOX_RESULT_LOAD_MODEL_TIMEOUT_ERROR => {
+            cstr!("制限時間内に音声モデルの読み込みが完了しませんでした")
+        }
+        VOICEVOX_RESULT_INVALID_ACCENT_PHRASE_POSITION_ERROR => {
+            cstr!("アクセント句やモーラの位置が範囲外です")
+        }
+        VOICEVOX_RESULT_TEXT_TOO_LONG_ERROR => cstr!("入力テキストが長すぎます"),
+        VOICEVOX_RESULT_BUFFER_TOO_SMALL_ERROR => cstr!("出力先のバッファが小さすぎます"),
+        VOICEVOX_RESULT_INVALID_ACCENT_HINT_ERROR => {
+            cstr!("入力テキスト中のアクセント指定が不正です")
+        }
+        VOICEVOX_RESULT_UNSUPPORTED_MODEL_VERSION_ERROR => {
+            cstr!("対応していないバージョンの音声モデルです")
+        }
+        VOICEVOX_RESULT_MISSING_NETWORK_ERROR => {
+            cstr!("音声モデルが処理に必要なネットワークを含んでいません")
+        }
+        VOICEVOX_RESULT_INVALID_BINARY_DATA_ERROR => cstr!("バイナリ形式のデータが不正です"),
+        VOICEVOX_RESULT_WRITE_OUTPUT_ERROR => cstr!("出力先への書き込みに失敗しました"),
+        VOICEVOX_RESULT_INCONSISTENT_ACCENT_PHRASE_ERROR => {
+            cstr!("音声合成に使えないアクセント句が含まれています")
+        }
+        VOICEVOX_RESULT_UNSUPPORTED_SAMPLING_RATE_ERROR => {
+            cstr!("対応していない出力のサンプリングレートです")
+        }
     }
 }
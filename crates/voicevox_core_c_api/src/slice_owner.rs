@@ -15,6 +15,9 @@ use std::{cell::UnsafeCell, collections::BTreeMap, ptr::NonNull, sync::Mutex};
 /// る。この構造体はその"所有者"であり、実際にRustのオブジェクトを保持し続ける。
 pub(crate) static U8_SLICE_OWNER: SliceOwner<u8> = SliceOwner::new();
 
+/// Cの世界に貸し出す`[f32]`の所有者。詳しくは[`U8_SLICE_OWNER`]を参照。
+pub(crate) static F32_SLICE_OWNER: SliceOwner<f32> = SliceOwner::new();
+
 pub(crate) struct SliceOwner<T> {
     slices: Mutex<BTreeMap<usize, UnsafeCell<Box<[T]>>>>,
 }
@@ -57,6 +57,8 @@ pub(crate) struct Symbols<'lib> {
     >,
     pub(crate) voicevox_synthesizer_create_metas_json:
         Symbol<'lib, unsafe extern "C" fn(*const VoicevoxSynthesizer) -> *mut c_char>,
+    pub(crate) voicevox_synthesizer_get_loaded_styles_json:
+        Symbol<'lib, unsafe extern "C" fn(*const VoicevoxSynthesizer) -> *mut c_char>,
     pub(crate) voicevox_create_supported_devices_json:
         Symbol<'lib, unsafe extern "C" fn(*mut *mut c_char) -> VoicevoxResultCode>,
     pub(crate) voicevox_make_default_audio_query_options:
@@ -84,6 +86,29 @@ pub(crate) struct Symbols<'lib> {
             *mut *mut u8,
         ) -> VoicevoxResultCode,
     >,
+    pub(crate) voicevox_synthesizer_synthesis_into: Symbol<
+        'lib,
+        unsafe extern "C" fn(
+            *const VoicevoxSynthesizer,
+            *const c_char,
+            VoicevoxStyleId,
+            VoicevoxSynthesisOptions,
+            *mut u8,
+            usize,
+            *mut usize,
+        ) -> VoicevoxResultCode,
+    >,
+    pub(crate) voicevox_synthesizer_synthesis_stream: Symbol<
+        'lib,
+        unsafe extern "C" fn(
+            *const VoicevoxSynthesizer,
+            *const c_char,
+            VoicevoxStyleId,
+            VoicevoxSynthesisOptions,
+            unsafe extern "C" fn(*const u8, usize, *mut c_void),
+            *mut c_void,
+        ) -> VoicevoxResultCode,
+    >,
     pub(crate) voicevox_make_default_tts_options:
         Symbol<'lib, unsafe extern "C" fn() -> VoicevoxTtsOptions>,
     pub(crate) voicevox_synthesizer_tts: Symbol<
@@ -97,10 +122,61 @@ pub(crate) struct Symbols<'lib> {
             *mut *mut u8,
         ) -> VoicevoxResultCode,
     >,
+    pub(crate) voicevox_synthesizer_predict_duration: Symbol<
+        'lib,
+        unsafe extern "C" fn(
+            *const VoicevoxSynthesizer,
+            usize,
+            *const i64,
+            VoicevoxStyleId,
+            *mut usize,
+            *mut *mut f32,
+        ) -> VoicevoxResultCode,
+    >,
+    pub(crate) voicevox_synthesizer_predict_intonation: Symbol<
+        'lib,
+        unsafe extern "C" fn(
+            *const VoicevoxSynthesizer,
+            usize,
+            *const i64,
+            *const i64,
+            *const i64,
+            *const i64,
+            *const i64,
+            *const i64,
+            VoicevoxStyleId,
+            *mut usize,
+            *mut *mut f32,
+        ) -> VoicevoxResultCode,
+    >,
+    pub(crate) voicevox_synthesizer_decode: Symbol<
+        'lib,
+        unsafe extern "C" fn(
+            *const VoicevoxSynthesizer,
+            usize,
+            usize,
+            *const f32,
+            *const f32,
+            VoicevoxStyleId,
+            *mut usize,
+            *mut *mut f32,
+        ) -> VoicevoxResultCode,
+    >,
     pub(crate) voicevox_json_free: Symbol<'lib, unsafe extern "C" fn(*mut c_char)>,
     pub(crate) voicevox_wav_free: Symbol<'lib, unsafe extern "C" fn(*mut u8)>,
+    pub(crate) voicevox_float_array_free: Symbol<'lib, unsafe extern "C" fn(*mut f32)>,
     pub(crate) voicevox_error_result_to_message:
         Symbol<'lib, unsafe extern "C" fn(VoicevoxResultCode) -> *const c_char>,
+    pub(crate) voicevox_get_last_error_message:
+        Symbol<'lib, unsafe extern "C" fn() -> *const c_char>,
+    pub(crate) voicevox_set_log_callback: Symbol<
+        'lib,
+        unsafe extern "C" fn(
+            Option<unsafe extern "C" fn(VoicevoxLogLevel, *const c_char, *mut c_void)>,
+            *mut c_void,
+            VoicevoxLogLevel,
+        ),
+    >,
 
     pub(crate) initialize: Symbol<'lib, unsafe extern "C" fn(bool, c_int, bool) -> bool>,
     pub(crate) load_model: Symbol<'lib, unsafe extern "C" fn(i64) -> bool>,
@@ -204,16 +280,25 @@ impl<'lib> Symbols<'lib> {
             voicevox_synthesizer_is_gpu_mode,
             voicevox_synthesizer_is_loaded_voice_model,
             voicevox_synthesizer_create_metas_json,
+            voicevox_synthesizer_get_loaded_styles_json,
             voicevox_create_supported_devices_json,
             voicevox_make_default_audio_query_options,
             voicevox_synthesizer_create_audio_query,
             voicevox_make_default_synthesis_options,
             voicevox_synthesizer_synthesis,
+            voicevox_synthesizer_synthesis_into,
+            voicevox_synthesizer_synthesis_stream,
             voicevox_make_default_tts_options,
             voicevox_synthesizer_tts,
+            voicevox_synthesizer_predict_duration,
+            voicevox_synthesizer_predict_intonation,
+            voicevox_synthesizer_decode,
             voicevox_json_free,
             voicevox_wav_free,
+            voicevox_float_array_free,
             voicevox_error_result_to_message,
+            voicevox_get_last_error_message,
+            voicevox_set_log_callback,
             initialize,
             load_model,
             is_model_loaded,
@@ -272,6 +357,17 @@ pub(crate) enum VoicevoxResultCode {
     VOICEVOX_RESULT_USE_USER_DICT_ERROR = 23,
     VOICEVOX_RESULT_INVALID_USER_DICT_WORD_ERROR = 24,
     VOICEVOX_RESULT_INVALID_UUID_ERROR = 25,
+    VOICEVOX_RESULT_LOAD_MODEL_TIMEOUT_ERROR = 28,
+    VOICEVOX_RESULT_INVALID_ACCENT_PHRASE_POSITION_ERROR = 29,
+    VOICEVOX_RESULT_TEXT_TOO_LONG_ERROR = 30,
+    VOICEVOX_RESULT_BUFFER_TOO_SMALL_ERROR = 31,
+    VOICEVOX_RESULT_INVALID_ACCENT_HINT_ERROR = 32,
+    VOICEVOX_RESULT_UNSUPPORTED_MODEL_VERSION_ERROR = 33,
+    VOICEVOX_RESULT_MISSING_NETWORK_ERROR = 34,
+    VOICEVOX_RESULT_INVALID_BINARY_DATA_ERROR = 35,
+    VOICEVOX_RESULT_WRITE_OUTPUT_ERROR = 36,
+    VOICEVOX_RESULT_INCONSISTENT_ACCENT_PHRASE_ERROR = 37,
+    VOICEVOX_RESULT_UNSUPPORTED_SAMPLING_RATE_ERROR = 38,
 }
 
 #[repr(i32)]
@@ -280,6 +376,16 @@ pub(crate) enum VoicevoxAccelerationMode {
     VOICEVOX_ACCELERATION_MODE_CPU = 1,
 }
 
+#[repr(i32)]
+#[allow(non_camel_case_types, dead_code)]
+pub(crate) enum VoicevoxLogLevel {
+    VOICEVOX_LOG_LEVEL_ERROR = 1,
+    VOICEVOX_LOG_LEVEL_WARN = 2,
+    VOICEVOX_LOG_LEVEL_INFO = 3,
+    VOICEVOX_LOG_LEVEL_DEBUG = 4,
+    VOICEVOX_LOG_LEVEL_TRACE = 5,
+}
+
 #[repr(C)]
 pub(crate) struct VoicevoxInitializeOptions {
     pub(crate) acceleration_mode: VoicevoxAccelerationMode,
@@ -5,6 +5,7 @@ import com.google.gson.annotations.Expose;
 import com.google.gson.annotations.SerializedName;
 import java.lang.ref.Cleaner;
 import javax.annotation.Nonnull;
+import javax.annotation.Nullable;
 
 /** 音声モデル。 */
 public class VoiceModel extends Dll {
@@ -67,6 +68,12 @@ public class VoiceModel extends Dll {
     @Nonnull
     final String version;
 
+    /** 話者の利用規約。VVMファイルが利用規約を含まない場合は{@code null}。 */
+    @SerializedName("terms")
+    @Expose
+    @Nullable
+    final String terms;
+
     private SpeakerMeta() {
       // GSONからコンストラクトするため、このメソッドは呼ばれることは無い。
       // このメソッドは@Nonnullを満たすために必要。
@@ -74,6 +81,7 @@ public class VoiceModel extends Dll {
       this.styles = new StyleMeta[0];
       this.speakerUuid = "";
       this.version = "";
+      this.terms = null;
     }
   }
 
@@ -150,7 +150,7 @@ unsafe extern "system" fn Java_jp_hiroshiba_voicevoxcore_Synthesizer_rsAudioQuer
             let internal = internal.lock().unwrap();
             let options = voicevox_core::AudioQueryOptions {
                 kana: kana != 0,
-                // ..Default::default()
+                ..Default::default()
             };
             RUNTIME.block_on(internal.audio_query(
                 &text,
@@ -187,7 +187,7 @@ unsafe extern "system" fn Java_jp_hiroshiba_voicevoxcore_Synthesizer_rsAccentPhr
             let internal = internal.lock().unwrap();
             let options = voicevox_core::AccentPhrasesOptions {
                 kana: kana != 0,
-                // ..Default::default()
+                ..Default::default()
             };
             RUNTIME.block_on(internal.create_accent_phrases(
                 &text,
@@ -318,7 +318,14 @@ unsafe extern "system" fn Java_jp_hiroshiba_voicevoxcore_Synthesizer_rsSynthesis
             let internal = internal.lock().unwrap();
             let options = voicevox_core::SynthesisOptions {
                 enable_interrogative_upspeak: enable_interrogative_upspeak != 0,
-                // ..Default::default()
+                capture_tensors: None,
+                quality: Default::default(),
+                output_gain_db: 0.,
+                emit_cue_points: false,
+                voice_mode: Default::default(),
+                seed: None,
+                eq: vec![],
+                prioritize_first_packet: false,
             };
             RUNTIME.block_on(internal.synthesis(
                 &audio_query,
@@ -355,7 +362,7 @@ unsafe extern "system" fn Java_jp_hiroshiba_voicevoxcore_Synthesizer_rsTts<'loca
             let options = voicevox_core::TtsOptions {
                 kana: kana != 0,
                 enable_interrogative_upspeak: enable_interrogative_upspeak != 0,
-                // ..Default::default()
+                ..Default::default()
             };
             RUNTIME.block_on(internal.tts(
                 &text,
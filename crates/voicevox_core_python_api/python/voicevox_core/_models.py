@@ -34,6 +34,9 @@ class SpeakerMeta:
     version: str
     """話者のUUID。"""
 
+    terms: Optional[str] = None
+    """話者の利用規約。VVMファイルが利用規約を含まない場合は ``None`` 。"""
+
 
 @pydantic.dataclasses.dataclass
 class SupportedDevices:
@@ -1,5 +1,5 @@
 from pathlib import Path
-from typing import Dict, Final, List, Literal, Union, TYPE_CHECKING
+from typing import Dict, Final, List, Literal, Optional, Union, TYPE_CHECKING
 from uuid import UUID
 
 import numpy as np
@@ -53,6 +53,10 @@ class VoiceModel:
     def metas(self) -> List[SpeakerMeta]:
         """メタ情報。"""
         ...
+    @property
+    def terms(self) -> Optional[str]:
+        """利用規約。VVMファイルが利用規約を含まない場合は ``None`` 。"""
+        ...
 
 class OpenJtalk:
     """
@@ -298,6 +302,22 @@ class Synthesizer:
         enable_interrogative_upspeak
             疑問文の調整を有効にするかどうか。
 
+        Returns
+        -------
+        WAVデータ。
+        """
+        ...
+    async def preview(self, style_id: int) -> bytes:
+        """
+        スタイルの試聴用の短い音声を生成する。
+
+        結果はスタイルごとにキャッシュされ、音声モデルの読み込みを解除したときに破棄される。
+
+        Parameters
+        ----------
+        style_id
+            スタイルID。
+
         Returns
         -------
         WAVデータ。
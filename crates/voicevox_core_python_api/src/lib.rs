@@ -85,6 +85,11 @@ impl VoiceModel {
     fn metas<'py>(&self, py: Python<'py>) -> Vec<&'py PyAny> {
         to_pydantic_voice_model_meta(self.model.metas(), py).unwrap()
     }
+
+    #[getter]
+    fn terms(&self) -> Option<&str> {
+        self.model.terms().as_deref()
+    }
 }
 
 #[pyclass]
@@ -140,6 +145,7 @@ impl Synthesizer {
                     acceleration_mode,
                     cpu_num_threads,
                     load_all_models,
+                    ..Default::default()
                 },
             )
             .await
@@ -228,7 +234,14 @@ impl Synthesizer {
                 let audio_query = synthesizer
                     .lock()
                     .await
-                    .audio_query(&text, StyleId::new(style_id), &AudioQueryOptions { kana })
+                    .audio_query(
+                        &text,
+                        StyleId::new(style_id),
+                        &AudioQueryOptions {
+                            kana,
+                            ..Default::default()
+                        },
+                    )
                     .await
                     .into_py_result()?;
 
@@ -261,7 +274,10 @@ impl Synthesizer {
                     .create_accent_phrases(
                         &text,
                         StyleId::new(style_id),
-                        &AccentPhrasesOptions { kana },
+                        &AccentPhrasesOptions {
+                            kana,
+                            ..Default::default()
+                        },
                     )
                     .await
                     .into_py_result()?;
@@ -344,6 +360,14 @@ impl Synthesizer {
                         StyleId::new(style_id),
                         &SynthesisOptions {
                             enable_interrogative_upspeak,
+                            capture_tensors: None,
+                            quality: Default::default(),
+                            output_gain_db: 0.,
+                            emit_cue_points: false,
+                            voice_mode: Default::default(),
+                            seed: None,
+                            eq: vec![],
+                            prioritize_first_packet: false,
                         },
                     )
                     .await
@@ -371,6 +395,7 @@ impl Synthesizer {
         let options = TtsOptions {
             kana,
             enable_interrogative_upspeak,
+            ..Default::default()
         };
         let synthesizer = self.synthesizer.get()?.clone();
         let text = text.to_owned();
@@ -389,6 +414,24 @@ impl Synthesizer {
         )
     }
 
+    fn preview<'py>(&self, style_id: u32, py: Python<'py>) -> PyResult<&'py PyAny> {
+        let style_id = StyleId::new(style_id);
+        let synthesizer = self.synthesizer.get()?.clone();
+        pyo3_asyncio::tokio::future_into_py_with_locals(
+            py,
+            pyo3_asyncio::tokio::get_current_locals(py)?,
+            async move {
+                let wav = synthesizer
+                    .lock()
+                    .await
+                    .preview(style_id)
+                    .await
+                    .into_py_result()?;
+                Python::with_gil(|py| Ok(PyBytes::new(py, &wav).to_object(py)))
+            },
+        )
+    }
+
     fn close(&mut self) {
         self.synthesizer.close()
     }